@@ -0,0 +1,288 @@
+//! Streaming, mergeable accumulators.
+//!
+//! `Moments` keeps the first four central moments up to date one value at a
+//! time (Welford's update, extended to third and fourth moments) and merges
+//! two partial results exactly using Pébay's pairwise formulae. That means
+//! each thread or file can summarise its own shard and the results combine
+//! into the same numbers a single pass over all the data would produce.
+
+use crate::{iqr_outliers, median, percentile, StatsError, Summary};
+
+/// Running count, mean, central moments, min and max in O(1) memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Moments {
+    n: usize,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for Moments {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Moments {
+    pub fn new() -> Self {
+        Self { n: 0, mean: 0.0, m2: 0.0, m3: 0.0, m4: 0.0, min: f64::INFINITY, max: f64::NEG_INFINITY }
+    }
+
+    /// Fold a single observation into the running moments.
+    pub fn push(&mut self, x: f64) {
+        let n1 = self.n as f64;
+        self.n += 1;
+        let n = self.n as f64;
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term1 = delta * delta_n * n1;
+
+        self.mean += delta_n;
+        self.m4 += term1 * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2 - 4.0 * delta_n * self.m3;
+        self.m3 += term1 * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term1;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    /// Combine another partial result into this one. The outcome is identical
+    /// (up to rounding) to having pushed both streams into a single accumulator.
+    pub fn merge(&mut self, other: &Moments) {
+        if other.n == 0 { return; }
+        if self.n == 0 { *self = other.clone(); return; }
+
+        let na = self.n as f64;
+        let nb = other.n as f64;
+        let n = na + nb;
+        let delta = other.mean - self.mean;
+        let delta2 = delta * delta;
+
+        let m2 = self.m2 + other.m2 + delta2 * na * nb / n;
+        let m3 = self.m3 + other.m3
+            + delta2 * delta * na * nb * (na - nb) / (n * n)
+            + 3.0 * delta * (na * other.m2 - nb * self.m2) / n;
+        let m4 = self.m4 + other.m4
+            + delta2 * delta2 * na * nb * (na * na - na * nb + nb * nb) / (n * n * n)
+            + 6.0 * delta2 * (na * na * other.m2 + nb * nb * self.m2) / (n * n)
+            + 4.0 * delta * (na * other.m3 - nb * self.m3) / n;
+
+        self.mean += delta * nb / n;
+        self.m2 = m2;
+        self.m3 = m3;
+        self.m4 = m4;
+        self.n += other.n;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn count(&self) -> usize {
+        self.n
+    }
+
+    pub fn mean(&self) -> Result<f64, StatsError> {
+        if self.n == 0 { return Err(StatsError::Empty); }
+        Ok(self.mean)
+    }
+
+    /// Population variance (divides by N)
+    pub fn variance(&self) -> Result<f64, StatsError> {
+        if self.n == 0 { return Err(StatsError::Empty); }
+        Ok(self.m2 / self.n as f64)
+    }
+
+    /// Sample variance (divides by N-1)
+    pub fn sample_variance(&self) -> Result<f64, StatsError> {
+        if self.n < 2 { return Err(StatsError::InsufficientData { needed: 2, got: self.n }); }
+        Ok(self.m2 / (self.n - 1) as f64)
+    }
+
+    pub fn std_dev(&self) -> Result<f64, StatsError> {
+        Ok(self.variance()?.sqrt())
+    }
+
+    /// Pearson's moment skewness
+    pub fn skewness(&self) -> Result<f64, StatsError> {
+        if self.n == 0 { return Err(StatsError::Empty); }
+        Ok((self.n as f64).sqrt() * self.m3 / self.m2.powf(1.5))
+    }
+
+    /// Excess kurtosis (0 for normal distribution)
+    pub fn kurtosis(&self) -> Result<f64, StatsError> {
+        if self.n == 0 { return Err(StatsError::Empty); }
+        Ok(self.n as f64 * self.m4 / (self.m2 * self.m2) - 3.0)
+    }
+
+    pub fn min(&self) -> Result<f64, StatsError> {
+        if self.n == 0 { return Err(StatsError::Empty); }
+        Ok(self.min)
+    }
+
+    pub fn max(&self) -> Result<f64, StatsError> {
+        if self.n == 0 { return Err(StatsError::Empty); }
+        Ok(self.max)
+    }
+}
+
+impl Extend<f64> for Moments {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter { self.push(x); }
+    }
+}
+
+impl FromIterator<f64> for Moments {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut m = Moments::new();
+        m.extend(iter);
+        m
+    }
+}
+
+/// Mergeable accumulator that can produce a full `Summary`.
+///
+/// Moments are maintained incrementally via `Moments`. Order statistics
+/// (median, percentiles, IQR outliers) cannot be merged exactly from a
+/// fixed-size state, so the observed values are retained as well.
+/// Use `Moments` directly when O(1) memory matters more than quantiles.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Accumulator {
+    moments: Moments,
+    values: Vec<f64>,
+}
+
+impl Accumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, x: f64) {
+        self.moments.push(x);
+        self.values.push(x);
+    }
+
+    /// Combine another partial result into this one.
+    pub fn merge(&mut self, other: &Accumulator) {
+        self.moments.merge(&other.moments);
+        self.values.extend_from_slice(&other.values);
+    }
+
+    pub fn count(&self) -> usize {
+        self.moments.count()
+    }
+
+    pub fn moments(&self) -> &Moments {
+        &self.moments
+    }
+
+    pub fn summary(&self) -> Result<Summary, StatsError> {
+        let m = &self.moments;
+        Ok(Summary {
+            mean: m.mean()?,
+            std_dev: m.std_dev()?,
+            median: median(&self.values)?,
+            p5: percentile(&self.values, 0.05)?,
+            p95: percentile(&self.values, 0.95)?,
+            skewness: m.skewness()?,
+            kurtosis: m.kurtosis()?,
+            n_outliers: iqr_outliers(&self.values)?.len(),
+        })
+    }
+}
+
+impl Extend<f64> for Accumulator {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter { self.push(x); }
+    }
+}
+
+impl FromIterator<f64> for Accumulator {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut acc = Accumulator::new();
+        acc.extend(iter);
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{kurtosis, mean, sample_variance, skewness, summarise, variance};
+
+    fn skewed_data() -> Vec<f64> {
+        (1..=200).map(|i| (i as f64 * 0.37).sin() * 10.0 + (i as f64).sqrt()).collect()
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9 * (1.0 + b.abs())
+    }
+
+    #[test]
+    fn streaming_moments_match_batch_functions() {
+        let data = skewed_data();
+        let m: Moments = data.iter().copied().collect();
+        assert!(close(m.mean().unwrap(), mean(&data).unwrap()));
+        assert!(close(m.variance().unwrap(), variance(&data).unwrap()));
+        assert!(close(m.sample_variance().unwrap(), sample_variance(&data).unwrap()));
+        assert!(close(m.skewness().unwrap(), skewness(&data).unwrap()));
+        assert!(close(m.kurtosis().unwrap(), kurtosis(&data).unwrap()));
+    }
+
+    #[test]
+    fn merged_shards_equal_single_pass() {
+        let data = skewed_data();
+        let whole: Moments = data.iter().copied().collect();
+        let mut merged = Moments::new();
+        for shard in data.chunks(37) {
+            merged.merge(&shard.iter().copied().collect());
+        }
+        assert_eq!(merged.count(), whole.count());
+        assert_eq!(merged.min(), whole.min());
+        assert_eq!(merged.max(), whole.max());
+        assert!(close(merged.mean().unwrap(), whole.mean().unwrap()));
+        assert!(close(merged.variance().unwrap(), whole.variance().unwrap()));
+        assert!(close(merged.skewness().unwrap(), whole.skewness().unwrap()));
+        assert!(close(merged.kurtosis().unwrap(), whole.kurtosis().unwrap()));
+    }
+
+    #[test]
+    fn merging_empty_is_identity() {
+        let mut m: Moments = [1.0, 2.0, 4.0].into_iter().collect();
+        let before = m.clone();
+        m.merge(&Moments::new());
+        assert_eq!(m, before);
+
+        let mut empty = Moments::new();
+        empty.merge(&before);
+        assert_eq!(empty, before);
+    }
+
+    #[test]
+    fn empty_accumulator_is_error() {
+        let m = Moments::new();
+        assert_eq!(m.mean().unwrap_err(), StatsError::Empty);
+        assert_eq!(m.sample_variance().unwrap_err(), StatsError::InsufficientData { needed: 2, got: 0 });
+        assert_eq!(Accumulator::new().summary().unwrap_err(), StatsError::Empty);
+    }
+
+    #[test]
+    fn merged_accumulators_summarise_like_full_data() {
+        let mut data = skewed_data();
+        data.push(500.0);
+        let (left, right) = data.split_at(80);
+        let mut acc: Accumulator = left.iter().copied().collect();
+        acc.merge(&right.iter().copied().collect());
+
+        let s = acc.summary().unwrap();
+        let expected = summarise(&data).unwrap();
+        assert!(close(s.mean, expected.mean));
+        assert!(close(s.std_dev, expected.std_dev));
+        assert!(close(s.skewness, expected.skewness));
+        assert!(close(s.kurtosis, expected.kurtosis));
+        assert_eq!(s.median, expected.median);
+        assert_eq!(s.p95, expected.p95);
+        assert_eq!(s.n_outliers, expected.n_outliers);
+    }
+}
@@ -0,0 +1,230 @@
+//! Bootstrap confidence intervals for arbitrary statistics.
+//!
+//! Resample the data with replacement many times, recompute the statistic on
+//! each resample, and read the interval off the resulting distribution. Works
+//! for medians, percentiles or any custom metric without a closed-form
+//! standard error.
+//!
+//! Every resample draws from its own RNG, seeded with the index-th output of
+//! a SplitMix64 stream started at `seed`, so the serial and parallel paths
+//! produce identical intervals for the same seed. Mixing matters: with
+//! `seed + index`, seeds 42 and 43 would share all but one resample.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::distributions::{normal_cdf, normal_quantile};
+use crate::{percentile, StatsError};
+
+/// How many resamples to draw and at what confidence level.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BootstrapConfig {
+    pub resamples: usize,
+    /// Two-sided confidence level in (0, 1), e.g. 0.95
+    pub confidence: f64,
+    pub seed: u64,
+    /// Spread resampling (and the BCa jackknife) across threads with rayon.
+    /// Needs the `rayon` feature; without it the work runs serially.
+    pub parallel: bool,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self { resamples: 2000, confidence: 0.95, seed: 42, parallel: false }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConfidenceInterval {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl ConfidenceInterval {
+    pub fn contains(&self, x: f64) -> bool {
+        self.lower <= x && x <= self.upper
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BootstrapResult {
+    /// Statistic evaluated on the original data
+    pub estimate: f64,
+    /// Standard deviation of the bootstrap distribution
+    pub std_error: f64,
+    /// Mean of the bootstrap distribution minus the estimate
+    pub bias: f64,
+    /// Quantiles of the bootstrap distribution
+    pub percentile: ConfidenceInterval,
+    /// Percentile interval reflected around the estimate (2θ̂ - q)
+    pub basic: ConfidenceInterval,
+    /// Bias-corrected and accelerated interval (Efron 1987)
+    pub bca: ConfidenceInterval,
+}
+
+/// Bootstrap percentile, basic and BCa intervals for `statistic` over `data`.
+/// Pass any `Fn(&[f64]) -> Result<f64, StatsError>`, e.g. `median` or
+/// `|d| percentile(d, 0.99)`.
+pub fn bootstrap<F>(data: &[f64], statistic: F, config: &BootstrapConfig) -> Result<BootstrapResult, StatsError>
+where
+    F: Fn(&[f64]) -> Result<f64, StatsError> + Sync,
+{
+    if data.is_empty() { return Err(StatsError::Empty); }
+    if data.len() < 2 { return Err(StatsError::InsufficientData { needed: 2, got: data.len() }); }
+    if config.resamples < 2 {
+        return Err(StatsError::InsufficientData { needed: 2, got: config.resamples });
+    }
+    if !(config.confidence > 0.0 && config.confidence < 1.0) {
+        return Err(StatsError::InvalidConfidence(config.confidence));
+    }
+
+    let estimate = statistic(data)?;
+    let resample = |b: usize| {
+        let mut rng = StdRng::seed_from_u64(resample_seed(config.seed, b as u64));
+        let sample: Vec<f64> = (0..data.len()).map(|_| data[rng.gen_range(0..data.len())]).collect();
+        statistic(&sample)
+    };
+    let thetas = map_indices(config.resamples, config.parallel, resample)?;
+
+    let b = thetas.len() as f64;
+    let theta_mean = thetas.iter().sum::<f64>() / b;
+    let std_error = (thetas.iter().map(|t| (t - theta_mean).powi(2)).sum::<f64>() / (b - 1.0)).sqrt();
+
+    let alpha = (1.0 - config.confidence) / 2.0;
+    let lo = percentile(&thetas, alpha)?;
+    let hi = percentile(&thetas, 1.0 - alpha)?;
+
+    // BCa bias correction: how far the bootstrap distribution is shifted from
+    // the estimate, counting ties as half so a degenerate distribution gives z0 = 0.
+    let below = thetas.iter().filter(|&&t| t < estimate).count() as f64;
+    let equal = thetas.iter().filter(|&&t| t == estimate).count() as f64;
+    let z0 = normal_quantile(((below + 0.5 * equal) / b).clamp(1.0 / b, 1.0 - 1.0 / b))?;
+
+    // BCa acceleration from the jackknife skewness of the statistic.
+    let jackknife = |i: usize| {
+        let loo: Vec<f64> = data.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, &x)| x).collect();
+        statistic(&loo)
+    };
+    let jack = map_indices(data.len(), config.parallel, jackknife)?;
+    let jack_mean = jack.iter().sum::<f64>() / jack.len() as f64;
+    let num: f64 = jack.iter().map(|t| (jack_mean - t).powi(3)).sum();
+    let den: f64 = jack.iter().map(|t| (jack_mean - t).powi(2)).sum::<f64>().powf(1.5);
+    let accel = if den > 0.0 { num / (6.0 * den) } else { 0.0 };
+
+    let adjust = |a: f64| -> Result<f64, StatsError> {
+        let z = z0 + normal_quantile(a)?;
+        Ok(normal_cdf(z0 + z / (1.0 - accel * z)))
+    };
+
+    Ok(BootstrapResult {
+        estimate,
+        std_error,
+        bias: theta_mean - estimate,
+        percentile: ConfidenceInterval { lower: lo, upper: hi },
+        basic: ConfidenceInterval { lower: 2.0 * estimate - hi, upper: 2.0 * estimate - lo },
+        bca: ConfidenceInterval {
+            lower: percentile(&thetas, adjust(alpha)?)?,
+            upper: percentile(&thetas, adjust(1.0 - alpha)?)?,
+        },
+    })
+}
+
+/// `f(0), ..., f(count - 1)` in order, on rayon's pool when `parallel` is set
+/// and the `rayon` feature is enabled
+fn map_indices<F>(count: usize, parallel: bool, f: F) -> Result<Vec<f64>, StatsError>
+where
+    F: Fn(usize) -> Result<f64, StatsError> + Send + Sync,
+{
+    #[cfg(feature = "rayon")]
+    if parallel { return (0..count).into_par_iter().map(f).collect(); }
+    #[cfg(not(feature = "rayon"))]
+    let _ = parallel;
+    (0..count).map(f).collect()
+}
+
+/// The b-th output of SplitMix64 seeded with `seed`: its state advances by
+/// the golden-ratio increment and each state is scrambled by a bijective mix.
+fn resample_seed(seed: u64, b: u64) -> u64 {
+    let mut z = seed.wrapping_add(b.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mean, median};
+    use rand_distr::{Distribution, Exp, Normal};
+
+    fn normal_sample(n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let dist = Normal::new(10.0, 2.0).unwrap();
+        (0..n).map(|_| dist.sample(&mut rng)).collect()
+    }
+
+    #[test]
+    fn median_interval_covers_true_median() {
+        let data = normal_sample(400, 7);
+        let r = bootstrap(&data, median, &BootstrapConfig::default()).unwrap();
+        assert!(r.percentile.contains(10.0), "{:?}", r.percentile);
+        assert!(r.bca.contains(10.0), "{:?}", r.bca);
+        assert!(r.percentile.lower < r.estimate && r.estimate < r.percentile.upper);
+    }
+
+    #[test]
+    fn same_seed_is_reproducible_and_parallel_matches_serial() {
+        let data = normal_sample(200, 1);
+        let p90 = |d: &[f64]| percentile(d, 0.9);
+        let config = BootstrapConfig { resamples: 500, ..BootstrapConfig::default() };
+        let a = bootstrap(&data, p90, &config).unwrap();
+        let b = bootstrap(&data, p90, &config).unwrap();
+        let par = bootstrap(&data, p90, &BootstrapConfig { parallel: true, ..config }).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a, par);
+    }
+
+    #[test]
+    fn neighbouring_seeds_share_no_resamples() {
+        // Reference outputs of SplitMix64 seeded with 0
+        assert_eq!(resample_seed(0, 0), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(resample_seed(0, 1), 0x6E78_9E6A_A1B9_65F4);
+        let seeds = |seed: u64| (0..2000).map(move |b| resample_seed(seed, b)).collect::<std::collections::HashSet<_>>();
+        assert_eq!(seeds(42).intersection(&seeds(43)).count(), 0);
+        let data = normal_sample(100, 2);
+        let a = bootstrap(&data, mean, &BootstrapConfig { seed: 42, ..BootstrapConfig::default() }).unwrap();
+        let b = bootstrap(&data, mean, &BootstrapConfig { seed: 43, ..BootstrapConfig::default() }).unwrap();
+        assert_ne!(a.std_error, b.std_error);
+    }
+
+    #[test]
+    fn mean_intervals_agree_with_normal_theory() {
+        let data = normal_sample(1000, 3);
+        let r = bootstrap(&data, mean, &BootstrapConfig::default()).unwrap();
+        // Standard error of the mean is sigma / sqrt(n) = 2 / sqrt(1000)
+        assert!((r.std_error - 0.0632).abs() < 0.01, "se = {}", r.std_error);
+        for ci in [r.percentile, r.basic, r.bca] {
+            assert!((ci.upper - ci.lower - 2.0 * 1.96 * 0.0632).abs() < 0.05, "{ci:?}");
+        }
+    }
+
+    #[test]
+    fn bca_shifts_right_for_skewed_data() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let dist = Exp::new(1.0).unwrap();
+        let data: Vec<f64> = (0..60).map(|_| dist.sample(&mut rng)).collect();
+        let r = bootstrap(&data, mean, &BootstrapConfig::default()).unwrap();
+        // Right-skewed data: the upper arm of the BCa interval is the longer one
+        assert!(r.bca.upper - r.estimate > r.estimate - r.bca.lower, "{:?}", r.bca);
+    }
+
+    #[test]
+    fn invalid_inputs_are_errors() {
+        let config = BootstrapConfig::default();
+        assert_eq!(bootstrap(&[], mean, &config).unwrap_err(), StatsError::Empty);
+        let bad = BootstrapConfig { confidence: 1.0, ..config };
+        assert_eq!(bootstrap(&[1.0, 2.0], mean, &bad).unwrap_err(), StatsError::InvalidConfidence(1.0));
+    }
+}
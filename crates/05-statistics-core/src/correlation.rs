@@ -0,0 +1,244 @@
+//! Bivariate correlation with significance tests, plus covariance and
+//! correlation matrices over multi-column data.
+
+use matrix_math::Matrix;
+
+use crate::distributions::{normal_sf, student_t_two_sided};
+use crate::hypothesis::ranks_with_ties;
+use crate::{check_finite, mean, StatsError, TestResult};
+
+/// A correlation coefficient together with the test of H0: no association.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Correlation {
+    pub coefficient: f64,
+    pub test: TestResult,
+}
+
+/// Sample covariance (divides by N-1)
+pub fn covariance(x: &[f64], y: &[f64]) -> Result<f64, StatsError> {
+    check_pair(x, y, 2)?;
+    let (mx, my) = (mean(x)?, mean(y)?);
+    let sxy: f64 = x.iter().zip(y).map(|(a, b)| (a - mx) * (b - my)).sum();
+    Ok(sxy / (x.len() - 1) as f64)
+}
+
+/// Pearson product-moment correlation, tested with t = r√((n-2)/(1-r²)) on n-2 df
+pub fn pearson(x: &[f64], y: &[f64]) -> Result<Correlation, StatsError> {
+    check_pair(x, y, 3)?;
+    let r = pearson_r(x, y)?;
+    Ok(Correlation { coefficient: r, test: r_to_t_test(r, x.len()) })
+}
+
+/// Spearman rank correlation: Pearson on average ranks, same t approximation
+pub fn spearman(x: &[f64], y: &[f64]) -> Result<Correlation, StatsError> {
+    check_pair(x, y, 3)?;
+    check_finite(x)?;
+    check_finite(y)?;
+    let (rx, _) = ranks_with_ties(x);
+    let (ry, _) = ranks_with_ties(y);
+    let r = pearson_r(&rx, &ry)?;
+    Ok(Correlation { coefficient: r, test: r_to_t_test(r, x.len()) })
+}
+
+/// Kendall's tau-b (tie-adjusted), tested with the normal approximation of S.
+/// O(n²) pair comparisons.
+pub fn kendall_tau(x: &[f64], y: &[f64]) -> Result<Correlation, StatsError> {
+    check_pair(x, y, 3)?;
+    check_finite(x)?;
+    check_finite(y)?;
+    let n = x.len();
+
+    let mut s = 0.0;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            // Concordant pairs add one, discordant subtract one, ties add nothing
+            let d = (x[i] - x[j]) * (y[i] - y[j]);
+            if d > 0.0 { s += 1.0; } else if d < 0.0 { s -= 1.0; }
+        }
+    }
+
+    let (tx1, tx2, tx3) = tie_terms(x);
+    let (ty1, ty2, ty3) = tie_terms(y);
+    let nf = n as f64;
+    let n0 = nf * (nf - 1.0) / 2.0;
+    let denom = ((n0 - tx1 / 2.0) * (n0 - ty1 / 2.0)).sqrt();
+    if denom == 0.0 { return Err(StatsError::ZeroVariance); }
+    let tau = s / denom;
+
+    let v0 = nf * (nf - 1.0) * (2.0 * nf + 5.0);
+    let var_s = (v0 - tx2 - ty2) / 18.0
+        + tx1 * ty1 / (2.0 * nf * (nf - 1.0))
+        + tx3 * ty3 / (9.0 * nf * (nf - 1.0) * (nf - 2.0));
+    let z = s / var_s.sqrt();
+    Ok(Correlation {
+        coefficient: tau,
+        test: TestResult { statistic: z, df: f64::INFINITY, p_value: 2.0 * normal_sf(z.abs()) },
+    })
+}
+
+/// Sample covariance matrix of `data` (rows = observations, columns = variables)
+pub fn covariance_matrix(data: &[Vec<f64>]) -> Result<Matrix, StatsError> {
+    let columns = columns_of(data)?;
+    let p = columns.len();
+    let means: Vec<f64> = columns.iter().map(|c| mean(c)).collect::<Result<_, _>>()?;
+    let n1 = (data.len() - 1) as f64;
+
+    let mut cov = Matrix::new(p, p);
+    for i in 0..p {
+        for j in i..p {
+            let s: f64 = columns[i].iter().zip(&columns[j])
+                .map(|(a, b)| (a - means[i]) * (b - means[j]))
+                .sum();
+            cov[(i, j)] = s / n1;
+            cov[(j, i)] = s / n1;
+        }
+    }
+    Ok(cov)
+}
+
+/// Pearson correlation matrix of `data` (rows = observations, columns = variables)
+pub fn correlation_matrix(data: &[Vec<f64>]) -> Result<Matrix, StatsError> {
+    let mut m = covariance_matrix(data)?;
+    let sd: Vec<f64> = (0..m.rows).map(|i| m[(i, i)].sqrt()).collect();
+    if sd.contains(&0.0) { return Err(StatsError::ZeroVariance); }
+    for i in 0..m.rows {
+        for j in 0..m.cols {
+            m[(i, j)] = if i == j { 1.0 } else { m[(i, j)] / (sd[i] * sd[j]) };
+        }
+    }
+    Ok(m)
+}
+
+fn check_pair(x: &[f64], y: &[f64], needed: usize) -> Result<(), StatsError> {
+    if x.len() != y.len() {
+        return Err(StatsError::LengthMismatch { left: x.len(), right: y.len() });
+    }
+    if x.is_empty() { return Err(StatsError::Empty); }
+    if x.len() < needed { return Err(StatsError::InsufficientData { needed, got: x.len() }); }
+    Ok(())
+}
+
+fn pearson_r(x: &[f64], y: &[f64]) -> Result<f64, StatsError> {
+    let (mx, my) = (mean(x)?, mean(y)?);
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (a, b) in x.iter().zip(y) {
+        sxy += (a - mx) * (b - my);
+        sxx += (a - mx).powi(2);
+        syy += (b - my).powi(2);
+    }
+    if sxx == 0.0 || syy == 0.0 { return Err(StatsError::ZeroVariance); }
+    Ok((sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0))
+}
+
+fn r_to_t_test(r: f64, n: usize) -> TestResult {
+    let df = (n - 2) as f64;
+    let t = if r.abs() == 1.0 { r * f64::INFINITY } else { r * (df / (1.0 - r * r)).sqrt() };
+    TestResult { statistic: t, df, p_value: student_t_two_sided(t, df) }
+}
+
+/// Σt(t-1), Σt(t-1)(2t+5) and Σt(t-1)(t-2) over groups of tied values
+fn tie_terms(data: &[f64]) -> (f64, f64, f64) {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let (mut t1, mut t2, mut t3) = (0.0, 0.0, 0.0);
+    for group in sorted.chunk_by(|a, b| a == b) {
+        let t = group.len() as f64;
+        t1 += t * (t - 1.0);
+        t2 += t * (t - 1.0) * (2.0 * t + 5.0);
+        t3 += t * (t - 1.0) * (t - 2.0);
+    }
+    (t1, t2, t3)
+}
+
+/// Transpose row-major observations into per-variable columns.
+fn columns_of(data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, StatsError> {
+    if data.is_empty() { return Err(StatsError::Empty); }
+    if data.len() < 2 { return Err(StatsError::InsufficientData { needed: 2, got: data.len() }); }
+    let p = data[0].len();
+    if p == 0 { return Err(StatsError::Empty); }
+    if let Some(row) = data.iter().find(|r| r.len() != p) {
+        return Err(StatsError::LengthMismatch { left: p, right: row.len() });
+    }
+    Ok((0..p).map(|j| data.iter().map(|r| r[j]).collect()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_variance;
+
+    fn close(a: f64, b: f64, tol: f64) -> bool {
+        (a - b).abs() < tol
+    }
+
+    const X: [f64; 10] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+    const Y: [f64; 10] = [2.1, 3.9, 6.2, 7.8, 10.1, 12.2, 13.8, 16.1, 18.0, 19.9];
+
+    #[test]
+    fn pearson_known_result() {
+        let y = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0];
+        let c = pearson(&X, &y).unwrap();
+        assert!(close(c.coefficient, 0.334_325_399, 1e-8), "r = {}", c.coefficient);
+        assert_eq!(c.test.df, 8.0);
+        assert!(close(c.test.p_value, 0.345_071_05, 1e-7), "p = {}", c.test.p_value);
+    }
+
+    #[test]
+    fn perfect_linear_relationship_is_significant() {
+        let c = pearson(&X, &Y).unwrap();
+        assert!(c.coefficient > 0.999);
+        assert!(c.test.p_value < 1e-10);
+    }
+
+    #[test]
+    fn spearman_is_one_for_monotonic_nonlinear() {
+        let y: Vec<f64> = X.iter().map(|x| x.powi(3)).collect();
+        assert!(close(spearman(&X, &y).unwrap().coefficient, 1.0, 1e-12));
+        assert!(pearson(&X, &y).unwrap().coefficient < 1.0);
+
+        let mut with_nan = y.clone();
+        with_nan[2] = f64::NAN;
+        assert!(matches!(spearman(&X, &with_nan), Err(StatsError::NonFinite(x)) if x.is_nan()));
+    }
+
+    #[test]
+    fn kendall_tau_with_ties_known_result() {
+        let x = [1.0, 2.0, 2.0, 3.0, 4.0, 5.0, 5.0, 6.0];
+        let y = [2.0, 1.0, 3.0, 3.0, 5.0, 4.0, 6.0, 6.0];
+        let c = kendall_tau(&x, &y).unwrap();
+        assert!(close(c.coefficient, 0.769_230_769, 1e-8), "tau = {}", c.coefficient);
+        assert!(close(c.test.p_value, 0.010_747_58, 1e-7), "p = {}", c.test.p_value);
+
+        let nan_x = [1.0, f64::NAN, 2.0, 3.0, 4.0, 5.0, 5.0, 6.0];
+        assert!(matches!(kendall_tau(&nan_x, &y), Err(StatsError::NonFinite(v)) if v.is_nan()));
+        assert!(matches!(kendall_tau(&x, &nan_x), Err(StatsError::NonFinite(v)) if v.is_nan()));
+    }
+
+    #[test]
+    fn covariance_matrix_diagonal_holds_variances() {
+        let data: Vec<Vec<f64>> = X.iter().zip(&Y).map(|(a, b)| vec![*a, *b, a * a]).collect();
+        let cov = covariance_matrix(&data).unwrap();
+        assert_eq!((cov.rows, cov.cols), (3, 3));
+        assert!(close(cov[(0, 0)], sample_variance(&X).unwrap(), 1e-12));
+        assert!(close(cov[(1, 1)], sample_variance(&Y).unwrap(), 1e-12));
+        assert!(close(cov[(0, 1)], covariance(&X, &Y).unwrap(), 1e-12));
+        assert_eq!(cov[(0, 2)], cov[(2, 0)]);
+    }
+
+    #[test]
+    fn correlation_matrix_matches_pairwise_pearson() {
+        let data: Vec<Vec<f64>> = X.iter().zip(&Y).map(|(a, b)| vec![*a, *b]).collect();
+        let corr = correlation_matrix(&data).unwrap();
+        assert_eq!(corr[(0, 0)], 1.0);
+        assert!(close(corr[(0, 1)], pearson(&X, &Y).unwrap().coefficient, 1e-12));
+    }
+
+    #[test]
+    fn mismatched_inputs_are_errors() {
+        assert_eq!(pearson(&[1.0, 2.0, 3.0], &[1.0, 2.0]).unwrap_err(),
+            StatsError::LengthMismatch { left: 3, right: 2 });
+        assert_eq!(spearman(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]).unwrap_err(), StatsError::ZeroVariance);
+        let ragged = vec![vec![1.0, 2.0], vec![3.0]];
+        assert_eq!(covariance_matrix(&ragged).unwrap_err(), StatsError::LengthMismatch { left: 2, right: 1 });
+    }
+}
@@ -0,0 +1,308 @@
+//! Histogram binning and kernel density estimation.
+
+use std::fmt;
+
+use crate::{check_finite, percentile, sample_variance, StatsError};
+
+/// Rule for choosing the histogram bin width from the data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinRule {
+    /// ceil(log2 n) + 1 bins - fine for small, roughly normal samples
+    Sturges,
+    /// width = 3.49 σ n^(-1/3) - optimal for normal data
+    Scott,
+    /// width = 2 IQR n^(-1/3) - robust to outliers and heavy tails
+    FreedmanDiaconis,
+}
+
+/// Most bins a `BinRule` may choose. A rule's width comes from the bulk of
+/// the data, so one far outlier would otherwise ask for billions of bins.
+pub const MAX_RULE_BINS: usize = 10_000;
+
+/// Equal-width histogram. `edges` has one more entry than `counts`; every bin
+/// is half-open [lo, hi) except the last, which also includes the maximum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    pub edges: Vec<f64>,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Bin `data` with a width chosen by `rule`.
+    /// Falls back to Sturges when the rule yields a zero width (e.g. IQR = 0),
+    /// and never uses more than `MAX_RULE_BINS` bins.
+    pub fn new(data: &[f64], rule: BinRule) -> Result<Self, StatsError> {
+        let (lo, hi) = min_max(data)?;
+        let n = data.len() as f64;
+        let sturges = (n.log2().ceil() as usize + 1).max(1);
+        let width = match rule {
+            _ if data.len() < 2 => 0.0,
+            BinRule::Sturges => 0.0,
+            BinRule::Scott => 3.49 * sample_variance(data)?.sqrt() * n.powf(-1.0 / 3.0),
+            BinRule::FreedmanDiaconis => {
+                2.0 * (percentile(data, 0.75)? - percentile(data, 0.25)?) * n.powf(-1.0 / 3.0)
+            }
+        };
+        let bins = if width > 0.0 && hi > lo { ((hi - lo) / width).ceil().min(MAX_RULE_BINS as f64) as usize } else { sturges };
+        Self::with_bins(data, bins.clamp(1, MAX_RULE_BINS))
+    }
+
+    /// Bin `data` into exactly `bins` equal-width bins spanning [min, max].
+    pub fn with_bins(data: &[f64], bins: usize) -> Result<Self, StatsError> {
+        let (mut lo, mut hi) = min_max(data)?;
+        if bins == 0 { return Err(StatsError::InsufficientData { needed: 1, got: 0 }); }
+        if lo == hi {
+            // Constant data: centre a unit-wide range on the value, as NumPy does
+            lo -= 0.5;
+            hi += 0.5;
+        }
+        let width = (hi - lo) / bins as f64;
+        let edges: Vec<f64> = (0..=bins).map(|i| lo + i as f64 * width).collect();
+        let mut counts = vec![0; bins];
+        for &x in data {
+            let idx = (((x - lo) / width) as usize).min(bins - 1);
+            counts[idx] += 1;
+        }
+        Ok(Self { edges, counts })
+    }
+
+    pub fn bin_width(&self) -> f64 {
+        self.edges[1] - self.edges[0]
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Counts normalised so the histogram integrates to 1
+    pub fn densities(&self) -> Vec<f64> {
+        let norm = self.total() as f64 * self.bin_width();
+        self.counts.iter().map(|&c| c as f64 / norm).collect()
+    }
+
+    /// One text line per bin with a bar scaled so the fullest bin spans `bar_width` cells.
+    pub fn render(&self, bar_width: usize) -> String {
+        let max = self.counts.iter().copied().max().unwrap_or(0).max(1);
+        let label_width = self.edges.iter().map(|e| format!("{e:.2}").len()).max().unwrap_or(0);
+        let mut out = String::new();
+        for (i, &count) in self.counts.iter().enumerate() {
+            let bar = "█".repeat((count * bar_width).div_ceil(max));
+            out.push_str(&format!(
+                "  {:>w$.2} – {:>w$.2} │{:<bw$} {}\n",
+                self.edges[i], self.edges[i + 1], bar, count, w = label_width, bw = bar_width,
+            ));
+        }
+        out
+    }
+}
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(40))
+    }
+}
+
+/// Smoothing kernel for density estimation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kernel {
+    Gaussian,
+    /// K(u) = 3/4 (1 - u²) on |u| <= 1 - optimal in mean integrated squared error
+    Epanechnikov,
+}
+
+/// How to pick the KDE bandwidth.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Bandwidth {
+    /// 0.9 min(σ, IQR / 1.34) n^(-1/5) - Silverman's rule of thumb
+    Silverman,
+    /// 1.06 σ n^(-1/5) - Scott's rule
+    Scott,
+    Fixed(f64),
+}
+
+/// Kernel density estimate over a sample.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Kde {
+    data: Vec<f64>,
+    kernel: Kernel,
+    bandwidth: f64,
+}
+
+impl Kde {
+    pub fn new(data: &[f64], kernel: Kernel, bandwidth: Bandwidth) -> Result<Self, StatsError> {
+        if data.is_empty() { return Err(StatsError::Empty); }
+        check_finite(data)?;
+        let n = data.len() as f64;
+        let h = match bandwidth {
+            Bandwidth::Fixed(h) => h,
+            Bandwidth::Scott => 1.06 * sample_variance(data)?.sqrt() * n.powf(-0.2),
+            Bandwidth::Silverman => {
+                let sd = sample_variance(data)?.sqrt();
+                let iqr = percentile(data, 0.75)? - percentile(data, 0.25)?;
+                let spread = if iqr > 0.0 { sd.min(iqr / 1.34) } else { sd };
+                0.9 * spread * n.powf(-0.2)
+            }
+        };
+        if !(h > 0.0 && h.is_finite()) {
+            return Err(match bandwidth {
+                Bandwidth::Fixed(_) => StatsError::InvalidBandwidth(h),
+                _ => StatsError::ZeroVariance,
+            });
+        }
+        Ok(Self { data: data.to_vec(), kernel, bandwidth: h })
+    }
+
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Estimated probability density at `x`
+    pub fn density(&self, x: f64) -> f64 {
+        let h = self.bandwidth;
+        let sum: f64 = self.data.iter().map(|&xi| self.kernel_at((x - xi) / h)).sum();
+        sum / (self.data.len() as f64 * h)
+    }
+
+    /// Density evaluated at each grid point
+    pub fn evaluate(&self, grid: &[f64]) -> Vec<f64> {
+        grid.iter().map(|&x| self.density(x)).collect()
+    }
+
+    /// `points` evenly spaced grid values covering the data plus three bandwidths either side
+    pub fn grid(&self, points: usize) -> Vec<f64> {
+        let lo = self.data.iter().copied().fold(f64::INFINITY, f64::min) - 3.0 * self.bandwidth;
+        let hi = self.data.iter().copied().fold(f64::NEG_INFINITY, f64::max) + 3.0 * self.bandwidth;
+        if points < 2 { return vec![(lo + hi) / 2.0; points]; }
+        let step = (hi - lo) / (points - 1) as f64;
+        (0..points).map(|i| lo + i as f64 * step).collect()
+    }
+
+    fn kernel_at(&self, u: f64) -> f64 {
+        match self.kernel {
+            Kernel::Gaussian => (-0.5 * u * u).exp() / (2.0 * std::f64::consts::PI).sqrt(),
+            Kernel::Epanechnikov if u.abs() <= 1.0 => 0.75 * (1.0 - u * u),
+            Kernel::Epanechnikov => 0.0,
+        }
+    }
+}
+
+fn min_max(data: &[f64]) -> Result<(f64, f64), StatsError> {
+    if data.is_empty() { return Err(StatsError::Empty); }
+    check_finite(data)?;
+    Ok(data.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| (lo.min(x), hi.max(x))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, Normal};
+
+    fn normal_sample(n: usize) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(5);
+        let dist = Normal::new(0.0, 1.0).unwrap();
+        (0..n).map(|_| dist.sample(&mut rng)).collect()
+    }
+
+    #[test]
+    fn every_value_lands_in_a_bin() {
+        let data = normal_sample(1000);
+        for rule in [BinRule::Sturges, BinRule::Scott, BinRule::FreedmanDiaconis] {
+            let h = Histogram::new(&data, rule).unwrap();
+            assert_eq!(h.total(), 1000, "{rule:?}");
+            assert_eq!(h.edges.len(), h.counts.len() + 1);
+        }
+    }
+
+    #[test]
+    fn sturges_bin_count() {
+        let data: Vec<f64> = (0..100).map(|x| x as f64).collect();
+        // ceil(log2 100) + 1 = 8
+        assert_eq!(Histogram::new(&data, BinRule::Sturges).unwrap().counts.len(), 8);
+    }
+
+    #[test]
+    fn freedman_diaconis_width_is_robust_to_outlier() {
+        let mut data = normal_sample(1000);
+        let scott = Histogram::new(&data, BinRule::Scott).unwrap().bin_width();
+        let fd = Histogram::new(&data, BinRule::FreedmanDiaconis).unwrap().bin_width();
+        data.push(1_000.0);
+        let scott_outlier = Histogram::new(&data, BinRule::Scott).unwrap().bin_width();
+        let fd_outlier = Histogram::new(&data, BinRule::FreedmanDiaconis).unwrap().bin_width();
+        assert!(scott_outlier / scott > 5.0);
+        assert!((fd_outlier / fd - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn a_far_outlier_cannot_explode_the_bin_count() {
+        let mut data = normal_sample(1000);
+        data.push(1e9);
+        // The IQR ignores the outlier, so Freedman-Diaconis would ask for ~4e9 bins
+        let h = Histogram::new(&data, BinRule::FreedmanDiaconis).unwrap();
+        assert_eq!(h.counts.len(), MAX_RULE_BINS);
+        assert_eq!(h.total(), 1001);
+    }
+
+    #[test]
+    fn non_finite_data_is_an_error() {
+        for bad in [f64::INFINITY, f64::NEG_INFINITY] {
+            let data = [1.0, 2.0, bad];
+            assert_eq!(Histogram::new(&data, BinRule::FreedmanDiaconis).unwrap_err(), StatsError::NonFinite(bad));
+            assert_eq!(Histogram::with_bins(&data, 4).unwrap_err(), StatsError::NonFinite(bad));
+        }
+        assert!(matches!(Histogram::new(&[f64::NAN, 1.0], BinRule::Sturges), Err(StatsError::NonFinite(x)) if x.is_nan()));
+    }
+
+    #[test]
+    fn constant_data_gets_single_centred_bin() {
+        let h = Histogram::with_bins(&[3.0, 3.0, 3.0], 1).unwrap();
+        assert_eq!(h.edges, vec![2.5, 3.5]);
+        assert_eq!(h.counts, vec![3]);
+    }
+
+    #[test]
+    fn render_draws_one_line_per_bin_with_full_bar_for_max() {
+        let h = Histogram::with_bins(&[0.0, 1.0, 1.0, 2.0], 2).unwrap();
+        let text = h.render(10);
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.lines().nth(1).unwrap().contains(&"█".repeat(10)));
+    }
+
+    #[test]
+    fn kde_integrates_to_one() {
+        let data = normal_sample(500);
+        for kernel in [Kernel::Gaussian, Kernel::Epanechnikov] {
+            let kde = Kde::new(&data, kernel, Bandwidth::Silverman).unwrap();
+            let grid = kde.grid(2000);
+            let step = grid[1] - grid[0];
+            let area: f64 = kde.evaluate(&grid).iter().sum::<f64>() * step;
+            assert!((area - 1.0).abs() < 1e-3, "{kernel:?} area {area}");
+        }
+    }
+
+    #[test]
+    fn gaussian_kde_approximates_normal_density() {
+        let kde = Kde::new(&normal_sample(5000), Kernel::Gaussian, Bandwidth::Scott).unwrap();
+        let peak = 1.0 / (2.0 * std::f64::consts::PI).sqrt();
+        assert!((kde.density(0.0) - peak).abs() < 0.03, "{}", kde.density(0.0));
+    }
+
+    #[test]
+    fn invalid_bandwidths_are_errors() {
+        assert_eq!(Kde::new(&[1.0, 2.0], Kernel::Gaussian, Bandwidth::Fixed(0.0)).unwrap_err(),
+            StatsError::InvalidBandwidth(0.0));
+        assert_eq!(Kde::new(&[1.0, 1.0], Kernel::Gaussian, Bandwidth::Silverman).unwrap_err(),
+            StatsError::ZeroVariance);
+    }
+
+    #[test]
+    fn kde_rejects_non_finite_data() {
+        let data = [1.0, 2.0, f64::NAN, 4.0];
+        for bandwidth in [Bandwidth::Scott, Bandwidth::Silverman, Bandwidth::Fixed(0.5)] {
+            assert!(matches!(Kde::new(&data, Kernel::Gaussian, bandwidth), Err(StatsError::NonFinite(x)) if x.is_nan()));
+        }
+        assert_eq!(Kde::new(&[1.0, f64::NEG_INFINITY], Kernel::Epanechnikov, Bandwidth::Fixed(1.0)).unwrap_err(),
+            StatsError::NonFinite(f64::NEG_INFINITY));
+    }
+}
@@ -0,0 +1,276 @@
+//! Classical hypothesis tests. Every p-value is two-sided.
+
+use crate::distributions::{chi_square_sf, f_tail, normal_sf, student_t_two_sided};
+use crate::{check_finite, mean, sample_variance, StatsError};
+
+/// Outcome of a hypothesis test.
+///
+/// Tests whose reference distribution is the standard normal (Mann-Whitney U
+/// under the large-sample approximation) report `df = f64::INFINITY`, the limit
+/// of Student's t.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestResult {
+    pub statistic: f64,
+    pub df: f64,
+    pub p_value: f64,
+}
+
+/// One-way ANOVA table
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnovaResult {
+    pub f_statistic: f64,
+    pub df_between: f64,
+    pub df_within: f64,
+    pub ss_between: f64,
+    pub ss_within: f64,
+    pub p_value: f64,
+}
+
+/// One-sample t-test of H0: mean(data) == mu
+pub fn one_sample_t_test(data: &[f64], mu: f64) -> Result<TestResult, StatsError> {
+    let n = data.len() as f64;
+    let var = sample_variance(data)?;
+    if var == 0.0 { return Err(StatsError::ZeroVariance); }
+    let t = (mean(data)? - mu) / (var / n).sqrt();
+    Ok(t_result(t, n - 1.0))
+}
+
+/// Student's two-sample t-test assuming equal variances (pooled estimate)
+pub fn student_t_test(a: &[f64], b: &[f64]) -> Result<TestResult, StatsError> {
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    let (va, vb) = (sample_variance(a)?, sample_variance(b)?);
+    let df = na + nb - 2.0;
+    let pooled = ((na - 1.0) * va + (nb - 1.0) * vb) / df;
+    if pooled == 0.0 { return Err(StatsError::ZeroVariance); }
+    let t = (mean(a)? - mean(b)?) / (pooled * (1.0 / na + 1.0 / nb)).sqrt();
+    Ok(t_result(t, df))
+}
+
+/// Welch's two-sample t-test, with Welch-Satterthwaite degrees of freedom
+pub fn welch_t_test(a: &[f64], b: &[f64]) -> Result<TestResult, StatsError> {
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    let sa = sample_variance(a)? / na;
+    let sb = sample_variance(b)? / nb;
+    if sa + sb == 0.0 { return Err(StatsError::ZeroVariance); }
+    let t = (mean(a)? - mean(b)?) / (sa + sb).sqrt();
+    let df = (sa + sb).powi(2) / (sa * sa / (na - 1.0) + sb * sb / (nb - 1.0));
+    Ok(t_result(t, df))
+}
+
+/// Paired t-test: a one-sample t-test on the differences a[i] - b[i]
+pub fn paired_t_test(a: &[f64], b: &[f64]) -> Result<TestResult, StatsError> {
+    if a.len() != b.len() {
+        return Err(StatsError::LengthMismatch { left: a.len(), right: b.len() });
+    }
+    let diffs: Vec<f64> = a.iter().zip(b).map(|(x, y)| x - y).collect();
+    one_sample_t_test(&diffs, 0.0)
+}
+
+/// Mann-Whitney U test (Wilcoxon rank-sum).
+///
+/// `statistic` is U for sample `a`. The p-value uses the normal approximation
+/// with tie correction and a 0.5 continuity correction, so it is reliable once
+/// both samples have more than roughly 8 observations.
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> Result<TestResult, StatsError> {
+    if a.is_empty() || b.is_empty() { return Err(StatsError::Empty); }
+    check_finite(a)?;
+    check_finite(b)?;
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    let n = na + nb;
+
+    let pooled: Vec<f64> = a.iter().chain(b).copied().collect();
+    let (ranks, tie_sum) = ranks_with_ties(&pooled);
+    let rank_sum_a: f64 = ranks[..a.len()].iter().sum();
+    let u = rank_sum_a - na * (na + 1.0) / 2.0;
+
+    let mu = na * nb / 2.0;
+    let sigma = (na * nb / 12.0 * ((n + 1.0) - tie_sum / (n * (n - 1.0)))).sqrt();
+    if sigma == 0.0 { return Err(StatsError::ZeroVariance); }
+    let z = ((u - mu).abs() - 0.5).max(0.0) / sigma;
+    Ok(TestResult { statistic: u, df: f64::INFINITY, p_value: (2.0 * normal_sf(z)).min(1.0) })
+}
+
+/// Chi-square test of independence on an r x c contingency table of counts.
+/// No Yates continuity correction is applied.
+pub fn chi_square_independence(table: &[Vec<f64>]) -> Result<TestResult, StatsError> {
+    let rows = table.len();
+    let cols = table.first().map_or(0, Vec::len);
+    if rows < 2 || cols < 2 {
+        return Err(StatsError::InvalidTable("need at least 2 rows and 2 columns"));
+    }
+    if table.iter().any(|r| r.len() != cols) {
+        return Err(StatsError::InvalidTable("rows have different lengths"));
+    }
+    if table.iter().flatten().any(|&o| o < 0.0 || !o.is_finite()) {
+        return Err(StatsError::InvalidTable("counts must be finite and non-negative"));
+    }
+
+    let row_sums: Vec<f64> = table.iter().map(|r| r.iter().sum()).collect();
+    let col_sums: Vec<f64> = (0..cols).map(|c| table.iter().map(|r| r[c]).sum()).collect();
+    let total: f64 = row_sums.iter().sum();
+    if row_sums.iter().chain(&col_sums).any(|&s| s == 0.0) {
+        return Err(StatsError::InvalidTable("a row or column sums to zero"));
+    }
+
+    let mut stat = 0.0;
+    for (r, row) in table.iter().enumerate() {
+        for (c, &observed) in row.iter().enumerate() {
+            let expected = row_sums[r] * col_sums[c] / total;
+            stat += (observed - expected).powi(2) / expected;
+        }
+    }
+    let df = ((rows - 1) * (cols - 1)) as f64;
+    Ok(TestResult { statistic: stat, df, p_value: chi_square_sf(stat, df) })
+}
+
+/// One-way ANOVA: do all groups share the same mean?
+pub fn one_way_anova(groups: &[&[f64]]) -> Result<AnovaResult, StatsError> {
+    if groups.len() < 2 {
+        return Err(StatsError::InsufficientData { needed: 2, got: groups.len() });
+    }
+    if groups.iter().any(|g| g.is_empty()) { return Err(StatsError::Empty); }
+
+    let n_total: usize = groups.iter().map(|g| g.len()).sum();
+    let k = groups.len();
+    if n_total <= k {
+        return Err(StatsError::InsufficientData { needed: k + 1, got: n_total });
+    }
+    let grand_mean = groups.iter().flat_map(|g| g.iter()).sum::<f64>() / n_total as f64;
+
+    let mut ss_between = 0.0;
+    let mut ss_within = 0.0;
+    for g in groups {
+        let m = mean(g)?;
+        ss_between += g.len() as f64 * (m - grand_mean).powi(2);
+        ss_within += g.iter().map(|x| (x - m).powi(2)).sum::<f64>();
+    }
+    if ss_within == 0.0 { return Err(StatsError::ZeroVariance); }
+
+    let df_between = (k - 1) as f64;
+    let df_within = (n_total - k) as f64;
+    let f = (ss_between / df_between) / (ss_within / df_within);
+    Ok(AnovaResult {
+        f_statistic: f,
+        df_between,
+        df_within,
+        ss_between,
+        ss_within,
+        p_value: f_tail(f, df_between, df_within),
+    })
+}
+
+fn t_result(t: f64, df: f64) -> TestResult {
+    TestResult { statistic: t, df, p_value: student_t_two_sided(t, df) }
+}
+
+/// 1-based ranks with ties given their average rank, plus Σ(t³ - t) over tie groups.
+pub(crate) fn ranks_with_ties(data: &[f64]) -> (Vec<f64>, f64) {
+    let mut order: Vec<usize> = (0..data.len()).collect();
+    order.sort_by(|&i, &j| data[i].partial_cmp(&data[j]).unwrap());
+
+    let mut ranks = vec![0.0; data.len()];
+    let mut tie_sum = 0.0;
+    let mut i = 0;
+    while i < order.len() {
+        let mut j = i + 1;
+        while j < order.len() && data[order[j]] == data[order[i]] { j += 1; }
+        let avg_rank = (i + j + 1) as f64 / 2.0;
+        for &idx in &order[i..j] { ranks[idx] = avg_rank; }
+        let t = (j - i) as f64;
+        tie_sum += t * t * t - t;
+        i = j;
+    }
+    (ranks, tie_sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64, tol: f64) -> bool {
+        (a - b).abs() < tol
+    }
+
+    #[test]
+    fn student_t_test_known_result() {
+        let a = [19.0, 22.0, 16.0, 29.0, 24.0];
+        let b = [20.0, 11.0, 17.0, 12.0];
+        let r = student_t_test(&a, &b).unwrap();
+        assert!(close(r.statistic, 2.239_334_696_5, 1e-8), "t = {}", r.statistic);
+        assert_eq!(r.df, 7.0);
+        assert!(close(r.p_value, 0.060_136_88, 1e-7), "p = {}", r.p_value);
+    }
+
+    #[test]
+    fn welch_t_test_known_result() {
+        let a = [19.0, 22.0, 16.0, 29.0, 24.0];
+        let b = [20.0, 11.0, 17.0, 12.0];
+        let r = welch_t_test(&a, &b).unwrap();
+        assert!(close(r.statistic, 2.283_148_26, 1e-7), "t = {}", r.statistic);
+        assert!(close(r.df, 6.928_837_48, 1e-7), "df = {}", r.df);
+        assert!(close(r.p_value, 0.056_750_93, 1e-7), "p = {}", r.p_value);
+    }
+
+    #[test]
+    fn paired_t_test_matches_one_sample_on_differences() {
+        let before = [200.0, 190.0, 210.0, 220.0, 205.0, 198.0];
+        let after = [195.0, 188.0, 200.0, 214.0, 203.0, 190.0];
+        let paired = paired_t_test(&before, &after).unwrap();
+        let diffs: Vec<f64> = before.iter().zip(&after).map(|(a, b)| a - b).collect();
+        assert_eq!(paired, one_sample_t_test(&diffs, 0.0).unwrap());
+        assert_eq!(paired.df, 5.0);
+        assert!(paired.p_value < 0.01);
+    }
+
+    #[test]
+    fn paired_t_test_rejects_unequal_lengths() {
+        assert_eq!(
+            paired_t_test(&[1.0, 2.0], &[1.0]).unwrap_err(),
+            StatsError::LengthMismatch { left: 2, right: 1 }
+        );
+    }
+
+    #[test]
+    fn mann_whitney_detects_shift_and_handles_ties() {
+        let a: Vec<f64> = (0..20).map(|i| (i / 2) as f64).collect();
+        let b: Vec<f64> = (0..20).map(|i| (i / 2) as f64 + 6.0).collect();
+        let r = mann_whitney_u(&a, &b).unwrap();
+        assert!(r.statistic < 200.0, "a should rank lower, U = {}", r.statistic);
+        assert!(r.p_value < 0.001, "p = {}", r.p_value);
+
+        let same = mann_whitney_u(&a, &a).unwrap();
+        assert_eq!(same.statistic, 200.0);
+        assert!(same.p_value > 0.99);
+
+        let mut with_nan = a.clone();
+        with_nan[3] = f64::NAN;
+        assert!(matches!(mann_whitney_u(&with_nan, &b), Err(StatsError::NonFinite(x)) if x.is_nan()));
+        assert_eq!(mann_whitney_u(&a, &[f64::INFINITY]).unwrap_err(), StatsError::NonFinite(f64::INFINITY));
+    }
+
+    #[test]
+    fn chi_square_independence_known_result() {
+        let table = vec![vec![10.0, 20.0, 30.0], vec![6.0, 9.0, 17.0]];
+        let r = chi_square_independence(&table).unwrap();
+        assert!(close(r.statistic, 0.271_574_65, 1e-7), "chi2 = {}", r.statistic);
+        assert_eq!(r.df, 2.0);
+        assert!(close(r.p_value, 0.873_028_28, 1e-7), "p = {}", r.p_value);
+    }
+
+    #[test]
+    fn chi_square_rejects_ragged_table() {
+        let table = vec![vec![1.0, 2.0], vec![3.0]];
+        assert!(matches!(chi_square_independence(&table), Err(StatsError::InvalidTable(_))));
+    }
+
+    #[test]
+    fn anova_known_result() {
+        let g1 = [6.0, 8.0, 4.0, 5.0, 3.0, 4.0];
+        let g2 = [8.0, 12.0, 9.0, 11.0, 6.0, 8.0];
+        let g3 = [13.0, 9.0, 11.0, 8.0, 7.0, 12.0];
+        let r = one_way_anova(&[&g1, &g2, &g3]).unwrap();
+        assert!(close(r.f_statistic, 9.264_705_88, 1e-7), "F = {}", r.f_statistic);
+        assert_eq!((r.df_between, r.df_within), (2.0, 15.0));
+        assert!(close(r.p_value, 0.002_398_777, 1e-8), "p = {}", r.p_value);
+    }
+}
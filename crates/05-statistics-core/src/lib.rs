@@ -13,6 +13,35 @@
 
 use thiserror::Error;
 
+mod accumulator;
+mod bootstrap;
+mod correlation;
+mod distributions;
+mod histogram;
+mod hypothesis;
+mod robust;
+mod rolling;
+mod table;
+
+pub use accumulator::{Accumulator, Moments};
+pub use bootstrap::{bootstrap, BootstrapConfig, BootstrapResult, ConfidenceInterval};
+pub use correlation::{
+    correlation_matrix, covariance, covariance_matrix, kendall_tau, pearson, spearman, Correlation,
+};
+pub use distributions::{
+    chi_square_cdf, f_cdf, f_sf, normal_cdf, normal_quantile, student_t_cdf, student_t_quantile, student_t_sf,
+};
+pub use histogram::{Bandwidth, BinRule, Histogram, Kde, Kernel, MAX_RULE_BINS};
+pub use hypothesis::{
+    chi_square_independence, mann_whitney_u, one_sample_t_test, one_way_anova, paired_t_test,
+    student_t_test, welch_t_test, AnovaResult, TestResult,
+};
+pub use robust::{
+    hampel_filter, mad, scaled_mad, trimmed_mean, winsorised_mean, HampelResult, MAD_NORMAL_SCALE,
+};
+pub use rolling::{ewma, rolling_mean, rolling_percentile, rolling_std};
+pub use table::{delimiter_for, InvalidCell, NumericColumn, Table};
+
 #[derive(Debug, Error, PartialEq)]
 pub enum StatsError {
     #[error("input data is empty")]
@@ -21,56 +50,95 @@ pub enum StatsError {
     InvalidPercentile(f64),
     #[error("insufficient data: need at least {needed} points, got {got}")]
     InsufficientData { needed: usize, got: usize },
+    #[error("samples must have equal length, got {left} and {right}")]
+    LengthMismatch { left: usize, right: usize },
+    #[error("data has zero variance; the test statistic is undefined")]
+    ZeroVariance,
+    #[error("degrees of freedom must be positive, got {0}")]
+    InvalidDegreesOfFreedom(f64),
+    #[error("confidence level must be in (0.0, 1.0), got {0}")]
+    InvalidConfidence(f64),
+    #[error("bandwidth must be positive and finite, got {0}")]
+    InvalidBandwidth(f64),
+    #[error("window of {window} is invalid for a series of length {len}")]
+    InvalidWindow { window: usize, len: usize },
+    #[error("invalid {name}: {value}")]
+    InvalidParameter { name: &'static str, value: f64 },
+    #[error("invalid contingency table: {0}")]
+    InvalidTable(&'static str),
+    #[error("data must be finite, got {0}")]
+    NonFinite(f64),
+}
+
+/// Reject NaN and ±∞ up front, for routines that sort or rank their input
+pub(crate) fn check_finite(data: &[f64]) -> Result<(), StatsError> {
+    match data.iter().find(|x| !x.is_finite()) {
+        Some(&x) => Err(StatsError::NonFinite(x)),
+        None => Ok(()),
+    }
 }
 
 pub fn mean(data: &[f64]) -> Result<f64, StatsError> {
-    todo!()
+    if data.is_empty() { return Err(StatsError::Empty); }
+    Ok(data.iter().sum::<f64>() / data.len() as f64)
 }
 
 /// Population variance (divides by N, not N-1)
 pub fn variance(data: &[f64]) -> Result<f64, StatsError> {
-    todo!()
+    let m = mean(data)?;
+    Ok(data.iter().map(|x| (x - m).powi(2)).sum::<f64>() / data.len() as f64)
 }
 
 /// Sample variance (divides by N-1, unbiased estimator)
 pub fn sample_variance(data: &[f64]) -> Result<f64, StatsError> {
-    todo!()
+    if data.len() < 2 { return Err(StatsError::InsufficientData { needed: 2, got: data.len() }); }
+    let m = mean(data)?;
+    Ok(data.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (data.len() - 1) as f64)
 }
 
 pub fn std_dev(data: &[f64]) -> Result<f64, StatsError> {
-    todo!()
+    Ok(variance(data)?.sqrt())
 }
 
 pub fn median(data: &[f64]) -> Result<f64, StatsError> {
-    todo!()
+    percentile(data, 0.5)
 }
 
 /// p in [0.0, 1.0]; uses linear interpolation (same as NumPy default)
 pub fn percentile(data: &[f64], p: f64) -> Result<f64, StatsError> {
-    todo!()
+    if data.is_empty() { return Err(StatsError::Empty); }
+    if !(0.0..=1.0).contains(&p) { return Err(StatsError::InvalidPercentile(p)); }
+    let mut s = data.to_vec(); s.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let idx = p * (s.len() - 1) as f64; let lo = idx.floor() as usize; let hi = (lo + 1).min(s.len() - 1);
+    Ok(s[lo] + (idx - lo as f64) * (s[hi] - s[lo]))
 }
 
 /// Excess kurtosis (0 for normal distribution)
 pub fn kurtosis(data: &[f64]) -> Result<f64, StatsError> {
-    todo!()
+    let m = mean(data)?; let v = variance(data)?;
+    Ok(data.iter().map(|x| (x - m).powi(4)).sum::<f64>() / data.len() as f64 / (v * v) - 3.0)
 }
 
 /// Pearson's moment skewness
 pub fn skewness(data: &[f64]) -> Result<f64, StatsError> {
-    todo!()
+    let m = mean(data)?; let v = variance(data)?;
+    Ok(data.iter().map(|x| (x - m).powi(3)).sum::<f64>() / data.len() as f64 / v.powf(1.5))
 }
 
 /// Z-score normalisation: (x - mean) / std_dev
 pub fn z_scores(data: &[f64]) -> Result<Vec<f64>, StatsError> {
-    todo!()
+    let m = mean(data)?; let s = std_dev(data)?;
+    Ok(data.iter().map(|x| (x - m) / s).collect())
 }
 
 /// IQR-based outlier detection. Returns the values flagged as outliers (|z| > 1.5*IQR).
 pub fn iqr_outliers(data: &[f64]) -> Result<Vec<f64>, StatsError> {
-    todo!()
+    let q1 = percentile(data, 0.25)?; let q3 = percentile(data, 0.75)?; let i = q3 - q1;
+    Ok(data.iter().copied().filter(|&x| x < q1 - 1.5 * i || x > q3 + 1.5 * i).collect())
 }
 
 /// Summary statistics bundle
+#[derive(Clone, Debug, PartialEq)]
 pub struct Summary {
     pub mean: f64,
     pub std_dev: f64,
@@ -83,7 +151,7 @@ pub struct Summary {
 }
 
 pub fn summarise(data: &[f64]) -> Result<Summary, StatsError> {
-    todo!()
+    Ok(Summary { mean: mean(data)?, std_dev: std_dev(data)?, median: median(data)?, p5: percentile(data, 0.05)?, p95: percentile(data, 0.95)?, skewness: skewness(data)?, kurtosis: kurtosis(data)?, n_outliers: iqr_outliers(data)?.len() })
 }
 
 #[cfg(test)]
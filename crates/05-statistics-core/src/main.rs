@@ -1,16 +1,202 @@
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, ValueEnum};
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 use rand_distr::{Distribution, Normal};
-use statistics_core::summarise;
+use serde_json::json;
+use statistics_core::{
+    delimiter_for, ewma, hampel_filter, rolling_std, scaled_mad, summarise, trimmed_mean, Bandwidth, BinRule,
+    Histogram, Kde, Kernel, NumericColumn, Summary, Table,
+};
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Bins {
+    Sturges,
+    Scott,
+    Fd,
+}
+
+impl From<Bins> for BinRule {
+    fn from(b: Bins) -> Self {
+        match b {
+            Bins::Sturges => BinRule::Sturges,
+            Bins::Scott   => BinRule::Scott,
+            Bins::Fd      => BinRule::FreedmanDiaconis,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Parser)]
+#[command(name = "statistics-core", about = "Descriptive statistics for sensor telemetry")]
+struct Args {
+    /// CSV/TSV file to summarise ('-' for stdin). Runs the telemetry demo when omitted.
+    input: Option<PathBuf>,
+
+    /// Columns to summarise, by header name or 0-based index (default: every numeric column)
+    #[arg(short, long, value_delimiter = ',')]
+    columns: Vec<String>,
+
+    /// Summarise separately for each distinct value of this key column
+    #[arg(short, long)]
+    group_by: Option<String>,
+
+    /// Field delimiter: a single character or 'tab' (default: tab for .tsv/.tab files, else ',')
+    #[arg(short, long)]
+    delimiter: Option<String>,
+
+    /// Treat the first row as data rather than a header
+    #[arg(long)]
+    no_header: bool,
+
+    /// Print every non-numeric cell to stderr instead of silently skipping it
+    #[arg(long)]
+    report_invalid: bool,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+
+    /// Draw an ASCII histogram under each column summary (table format only)
+    #[arg(long)]
+    histogram: bool,
+
+    /// Histogram bin-width rule
+    #[arg(short, long, value_enum, default_value_t = Bins::Fd)]
+    bins: Bins,
+
+    /// Width of the longest histogram bar, in characters
+    #[arg(short, long, default_value_t = 40)]
+    width: usize,
+}
 
 fn main() {
+    let args = Args::parse();
+    let result = match &args.input {
+        Some(path) => run_csv(&args, path),
+        None => { run_demo(&args); Ok(()) }
+    };
+    if let Err(e) = result {
+        // `stats data.csv | head` closing the pipe early is not a failure
+        if e.downcast_ref::<io::Error>().is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe) { return; }
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn run_csv(args: &Args, path: &Path) -> Result<(), Box<dyn Error>> {
+    let delimiter = delimiter_for(args.delimiter.as_deref(), path)?;
+    let table = Table::open(path, delimiter, !args.no_header)?;
+
+    let key = args.group_by.as_deref().map(|k| table.resolve(k)).transpose()?;
+    let columns: Vec<usize> = if args.columns.is_empty() {
+        table.numeric_columns().into_iter().filter(|&c| Some(c) != key).collect()
+    } else {
+        args.columns.iter().map(|c| table.resolve(c)).collect::<Result<_, _>>()?
+    };
+    if columns.is_empty() { return Err("no numeric columns found".into()); }
+
+    let groups = match key {
+        Some(k) => table.group_by(k),
+        None => vec![(String::new(), table.all_rows())],
+    };
+
+    let mut rows = Vec::new();
+    for (group, row_ids) in &groups {
+        for &c in &columns {
+            let (column, invalid) = table.numeric_column(c, row_ids);
+            if args.report_invalid {
+                for cell in &invalid {
+                    eprintln!("warning: row {}, column '{}': '{}' is not numeric", cell.row, cell.column, cell.value);
+                }
+            }
+            let summary = summarise(&column.values).ok();
+            rows.push((group.clone(), column, summary));
+        }
+    }
+
+    let mut out = io::stdout().lock();
+    match args.format {
+        Format::Json => print_json(&mut out, &rows, key.map(|k| table.headers[k].as_str()))?,
+        Format::Table => print_table(&mut out, &rows, key.is_some(), args)?,
+    }
+    Ok(out.flush()?)
+}
+
+type Row = (String, NumericColumn, Option<Summary>);
+
+fn print_table(out: &mut impl Write, rows: &[Row], grouped: bool, args: &Args) -> io::Result<()> {
+    let group_w = rows.iter().map(|r| r.0.len()).max().unwrap_or(0).max(5);
+    let name_w = rows.iter().map(|r| r.1.name.len()).max().unwrap_or(0).max(6);
+    if grouped { write!(out, "{:<group_w$}  ", "group")?; }
+    writeln!(
+        out,
+        "{:<name_w$} {:>7} {:>7} {:>11} {:>11} {:>11} {:>11} {:>11} {:>8} {:>8} {:>8}",
+        "column", "n", "skipped", "mean", "std_dev", "median", "p5", "p95", "skew", "ex.kurt", "outliers",
+    )?;
+    for (group, column, summary) in rows {
+        if grouped { write!(out, "{:<group_w$}  ", group)?; }
+        write!(out, "{:<name_w$} {:>7} {:>7}", column.name, column.values.len(), column.skipped)?;
+        match summary {
+            Some(s) => writeln!(
+                out,
+                " {:>11.4} {:>11.4} {:>11.4} {:>11.4} {:>11.4} {:>8.3} {:>8.3} {:>8}",
+                s.mean, s.std_dev, s.median, s.p5, s.p95, s.skewness, s.kurtosis, s.n_outliers,
+            )?,
+            None => writeln!(out, "  (no numeric values)")?,
+        }
+        if args.histogram && !column.values.is_empty() {
+            if let Ok(hist) = Histogram::new(&column.values, args.bins.into()) {
+                write!(out, "\n{}\n", hist.render(args.width))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_json(out: &mut impl Write, rows: &[Row], key: Option<&str>) -> io::Result<()> {
+    let values: Vec<_> = rows.iter().map(|(group, column, summary)| {
+        let mut obj = json!({
+            "column": column.name,
+            "n": column.values.len(),
+            "skipped": column.skipped,
+            "summary": summary.as_ref().map(|s| json!({
+                "mean": s.mean,
+                "std_dev": s.std_dev,
+                "median": s.median,
+                "p5": s.p5,
+                "p95": s.p95,
+                "skewness": s.skewness,
+                "kurtosis": s.kurtosis,
+                "n_outliers": s.n_outliers,
+            })),
+        });
+        if let Some(k) = key {
+            obj["group_by"] = json!(k);
+            obj["group"] = json!(group);
+        }
+        obj
+    }).collect();
+    writeln!(out, "{}", serde_json::to_string_pretty(&values).expect("JSON values always serialise"))
+}
+
+fn run_demo(args: &Args) {
     let mut rng = StdRng::seed_from_u64(42);
 
     // Simulate CPU temperature sensor readings (°C) — slightly skewed by occasional spikes
     let normal = Normal::new(65.0_f64, 5.0).unwrap();
     let mut readings: Vec<f64> = (0..1000).map(|_| normal.sample(&mut rng)).collect();
     // Inject thermal spike outliers
-    readings.extend_from_slice(&[98.5, 101.2, 99.8]);
+    for (at, spike) in [(250, 98.5), (500, 101.2), (750, 99.8)] {
+        readings.insert(at, spike);
+    }
 
     println!("=== CPU Temperature Telemetry ({} readings) ===\n", readings.len());
 
@@ -22,4 +208,32 @@ fn main() {
     println!("  Skewness:   {:>8.4}  (>0 = right tail from spikes)", s.skewness);
     println!("  Ex. Kurt:   {:>8.4}  (>0 = heavier tail than normal)", s.kurtosis);
     println!("  Outliers:   {:>8}  (IQR method)", s.n_outliers);
+
+    let hist = Histogram::new(&readings, args.bins.into()).expect("non-empty data");
+    println!("\n=== Distribution ({} bins, width {:.2} °C) ===\n", hist.counts.len(), hist.bin_width());
+    print!("{}", hist.render(args.width));
+
+    let kde = Kde::new(&readings, Kernel::Gaussian, Bandwidth::Silverman).expect("spread in data");
+    let grid = kde.grid(512);
+    let densities = kde.evaluate(&grid);
+    let (mode_idx, _) = densities.iter().enumerate()
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+        .unwrap();
+    println!("\n  KDE mode:   {:>8.2} °C  (Gaussian kernel, bandwidth {:.2})", grid[mode_idx], kde.bandwidth());
+
+    println!("\n=== Robust Anomaly Detection ===\n");
+    println!("  Scaled MAD:      {:>8.2} °C  (σ estimate that ignores spikes)", scaled_mad(&readings).unwrap());
+    println!("  10% trim mean:   {:>8.2} °C", trimmed_mean(&readings, 0.1).unwrap());
+
+    let hampel = hampel_filter(&readings, 25, 5.0).unwrap();
+    println!("  Hampel filter:   {:>8} readings flagged (±25 window, 5σ)", hampel.outliers.len());
+    for &i in &hampel.outliers {
+        println!("    reading {:>4}: {:>7.2} °C  → replaced with {:.2} °C", i, readings[i], hampel.filtered[i]);
+    }
+
+    let smoothed = ewma(&hampel.filtered, 0.05).unwrap();
+    let volatility = rolling_std(&hampel.filtered, 50).unwrap();
+    let max_vol = volatility.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    println!("  EWMA (α=0.05):   {:>8.2} °C  (latest, after filtering)", smoothed[smoothed.len() - 1]);
+    println!("  Rolling σ (50):  {:>8.2} °C  (peak)", max_vol);
 }
@@ -0,0 +1,144 @@
+//! Robust location/scale estimates and outlier filtering.
+//!
+//! These resist a handful of wild readings - a single stuck sensor can drag
+//! the mean and standard deviation arbitrarily far, but barely moves a median,
+//! MAD or trimmed mean.
+
+use crate::{check_finite, mean, median, StatsError};
+
+/// Scale factor that makes the MAD a consistent estimator of σ for normal data
+pub const MAD_NORMAL_SCALE: f64 = 1.482_602_218_505_602;
+
+/// Median absolute deviation: median(|x - median(x)|)
+pub fn mad(data: &[f64]) -> Result<f64, StatsError> {
+    check_finite(data)?;
+    let m = median(data)?;
+    let deviations: Vec<f64> = data.iter().map(|x| (x - m).abs()).collect();
+    median(&deviations)
+}
+
+/// MAD scaled by 1.4826 so it estimates the standard deviation of normal data
+pub fn scaled_mad(data: &[f64]) -> Result<f64, StatsError> {
+    Ok(MAD_NORMAL_SCALE * mad(data)?)
+}
+
+/// Mean after discarding floor(proportion * n) values from each end.
+/// `proportion` must be in [0.0, 0.5).
+pub fn trimmed_mean(data: &[f64], proportion: f64) -> Result<f64, StatsError> {
+    let (sorted, k) = sorted_with_cut(data, proportion)?;
+    mean(&sorted[k..sorted.len() - k])
+}
+
+/// Mean after clamping the lowest and highest floor(proportion * n) values to
+/// the nearest value that was kept. `proportion` must be in [0.0, 0.5).
+pub fn winsorised_mean(data: &[f64], proportion: f64) -> Result<f64, StatsError> {
+    let (mut sorted, k) = sorted_with_cut(data, proportion)?;
+    let n = sorted.len();
+    let (lo, hi) = (sorted[k], sorted[n - 1 - k]);
+    for x in &mut sorted[..k] { *x = lo; }
+    for x in &mut sorted[n - k..] { *x = hi; }
+    mean(&sorted)
+}
+
+/// Result of a Hampel filter pass
+#[derive(Clone, Debug, PartialEq)]
+pub struct HampelResult {
+    /// Input series with each outlier replaced by its window median
+    pub filtered: Vec<f64>,
+    /// Indices of the replaced samples
+    pub outliers: Vec<usize>,
+}
+
+/// Hampel filter: flag x[i] when |x[i] - m| > n_sigmas * 1.4826 * MAD, where m
+/// and MAD come from the centred window i - half_window ..= i + half_window
+/// (truncated at the ends of the series).
+pub fn hampel_filter(data: &[f64], half_window: usize, n_sigmas: f64) -> Result<HampelResult, StatsError> {
+    if data.is_empty() { return Err(StatsError::Empty); }
+    check_finite(data)?;
+    if half_window == 0 { return Err(StatsError::InvalidWindow { window: 0, len: data.len() }); }
+    if n_sigmas.is_nan() || n_sigmas < 0.0 {
+        return Err(StatsError::InvalidParameter { name: "n_sigmas", value: n_sigmas });
+    }
+
+    let mut filtered = data.to_vec();
+    let mut outliers = Vec::new();
+    for i in 0..data.len() {
+        let window = &data[i.saturating_sub(half_window)..(i + half_window + 1).min(data.len())];
+        let m = median(window)?;
+        let threshold = n_sigmas * scaled_mad(window)?;
+        if (data[i] - m).abs() > threshold {
+            filtered[i] = m;
+            outliers.push(i);
+        }
+    }
+    Ok(HampelResult { filtered, outliers })
+}
+
+fn sorted_with_cut(data: &[f64], proportion: f64) -> Result<(Vec<f64>, usize), StatsError> {
+    if data.is_empty() { return Err(StatsError::Empty); }
+    check_finite(data)?;
+    if !(0.0..0.5).contains(&proportion) {
+        return Err(StatsError::InvalidParameter { name: "proportion", value: proportion });
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let k = (proportion * data.len() as f64).floor() as usize;
+    Ok((sorted, k))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mad_known_result() {
+        // median 2, |deviations| = [1, 1, 0, 0, 2, 4, 7] -> median 1
+        let data = [1.0, 1.0, 2.0, 2.0, 4.0, 6.0, 9.0];
+        assert_eq!(mad(&data).unwrap(), 1.0);
+        assert!((scaled_mad(&data).unwrap() - MAD_NORMAL_SCALE).abs() < 1e-15);
+    }
+
+    #[test]
+    fn trimmed_and_winsorised_means_resist_outlier() {
+        let data = [10.0, 11.0, 9.0, 10.5, 9.5, 10.0, 10.2, 9.8, 10.1, 500.0];
+        assert!(mean(&data).unwrap() > 50.0);
+        // Trim one from each end: drops 9.0 and 500.0
+        assert!((trimmed_mean(&data, 0.1).unwrap() - 10.1375).abs() < 1e-12);
+        // Clamp 9.0 -> 9.5 and 500.0 -> 11.0
+        assert!((winsorised_mean(&data, 0.1).unwrap() - 10.16).abs() < 1e-12);
+    }
+
+    #[test]
+    fn zero_proportion_is_plain_mean() {
+        let data = [1.0, 2.0, 3.0, 10.0];
+        assert_eq!(trimmed_mean(&data, 0.0).unwrap(), mean(&data).unwrap());
+        assert_eq!(winsorised_mean(&data, 0.0).unwrap(), mean(&data).unwrap());
+        assert!(trimmed_mean(&data, 0.5).is_err());
+    }
+
+    #[test]
+    fn non_finite_samples_are_rejected() {
+        let data = [1.0, 2.0, f64::NAN, 3.0, 10.0];
+        assert!(matches!(trimmed_mean(&data, 0.2), Err(StatsError::NonFinite(x)) if x.is_nan()));
+        assert!(matches!(winsorised_mean(&data, 0.2), Err(StatsError::NonFinite(x)) if x.is_nan()));
+        assert!(matches!(mad(&data), Err(StatsError::NonFinite(x)) if x.is_nan()));
+        assert!(matches!(hampel_filter(&data, 1, 3.0), Err(StatsError::NonFinite(x)) if x.is_nan()));
+        assert_eq!(trimmed_mean(&[1.0, f64::INFINITY], 0.0).unwrap_err(), StatsError::NonFinite(f64::INFINITY));
+    }
+
+    #[test]
+    fn hampel_replaces_spike_with_window_median() {
+        let mut data: Vec<f64> = (0..50).map(|i| 65.0 + (i as f64 * 0.7).sin()).collect();
+        data[20] = 99.0;
+        let r = hampel_filter(&data, 5, 3.0).unwrap();
+        assert_eq!(r.outliers, vec![20]);
+        assert!((r.filtered[20] - 65.0).abs() < 1.5);
+        assert_eq!(r.filtered[19], data[19]);
+    }
+
+    #[test]
+    fn hampel_rejects_bad_parameters() {
+        assert_eq!(hampel_filter(&[1.0], 0, 3.0).unwrap_err(), StatsError::InvalidWindow { window: 0, len: 1 });
+        assert!(hampel_filter(&[1.0], 2, -1.0).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! Rolling-window and exponentially weighted statistics over a time series.
+//!
+//! Rolling functions use trailing windows and return one value per full
+//! window: output[i] summarises data[i .. i + window], so the output has
+//! `len - window + 1` entries. Non-finite samples are rejected: one NaN
+//! would otherwise poison every later running sum or sorted window.
+
+use crate::{check_finite, StatsError};
+
+/// Mean of each trailing window, via a running sum
+pub fn rolling_mean(data: &[f64], window: usize) -> Result<Vec<f64>, StatsError> {
+    check_window(data, window, 1)?;
+    let w = window as f64;
+    let mut sum: f64 = data[..window].iter().sum();
+    let mut out = Vec::with_capacity(data.len() - window + 1);
+    out.push(sum / w);
+    for i in window..data.len() {
+        sum += data[i] - data[i - window];
+        out.push(sum / w);
+    }
+    Ok(out)
+}
+
+/// Sample standard deviation (N-1) of each trailing window.
+/// Uses Welford's update with removal, so each step is O(1).
+pub fn rolling_std(data: &[f64], window: usize) -> Result<Vec<f64>, StatsError> {
+    check_window(data, window, 2)?;
+    let w = window as f64;
+    let (mut mean, mut m2) = (0.0, 0.0);
+    for (k, &x) in data[..window].iter().enumerate() {
+        let delta = x - mean;
+        mean += delta / (k + 1) as f64;
+        m2 += delta * (x - mean);
+    }
+    let mut out = Vec::with_capacity(data.len() - window + 1);
+    out.push((m2.max(0.0) / (w - 1.0)).sqrt());
+    for i in window..data.len() {
+        let (new, old) = (data[i], data[i - window]);
+        let delta = new - old;
+        let old_mean = mean;
+        mean += delta / w;
+        m2 += delta * (new - mean + old - old_mean);
+        out.push((m2.max(0.0) / (w - 1.0)).sqrt());
+    }
+    Ok(out)
+}
+
+/// Percentile (linear interpolation, p in [0.0, 1.0]) of each trailing window.
+/// Keeps the window sorted and updates it by binary-search insert/remove.
+pub fn rolling_percentile(data: &[f64], window: usize, p: f64) -> Result<Vec<f64>, StatsError> {
+    check_window(data, window, 1)?;
+    if !(0.0..=1.0).contains(&p) { return Err(StatsError::InvalidPercentile(p)); }
+
+    let mut sorted = data[..window].to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let idx = p * (window - 1) as f64;
+    let (lo, frac) = (idx.floor() as usize, idx.fract());
+    let hi = (lo + 1).min(window - 1);
+    let interpolate = |s: &[f64]| s[lo] + frac * (s[hi] - s[lo]);
+
+    let mut out = Vec::with_capacity(data.len() - window + 1);
+    out.push(interpolate(&sorted));
+    for i in window..data.len() {
+        let old = data[i - window];
+        let pos = sorted.partition_point(|&v| v < old);
+        sorted.remove(pos);
+        let new = data[i];
+        let pos = sorted.partition_point(|&v| v < new);
+        sorted.insert(pos, new);
+        out.push(interpolate(&sorted));
+    }
+    Ok(out)
+}
+
+/// Exponentially weighted moving average: s[0] = x[0], s[t] = α x[t] + (1-α) s[t-1].
+/// `alpha` must be in (0.0, 1.0]; larger values react faster.
+pub fn ewma(data: &[f64], alpha: f64) -> Result<Vec<f64>, StatsError> {
+    if data.is_empty() { return Err(StatsError::Empty); }
+    if !(alpha > 0.0 && alpha <= 1.0) {
+        return Err(StatsError::InvalidParameter { name: "alpha", value: alpha });
+    }
+    check_finite(data)?;
+    let mut s = data[0];
+    Ok(data.iter().map(|&x| { s = alpha * x + (1.0 - alpha) * s; s }).collect())
+}
+
+fn check_window(data: &[f64], window: usize, min: usize) -> Result<(), StatsError> {
+    if data.is_empty() { return Err(StatsError::Empty); }
+    if window < min || window > data.len() {
+        return Err(StatsError::InvalidWindow { window, len: data.len() });
+    }
+    check_finite(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mean, percentile, sample_variance};
+
+    fn series() -> Vec<f64> {
+        (0..60).map(|i| 20.0 + (i as f64 * 0.3).sin() * 5.0 + (i % 7) as f64).collect()
+    }
+
+    #[test]
+    fn rolling_mean_matches_naive_windows() {
+        let data = series();
+        let r = rolling_mean(&data, 8).unwrap();
+        assert_eq!(r.len(), data.len() - 7);
+        for (i, v) in r.iter().enumerate() {
+            assert!((v - mean(&data[i..i + 8]).unwrap()).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn rolling_std_matches_naive_windows() {
+        let data = series();
+        let r = rolling_std(&data, 10).unwrap();
+        for (i, v) in r.iter().enumerate() {
+            assert!((v - sample_variance(&data[i..i + 10]).unwrap().sqrt()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn rolling_percentile_matches_naive_windows() {
+        let data = series();
+        for p in [0.0, 0.5, 0.9, 1.0] {
+            let r = rolling_percentile(&data, 9, p).unwrap();
+            for (i, v) in r.iter().enumerate() {
+                assert_eq!(*v, percentile(&data[i..i + 9], p).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn ewma_known_values() {
+        let s = ewma(&[10.0, 20.0, 20.0], 0.5).unwrap();
+        assert_eq!(s, vec![10.0, 15.0, 17.5]);
+        assert_eq!(ewma(&[1.0, 5.0], 1.0).unwrap(), vec![1.0, 5.0]);
+    }
+
+    #[test]
+    fn invalid_windows_are_errors() {
+        let data = [1.0, 2.0, 3.0];
+        assert_eq!(rolling_mean(&data, 4).unwrap_err(), StatsError::InvalidWindow { window: 4, len: 3 });
+        assert_eq!(rolling_std(&data, 1).unwrap_err(), StatsError::InvalidWindow { window: 1, len: 3 });
+        assert!(ewma(&data, 0.0).is_err());
+    }
+
+    #[test]
+    fn nan_mid_series_is_rejected() {
+        // The NaN only enters the sorted window after the first step
+        let data = [1.0, 2.0, 3.0, 5.0, f64::NAN, 6.0, 7.0, 8.0];
+        assert!(matches!(rolling_percentile(&data, 3, 0.5), Err(StatsError::NonFinite(x)) if x.is_nan()));
+        assert!(matches!(rolling_percentile(&data[3..], 3, 0.5), Err(StatsError::NonFinite(x)) if x.is_nan()));
+        assert!(matches!(rolling_mean(&data, 3), Err(StatsError::NonFinite(x)) if x.is_nan()));
+        assert!(matches!(rolling_std(&data, 3), Err(StatsError::NonFinite(x)) if x.is_nan()));
+        assert!(matches!(ewma(&data, 0.5), Err(StatsError::NonFinite(x)) if x.is_nan()));
+    }
+}
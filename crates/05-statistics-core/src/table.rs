@@ -0,0 +1,229 @@
+//! CSV / TSV loading for the command-line tools: column selection by name
+//! or index, numeric parsing with invalid-cell tracking, and group-by.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Field delimiter from a `--delimiter` value: a single character, or 'tab'.
+/// Without one, tab for `.tsv` / `.tab` files and ',' for anything else.
+pub fn delimiter_for(spec: Option<&str>, path: &Path) -> Result<u8, String> {
+    match spec {
+        Some("tab") | Some("\\t") => Ok(b'\t'),
+        Some(d) if d.len() == 1 => Ok(d.as_bytes()[0]),
+        Some(d) => Err(format!("delimiter must be a single character or 'tab', got '{d}'")),
+        None if matches!(path.extension().and_then(|e| e.to_str()), Some("tsv" | "tab")) => Ok(b'\t'),
+        None => Ok(b','),
+    }
+}
+
+/// Raw text cells of a delimited file.
+pub struct Table {
+    pub headers: Vec<String>,
+    pub records: Vec<Vec<String>>,
+}
+
+/// The numeric values found in one column (optionally one group's rows of it).
+pub struct NumericColumn {
+    pub name: String,
+    pub values: Vec<f64>,
+    /// Cells that were empty or failed to parse
+    pub skipped: usize,
+}
+
+/// A non-empty cell that could not be parsed as a number.
+pub struct InvalidCell {
+    /// 1-based data row, not counting the header
+    pub row: usize,
+    pub column: String,
+    pub value: String,
+}
+
+impl Table {
+    /// Read a delimited file. Without a header row, columns are named by their 0-based index.
+    pub fn read<R: Read>(reader: R, delimiter: u8, has_header: bool) -> Result<Self, csv::Error> {
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(has_header)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+
+        let mut records: Vec<Vec<String>> = Vec::new();
+        for rec in rdr.records() {
+            records.push(rec?.iter().map(str::to_string).collect());
+        }
+        let headers = if has_header {
+            rdr.headers()?.iter().map(str::to_string).collect()
+        } else {
+            let width = records.iter().map(Vec::len).max().unwrap_or(0);
+            (0..width).map(|i| i.to_string()).collect()
+        };
+        Ok(Self { headers, records })
+    }
+
+    /// Read the file at `path`, or stdin when `path` is '-'.
+    pub fn open(path: &Path, delimiter: u8, has_header: bool) -> Result<Self, csv::Error> {
+        if path.as_os_str() == "-" { return Self::read(io::stdin().lock(), delimiter, has_header); }
+        let file = File::open(path).map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        Self::read(file, delimiter, has_header)
+    }
+
+    /// Resolve a column by exact header name, falling back to a 0-based index.
+    pub fn resolve(&self, spec: &str) -> Result<usize, String> {
+        if let Some(i) = self.headers.iter().position(|h| h == spec) { return Ok(i); }
+        match spec.parse::<usize>() {
+            Ok(i) if i < self.headers.len() => Ok(i),
+            _ => Err(format!("no column named or numbered '{spec}' (columns: {})", self.headers.join(", "))),
+        }
+    }
+
+    /// Columns with at least one numeric cell, in file order
+    pub fn numeric_columns(&self) -> Vec<usize> {
+        (0..self.headers.len())
+            .filter(|&c| self.records.iter().any(|r| parse_cell(r.get(c)).is_some()))
+            .collect()
+    }
+
+    /// Numeric values of column `col` over the given rows, plus any unparseable cells.
+    pub fn numeric_column(&self, col: usize, rows: &[usize]) -> (NumericColumn, Vec<InvalidCell>) {
+        let name = self.headers[col].clone();
+        let mut values = Vec::with_capacity(rows.len());
+        let mut invalid = Vec::new();
+        let mut skipped = 0;
+        for &r in rows {
+            let cell = self.records[r].get(col);
+            match parse_cell(cell) {
+                Some(v) => values.push(v),
+                None => {
+                    skipped += 1;
+                    if let Some(text) = cell.filter(|c| !c.is_empty()) {
+                        invalid.push(InvalidCell { row: r + 1, column: name.clone(), value: text.clone() });
+                    }
+                }
+            }
+        }
+        (NumericColumn { name, values, skipped }, invalid)
+    }
+
+    /// The values of `columns`, in that order, for every row where all of
+    /// them are numeric, and the number of rows dropped for a missing or
+    /// non-numeric cell.
+    pub fn complete_rows(&self, columns: &[usize]) -> (Vec<Vec<f64>>, usize) {
+        let mut rows = Vec::new();
+        for rec in &self.records {
+            if let Some(row) = columns.iter().map(|&c| parse_cell(rec.get(c))).collect() { rows.push(row); }
+        }
+        let dropped = self.records.len() - rows.len();
+        (rows, dropped)
+    }
+
+    pub fn all_rows(&self) -> Vec<usize> {
+        (0..self.records.len()).collect()
+    }
+
+    /// Row indices grouped by the value of column `key`, in first-seen order.
+    pub fn group_by(&self, key: usize) -> Vec<(String, Vec<usize>)> {
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for (r, rec) in self.records.iter().enumerate() {
+            let k = rec.get(key).map_or("", String::as_str);
+            match index.get(k) {
+                Some(&g) => groups[g].1.push(r),
+                None => {
+                    index.insert(k, groups.len());
+                    groups.push((k.to_string(), vec![r]));
+                }
+            }
+        }
+        groups
+    }
+}
+
+/// Parse a cell as a finite number; empty, missing and non-numeric cells give None.
+fn parse_cell(cell: Option<&String>) -> Option<f64> {
+    cell.and_then(|c| c.parse::<f64>().ok()).filter(|v| v.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "site,temp,load\nA,61.5,0.4\nB,n/a,0.9\nA,63.0,\nB,70.25,0.7\n";
+
+    fn table() -> Table {
+        Table::read(CSV.as_bytes(), b',', true).unwrap()
+    }
+
+    #[test]
+    fn resolves_columns_by_name_then_index() {
+        let t = table();
+        assert_eq!(t.resolve("load").unwrap(), 2);
+        assert_eq!(t.resolve("1").unwrap(), 1);
+        assert!(t.resolve("humidity").is_err());
+        assert!(t.resolve("7").is_err());
+    }
+
+    #[test]
+    fn numeric_columns_skip_text_only_columns() {
+        assert_eq!(table().numeric_columns(), vec![1, 2]);
+    }
+
+    #[test]
+    fn invalid_cells_are_reported_and_empty_cells_skipped() {
+        let t = table();
+        let (temp, invalid) = t.numeric_column(1, &t.all_rows());
+        assert_eq!(temp.values, vec![61.5, 63.0, 70.25]);
+        assert_eq!(temp.skipped, 1);
+        assert_eq!(invalid.len(), 1);
+        assert_eq!((invalid[0].row, invalid[0].value.as_str()), (2, "n/a"));
+
+        let (load, invalid) = t.numeric_column(2, &t.all_rows());
+        assert_eq!(load.skipped, 1);
+        assert!(invalid.is_empty());
+    }
+
+    #[test]
+    fn group_by_preserves_first_seen_order() {
+        let groups = table().group_by(0);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0], ("A".to_string(), vec![0, 2]));
+        assert_eq!(groups[1], ("B".to_string(), vec![1, 3]));
+    }
+
+    #[test]
+    fn group_by_handles_many_keys_and_short_rows() {
+        let rows: String = (0..20_000).map(|i| format!("k{},{i}\n", i % 5_000)).collect();
+        let t = Table::read(format!("key,v\n{rows}x").as_bytes(), b',', true).unwrap();
+        assert_eq!(t.group_by(1).len(), 20_001);
+        let groups = t.group_by(0);
+        assert_eq!(groups.len(), 5_001);
+        assert_eq!(groups[7], ("k7".to_string(), vec![7, 5_007, 10_007, 15_007]));
+        // The last row has no second field and groups under the empty key
+        assert_eq!(t.group_by(1).last().unwrap(), &(String::new(), vec![20_000]));
+    }
+
+    #[test]
+    fn complete_rows_need_every_column() {
+        let (rows, dropped) = table().complete_rows(&[2, 1]);
+        assert_eq!(rows, vec![vec![0.4, 61.5], vec![0.7, 70.25]]);
+        assert_eq!(dropped, 2);
+    }
+
+    #[test]
+    fn delimiter_comes_from_the_flag_or_the_extension() {
+        assert_eq!(delimiter_for(None, Path::new("data.tsv")), Ok(b'\t'));
+        assert_eq!(delimiter_for(None, Path::new("-")), Ok(b','));
+        assert_eq!(delimiter_for(Some("tab"), Path::new("data.csv")), Ok(b'\t'));
+        assert_eq!(delimiter_for(Some(";"), Path::new("data.tsv")), Ok(b';'));
+        assert!(delimiter_for(Some("::"), Path::new("data.csv")).is_err());
+    }
+
+    #[test]
+    fn headerless_tsv_names_columns_by_index() {
+        let t = Table::read("1\t2\n3\t4\n".as_bytes(), b'\t', false).unwrap();
+        assert_eq!(t.headers, vec!["0", "1"]);
+        assert_eq!(t.records.len(), 2);
+    }
+}
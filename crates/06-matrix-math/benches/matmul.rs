@@ -0,0 +1,46 @@
+//! Naive triple loop vs the blocked kernel behind `Matrix::matmul`.
+//!
+//! cargo bench -p matrix-math
+//! cargo bench -p matrix-math --features rayon
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use matrix_math::Matrix;
+
+fn filled(n: usize, seed: u64) -> Matrix {
+    let mut s = seed;
+    let data = (0..n * n).map(|_| {
+        s = s.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (s >> 11) as f64 / (1u64 << 53) as f64
+    }).collect();
+    Matrix::from_vec(n, n, data)
+}
+
+/// The textbook i-j-k loop, indexing through `Matrix` like the challenge does
+fn naive(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut c = Matrix::new(a.rows, b.cols);
+    for i in 0..a.rows {
+        for j in 0..b.cols {
+            c[(i, j)] = (0..a.cols).map(|k| a[(i, k)] * b[(k, j)]).sum();
+        }
+    }
+    c
+}
+
+fn bench_matmul(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matmul");
+    group.sample_size(10);
+    for n in [64, 256, 512] {
+        let (a, b) = (filled(n, 1), filled(n, 2));
+        group.throughput(Throughput::Elements((2 * n * n * n) as u64));
+        group.bench_with_input(BenchmarkId::new("naive", n), &n, |bench, _| {
+            bench.iter(|| naive(black_box(&a), black_box(&b)))
+        });
+        group.bench_with_input(BenchmarkId::new("blocked", n), &n, |bench, _| {
+            bench.iter(|| black_box(&a).matmul(black_box(&b)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_matmul);
+criterion_main!(benches);
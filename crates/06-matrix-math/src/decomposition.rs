@@ -0,0 +1,542 @@
+//! LU, QR and Cholesky factorisations.
+//!
+//! Factorise once, then solve against as many right-hand sides as you like -
+//! each solve is O(n²) instead of the O(n³) of a fresh inverse, and never
+//! forms A⁻¹ explicitly (which loses accuracy on ill-conditioned systems).
+
+use crate::{Matrix, Norm};
+
+/// LU factorisation with partial pivoting: P A = L U.
+///
+/// `L` (unit diagonal) and `U` are packed into one matrix; `perm[i]` is the
+/// row of A that ended up in row i.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lu {
+    lu: Matrix,
+    perm: Vec<usize>,
+    sign: f64,
+    /// ‖A‖₁, kept for the condition estimate
+    norm_1: f64,
+}
+
+impl Lu {
+    /// Factorise a square matrix. Returns None if it is non-square or singular;
+    /// a NaN or infinite entry meets a non-finite pivot and counts as singular.
+    pub fn new(a: &Matrix) -> Option<Self> {
+        if a.rows != a.cols { return None; }
+        let n = a.rows;
+        let mut lu = a.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut sign = 1.0;
+        let tol = max_abs(a) * n as f64 * f64::EPSILON;
+        let norm_1 = a.norm(Norm::One);
+
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&i, &j| lu[(i, k)].abs().total_cmp(&lu[(j, k)].abs()))
+                .unwrap();
+            if !lu[(p, k)].is_finite() || lu[(p, k)].abs() <= tol { return None; }
+            if p != k {
+                swap_rows(&mut lu, p, k);
+                perm.swap(p, k);
+                sign = -sign;
+            }
+            for i in k + 1..n {
+                let f = lu[(i, k)] / lu[(k, k)];
+                lu[(i, k)] = f;
+                for j in k + 1..n {
+                    lu[(i, j)] -= f * lu[(k, j)];
+                }
+            }
+        }
+        Some(Self { lu, perm, sign, norm_1 })
+    }
+
+    /// Solve A x = b. Returns None if `b` has the wrong length.
+    pub fn solve(&self, b: &[f64]) -> Option<Vec<f64>> {
+        let n = self.lu.rows;
+        if b.len() != n { return None; }
+        let mut x: Vec<f64> = self.perm.iter().map(|&p| b[p]).collect();
+        for i in 0..n {
+            for j in 0..i { x[i] -= self.lu[(i, j)] * x[j]; }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n { x[i] -= self.lu[(i, j)] * x[j]; }
+            x[i] /= self.lu[(i, i)];
+        }
+        Some(x)
+    }
+
+    /// Solve A X = B for every column of B at once.
+    pub fn solve_many(&self, b: &Matrix) -> Option<Matrix> {
+        solve_columns(b, self.lu.rows, |col| self.solve(col))
+    }
+
+    /// Solve Aᵀ x = b with the same factors: Aᵀ = Uᵀ Lᵀ P.
+    /// Returns None if `b` has the wrong length.
+    pub fn solve_transpose(&self, b: &[f64]) -> Option<Vec<f64>> {
+        let n = self.lu.rows;
+        if b.len() != n { return None; }
+        let mut z = b.to_vec();
+        for i in 0..n {
+            for j in 0..i { z[i] -= self.lu[(j, i)] * z[j]; }
+            z[i] /= self.lu[(i, i)];
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n { z[i] -= self.lu[(j, i)] * z[j]; }
+        }
+        let mut x = vec![0.0; n];
+        for (i, &p) in self.perm.iter().enumerate() { x[p] = z[i]; }
+        Some(x)
+    }
+
+    /// Estimate of the 1-norm condition number κ₁(A) = ‖A‖₁ ‖A⁻¹‖₁ in O(n²),
+    /// by Hager's method with Higham's refinements (as LAPACK's `dgecon` does).
+    /// Never exceeds the true value and is almost always within a factor of 3.
+    pub fn condition_estimate(&self) -> f64 {
+        let n = self.lu.rows;
+        if n == 0 { return 0.0; }
+        let solve = |v: &[f64]| self.solve(v).expect("length n");
+        let norm_1 = |v: &[f64]| v.iter().map(|x| x.abs()).sum::<f64>();
+
+        // Hunt for the column j of A⁻¹ with the largest 1-norm: step to the
+        // unit vector whose gradient component is largest until none improves
+        let mut x = vec![1.0 / n as f64; n];
+        let mut estimate = 0.0;
+        for step in 0..5 {
+            let y = solve(&x);
+            estimate = norm_1(&y);
+            let signs: Vec<f64> = y.iter().map(|&v| if v >= 0.0 { 1.0 } else { -1.0 }).collect();
+            let z = self.solve_transpose(&signs).expect("length n");
+            let (j, z_max) = z.iter().map(|v| v.abs()).enumerate().fold((0, 0.0), |best, (i, v)| if v > best.1 { (i, v) } else { best });
+            let zx: f64 = z.iter().zip(&x).map(|(a, b)| a * b).sum();
+            if step > 0 && z_max <= zx { break; }
+            x = vec![0.0; n];
+            x[j] = 1.0;
+        }
+        // Higham's safeguard: an alternating vector catches the cases above that stall
+        let alt: Vec<f64> = (0..n)
+            .map(|i| {
+                let s = if i % 2 == 0 { 1.0 } else { -1.0 };
+                s * (1.0 + i as f64 / (n as f64 - 1.0).max(1.0))
+            })
+            .collect();
+        let alt_estimate = 2.0 * norm_1(&solve(&alt)) / (3.0 * n as f64);
+        self.norm_1 * estimate.max(alt_estimate)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        (0..self.lu.rows).map(|i| self.lu[(i, i)]).product::<f64>() * self.sign
+    }
+
+    pub fn inverse(&self) -> Matrix {
+        self.solve_many(&Matrix::identity(self.lu.rows)).expect("identity has matching rows")
+    }
+
+    /// Unit lower-triangular factor
+    pub fn l(&self) -> Matrix {
+        let n = self.lu.rows;
+        let mut l = Matrix::identity(n);
+        for i in 0..n {
+            for j in 0..i { l[(i, j)] = self.lu[(i, j)]; }
+        }
+        l
+    }
+
+    /// Upper-triangular factor
+    pub fn u(&self) -> Matrix {
+        let n = self.lu.rows;
+        let mut u = Matrix::new(n, n);
+        for i in 0..n {
+            for j in i..n { u[(i, j)] = self.lu[(i, j)]; }
+        }
+        u
+    }
+
+    /// Row permutation: row i of P A is row `permutation()[i]` of A
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+}
+
+/// Householder QR factorisation of an m×n matrix (m >= n): A = Q R.
+///
+/// R sits in the upper triangle; the Householder vectors (with an implicit
+/// leading 1) sit below it, LAPACK-style, with their scale factors in `tau`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Qr {
+    qr: Matrix,
+    tau: Vec<f64>,
+}
+
+impl Qr {
+    /// Factorise a matrix with at least as many rows as columns.
+    pub fn new(a: &Matrix) -> Option<Self> {
+        if a.rows < a.cols { return None; }
+        let (m, n) = (a.rows, a.cols);
+        let mut qr = a.clone();
+        let mut tau = vec![0.0; n];
+
+        for k in 0..n {
+            let norm = (k..m).map(|i| qr[(i, k)] * qr[(i, k)]).sum::<f64>().sqrt();
+            if norm == 0.0 { continue; }
+            // Reflect onto -sign(x0)·‖x‖ e1 so that x0 - alpha never cancels
+            let x0 = qr[(k, k)];
+            let alpha = if x0 > 0.0 { -norm } else { norm };
+            let v0 = x0 - alpha;
+            for i in k + 1..m { qr[(i, k)] /= v0; }
+            tau[k] = (alpha - x0) / alpha;
+            qr[(k, k)] = alpha;
+
+            for j in k + 1..n {
+                let w = qr[(k, j)] + (k + 1..m).map(|i| qr[(i, k)] * qr[(i, j)]).sum::<f64>();
+                qr[(k, j)] -= tau[k] * w;
+                for i in k + 1..m {
+                    let v = qr[(i, k)];
+                    qr[(i, j)] -= tau[k] * v * w;
+                }
+            }
+        }
+        Some(Self { qr, tau })
+    }
+
+    /// Least-squares solution minimising ‖A x - b‖₂ (the exact solution when A is square).
+    /// Returns None if `b` has the wrong length or A is rank deficient.
+    pub fn solve(&self, b: &[f64]) -> Option<Vec<f64>> {
+        let (m, n) = (self.qr.rows, self.qr.cols);
+        if b.len() != m { return None; }
+        let tol = (0..n).map(|i| self.qr[(i, i)].abs()).fold(0.0, f64::max) * m as f64 * f64::EPSILON;
+        if (0..n).any(|i| self.qr[(i, i)].abs() <= tol) { return None; }
+
+        let y = self.apply_qt(b);
+        let mut x = y[..n].to_vec();
+        for i in (0..n).rev() {
+            for j in i + 1..n { x[i] -= self.qr[(i, j)] * x[j]; }
+            x[i] /= self.qr[(i, i)];
+        }
+        Some(x)
+    }
+
+    /// Least-squares solve for every column of B at once.
+    pub fn solve_many(&self, b: &Matrix) -> Option<Matrix> {
+        solve_columns(b, self.qr.rows, |col| self.solve(col))
+    }
+
+    /// Residual norm ‖A x - b‖₂ of the least-squares solution, without computing x
+    pub fn residual_norm(&self, b: &[f64]) -> Option<f64> {
+        if b.len() != self.qr.rows { return None; }
+        let y = self.apply_qt(b);
+        Some(y[self.qr.cols..].iter().map(|v| v * v).sum::<f64>().sqrt())
+    }
+
+    /// Thin orthonormal factor (m×n)
+    pub fn q(&self) -> Matrix {
+        let (m, n) = (self.qr.rows, self.qr.cols);
+        let mut q = Matrix::new(m, n);
+        for i in 0..n { q[(i, i)] = 1.0; }
+        for k in (0..n).rev() {
+            for j in 0..n {
+                let w = q[(k, j)] + (k + 1..m).map(|i| self.qr[(i, k)] * q[(i, j)]).sum::<f64>();
+                q[(k, j)] -= self.tau[k] * w;
+                for i in k + 1..m { q[(i, j)] -= self.tau[k] * self.qr[(i, k)] * w; }
+            }
+        }
+        q
+    }
+
+    /// Upper-triangular factor (n×n)
+    pub fn r(&self) -> Matrix {
+        let n = self.qr.cols;
+        let mut r = Matrix::new(n, n);
+        for i in 0..n {
+            for j in i..n { r[(i, j)] = self.qr[(i, j)]; }
+        }
+        r
+    }
+
+    /// Qᵀ b, applying the reflectors in order
+    fn apply_qt(&self, b: &[f64]) -> Vec<f64> {
+        let (m, n) = (self.qr.rows, self.qr.cols);
+        let mut y = b.to_vec();
+        for k in 0..n {
+            let w = y[k] + (k + 1..m).map(|i| self.qr[(i, k)] * y[i]).sum::<f64>();
+            y[k] -= self.tau[k] * w;
+            for (i, yi) in y.iter_mut().enumerate().skip(k + 1) { *yi -= self.tau[k] * self.qr[(i, k)] * w; }
+        }
+        y
+    }
+}
+
+/// Cholesky factorisation of a symmetric positive-definite matrix: A = L Lᵀ.
+/// About twice as fast as LU and needs no pivoting.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cholesky {
+    l: Matrix,
+}
+
+impl Cholesky {
+    /// Returns None if A is non-square, not symmetric or not positive definite.
+    pub fn new(a: &Matrix) -> Option<Self> {
+        if a.rows != a.cols { return None; }
+        let n = a.rows;
+        let sym_tol = max_abs(a) * 1e-12;
+        for i in 0..n {
+            for j in 0..i {
+                if (a[(i, j)] - a[(j, i)]).abs() > sym_tol { return None; }
+            }
+        }
+
+        let mut l = Matrix::new(n, n);
+        for j in 0..n {
+            let d = a[(j, j)] - (0..j).map(|k| l[(j, k)] * l[(j, k)]).sum::<f64>();
+            if d.is_nan() || d <= 0.0 { return None; }
+            l[(j, j)] = d.sqrt();
+            for i in j + 1..n {
+                let s = a[(i, j)] - (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum::<f64>();
+                l[(i, j)] = s / l[(j, j)];
+            }
+        }
+        Some(Self { l })
+    }
+
+    /// Solve A x = b by forward then back substitution.
+    pub fn solve(&self, b: &[f64]) -> Option<Vec<f64>> {
+        let n = self.l.rows;
+        if b.len() != n { return None; }
+        let mut x = b.to_vec();
+        for i in 0..n {
+            for j in 0..i { x[i] -= self.l[(i, j)] * x[j]; }
+            x[i] /= self.l[(i, i)];
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n { x[i] -= self.l[(j, i)] * x[j]; }
+            x[i] /= self.l[(i, i)];
+        }
+        Some(x)
+    }
+
+    /// Solve A X = B for every column of B at once.
+    pub fn solve_many(&self, b: &Matrix) -> Option<Matrix> {
+        solve_columns(b, self.l.rows, |col| self.solve(col))
+    }
+
+    pub fn determinant(&self) -> f64 {
+        (0..self.l.rows).map(|i| self.l[(i, i)] * self.l[(i, i)]).product()
+    }
+
+    pub fn inverse(&self) -> Matrix {
+        self.solve_many(&Matrix::identity(self.l.rows)).expect("identity has matching rows")
+    }
+
+    /// Lower-triangular factor
+    pub fn l(&self) -> &Matrix {
+        &self.l
+    }
+}
+
+impl Matrix {
+    pub fn lu(&self) -> Option<Lu> {
+        Lu::new(self)
+    }
+
+    pub fn qr(&self) -> Option<Qr> {
+        Qr::new(self)
+    }
+
+    pub fn cholesky(&self) -> Option<Cholesky> {
+        Cholesky::new(self)
+    }
+
+    /// Solve the square system A x = b via LU. Returns None if A is singular.
+    pub fn solve(&self, b: &[f64]) -> Option<Vec<f64>> {
+        self.lu()?.solve(b)
+    }
+
+    /// Least-squares solution of A x ≈ b via QR. Returns None if A is rank deficient.
+    pub fn least_squares(&self, b: &[f64]) -> Option<Vec<f64>> {
+        self.qr()?.solve(b)
+    }
+}
+
+/// Run `solve` on each column of `b`, collecting the results as columns.
+fn solve_columns(b: &Matrix, rows: usize, solve: impl Fn(&[f64]) -> Option<Vec<f64>>) -> Option<Matrix> {
+    if b.rows != rows { return None; }
+    let mut out: Option<Matrix> = None;
+    for c in 0..b.cols {
+        let col: Vec<f64> = (0..b.rows).map(|r| b[(r, c)]).collect();
+        let x = solve(&col)?;
+        let out = out.get_or_insert_with(|| Matrix::new(x.len(), b.cols));
+        for (r, v) in x.into_iter().enumerate() { out[(r, c)] = v; }
+    }
+    Some(out.unwrap_or_else(|| Matrix::new(0, 0)))
+}
+
+fn swap_rows(m: &mut Matrix, a: usize, b: usize) {
+    for c in 0..m.cols {
+        let t = m[(a, c)];
+        m[(a, c)] = m[(b, c)];
+        m[(b, c)] = t;
+    }
+}
+
+fn max_abs(m: &Matrix) -> f64 {
+    m.data.iter().fold(0.0, |acc, v| acc.max(v.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64, tol: f64) -> bool {
+        (a - b).abs() <= tol
+    }
+
+    fn assert_matrix_close(a: &Matrix, b: &Matrix, tol: f64) {
+        assert_eq!((a.rows, a.cols), (b.rows, b.cols));
+        for r in 0..a.rows {
+            for c in 0..a.cols {
+                assert!(close(a[(r, c)], b[(r, c)], tol), "[{r},{c}] {} vs {}", a[(r, c)], b[(r, c)]);
+            }
+        }
+    }
+
+    fn hilbert(n: usize) -> Matrix {
+        let mut h = Matrix::new(n, n);
+        for r in 0..n {
+            for c in 0..n { h[(r, c)] = 1.0 / (r + c + 1) as f64; }
+        }
+        h
+    }
+
+    #[test]
+    fn lu_solves_known_system() {
+        // x = [2, 3, -1]
+        let a = Matrix::from_vec(3, 3, vec![2.0, 1.0, -1.0, -3.0, -1.0, 2.0, -2.0, 1.0, 2.0]);
+        let lu = a.lu().unwrap();
+        let x = lu.solve(&[8.0, -11.0, -3.0]).unwrap();
+        for (xi, ei) in x.iter().zip([2.0, 3.0, -1.0]) {
+            assert!(close(*xi, ei, 1e-12));
+        }
+        assert!(close(lu.determinant(), -1.0, 1e-12));
+    }
+
+    #[test]
+    fn lu_pivots_past_zero_diagonal() {
+        let a = Matrix::from_vec(2, 2, vec![0.0, 1.0, 1.0, 1.0]);
+        let lu = a.lu().unwrap();
+        assert_eq!(lu.permutation(), &[1, 0]);
+        assert_eq!(lu.solve(&[2.0, 5.0]).unwrap(), vec![3.0, 2.0]);
+        assert!(close(lu.determinant(), -1.0, 1e-15));
+    }
+
+    #[test]
+    fn lu_treats_non_finite_entries_as_singular() {
+        assert!(Matrix::from_vec(2, 2, vec![f64::NAN, 1.0, 1.0, 1.0]).lu().is_none());
+        assert!(Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, f64::NAN]).lu().is_none());
+        assert!(Matrix::from_vec(2, 2, vec![1.0, f64::INFINITY, 3.0, 4.0]).lu().is_none());
+    }
+
+    #[test]
+    fn lu_factors_reconstruct_permuted_matrix() {
+        let a = Matrix::from_vec(3, 3, vec![1.0, 2.0, 3.0, 0.0, 1.0, 4.0, 5.0, 6.0, 0.0]);
+        let lu = a.lu().unwrap();
+        let mut pa = Matrix::new(3, 3);
+        for (i, &p) in lu.permutation().iter().enumerate() {
+            for c in 0..3 { pa[(i, c)] = a[(p, c)]; }
+        }
+        assert_matrix_close(&lu.l().matmul(&lu.u()).unwrap(), &pa, 1e-12);
+        assert_matrix_close(&a.matmul(&lu.inverse()).unwrap(), &Matrix::identity(3), 1e-12);
+    }
+
+    #[test]
+    fn lu_solve_transpose_uses_the_same_factors() {
+        // Non-symmetric and needs pivoting, so Aᵀ x = b differs from A x = b
+        let a = Matrix::from_vec(3, 3, vec![1.0, 2.0, 3.0, 0.0, 1.0, 4.0, 5.0, 6.0, 0.0]);
+        let b = [1.0, -2.0, 0.5];
+        let x = a.lu().unwrap().solve_transpose(&b).unwrap();
+        let at_x = a.transpose().mul_vec(&x).unwrap();
+        for (got, want) in at_x.iter().zip(b) {
+            assert!(close(*got, want, 1e-12));
+        }
+    }
+
+    #[test]
+    fn singular_and_mismatched_inputs_are_none() {
+        assert!(Matrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 4.0]).lu().is_none());
+        assert!(Matrix::new(2, 3).lu().is_none());
+        assert!(Matrix::new(2, 3).qr().is_none());
+        let lu = Matrix::identity(2).lu().unwrap();
+        assert!(lu.solve(&[1.0]).is_none());
+        assert!(lu.solve_many(&Matrix::new(3, 1)).is_none());
+    }
+
+    #[test]
+    fn solve_many_matches_column_by_column_solves() {
+        let a = Matrix::from_vec(3, 3, vec![4.0, 1.0, 2.0, 1.0, 5.0, 1.0, 2.0, 1.0, 6.0]);
+        let b = Matrix::from_vec(3, 2, vec![1.0, 0.0, 2.0, 1.0, 3.0, -1.0]);
+        let lu = a.lu().unwrap().solve_many(&b).unwrap();
+        let chol = a.cholesky().unwrap().solve_many(&b).unwrap();
+        let qr = a.qr().unwrap().solve_many(&b).unwrap();
+        assert_matrix_close(&a.matmul(&lu).unwrap(), &b, 1e-12);
+        assert_matrix_close(&chol, &lu, 1e-12);
+        assert_matrix_close(&qr, &lu, 1e-12);
+    }
+
+    #[test]
+    fn qr_is_orthonormal_times_upper_triangular() {
+        let a = Matrix::from_vec(4, 3, vec![
+            12.0, -51.0, 4.0,
+            6.0, 167.0, -68.0,
+            -4.0, 24.0, -41.0,
+            1.0, 1.0, 1.0,
+        ]);
+        let qr = a.qr().unwrap();
+        let (q, r) = (qr.q(), qr.r());
+        assert_matrix_close(&q.transpose().matmul(&q).unwrap(), &Matrix::identity(3), 1e-12);
+        assert_matrix_close(&q.matmul(&r).unwrap(), &a, 1e-10);
+        for i in 0..3 {
+            for j in 0..i { assert_eq!(r[(i, j)], 0.0); }
+        }
+    }
+
+    #[test]
+    fn qr_least_squares_fits_overdetermined_line() {
+        // y = 2x + 3 plus residuals (+0.1, -0.1, -0.1, +0.1) orthogonal to [1, x]
+        let x = Matrix::from_vec(4, 2, vec![1.0, 1.0, 1.0, 2.0, 1.0, 3.0, 1.0, 4.0]);
+        let y = [5.1, 6.9, 8.9, 11.1];
+        let beta = x.least_squares(&y).unwrap();
+        assert!(close(beta[0], 3.0, 1e-12) && close(beta[1], 2.0, 1e-12), "{beta:?}");
+        assert!(close(x.qr().unwrap().residual_norm(&y).unwrap(), 0.2, 1e-12));
+
+        let collinear = Matrix::from_vec(3, 2, vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]);
+        assert!(collinear.least_squares(&[1.0, 2.0, 3.0]).is_none());
+    }
+
+    #[test]
+    fn cholesky_reconstructs_spd_and_rejects_others() {
+        let a = Matrix::from_vec(3, 3, vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0]);
+        let chol = a.cholesky().unwrap();
+        let expected = Matrix::from_vec(3, 3, vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0]);
+        assert_matrix_close(chol.l(), &expected, 1e-12);
+        assert!(close(chol.determinant(), 36.0, 1e-9));
+
+        let indefinite = Matrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 1.0]);
+        let asymmetric = Matrix::from_vec(2, 2, vec![2.0, 1.0, 0.0, 2.0]);
+        assert!(indefinite.cholesky().is_none());
+        assert!(asymmetric.cholesky().is_none());
+    }
+
+    #[test]
+    fn ill_conditioned_hilbert_system_keeps_small_residual() {
+        // cond(H₈) ≈ 1.5e10: the solution error can be large, the residual must not be
+        let h = hilbert(8);
+        let b = h.mul_vec(&[1.0; 8]).unwrap();
+        for x in [h.lu().unwrap().solve(&b).unwrap(), h.qr().unwrap().solve(&b).unwrap(),
+                  h.cholesky().unwrap().solve(&b).unwrap()] {
+            let r = h.mul_vec(&x).unwrap();
+            let resid = r.iter().zip(&b).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+            assert!(resid < 1e-13, "residual {resid}");
+            assert!(x.iter().all(|xi| close(*xi, 1.0, 1e-4)), "{x:?}");
+        }
+    }
+}
@@ -0,0 +1,692 @@
+//! Reading and writing matrices in the formats other tools speak:
+//!
+//! - Matrix Market (`.mtx`), MATLAB's and SuiteSparse's text exchange format,
+//!   in both its dense `array` and sparse `coordinate` layouts;
+//! - CSV / TSV, one matrix row per line;
+//! - NumPy `.npy`, as written by `np.save`.
+//!
+//! Readers take any `BufRead` / `Read` and writers any `Write`, so the same
+//! code serves files, stdin and in-memory buffers. `Matrix::load` and
+//! `Matrix::save` pick the format from a path's extension.
+//!
+//! Text formats write each value with `{:?}`, the shortest decimal that
+//! parses back to the same f64, so a write then read is exact.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::{CsrMatrix, Matrix};
+
+#[derive(Debug)]
+pub enum IoError {
+    Io(io::Error),
+    /// Malformed input. `line` is 1-based, or 0 when no single line is to blame
+    /// (the binary .npy format, or a file that ends early).
+    Parse { line: usize, message: String },
+    /// Well-formed input using a dtype, shape or variant this crate cannot hold
+    Unsupported(String),
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IoError::Io(e)                      => write!(f, "{e}"),
+            IoError::Parse { line: 0, message } => write!(f, "{message}"),
+            IoError::Parse { line, message }    => write!(f, "line {line}: {message}"),
+            IoError::Unsupported(what)          => write!(f, "unsupported: {what}"),
+        }
+    }
+}
+
+impl std::error::Error for IoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IoError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for IoError {
+    fn from(e: io::Error) -> Self { IoError::Io(e) }
+}
+
+fn parse_err(line: usize, message: impl Into<String>) -> IoError {
+    IoError::Parse { line, message: message.into() }
+}
+
+fn parse_f64(token: &str, line: usize) -> Result<f64, IoError> {
+    token.trim().parse().map_err(|_| parse_err(line, format!("expected a number, found {token:?}")))
+}
+
+fn parse_usize(token: &str, line: usize) -> Result<usize, IoError> {
+    token.parse().map_err(|_| parse_err(line, format!("expected a non-negative integer, found {token:?}")))
+}
+
+/// Number of values in a dense rows×cols matrix, refusing sizes whose f64
+/// storage could not even be addressed. Header sizes are untrusted, so
+/// every product is checked rather than left to overflow.
+fn dense_len(rows: usize, cols: usize) -> Result<usize, IoError> {
+    rows.checked_mul(cols)
+        .filter(|n| n.checked_mul(std::mem::size_of::<f64>()).is_some_and(|bytes| bytes <= isize::MAX as usize))
+        .ok_or_else(|| IoError::Unsupported(format!("a {rows}x{cols} dense matrix is too large")))
+}
+
+/// Largest row or column count a sparse read will accept. CSR allocates one
+/// row pointer per row up front, so the size line alone could otherwise ask
+/// for terabytes; 2^28 still covers the largest SuiteSparse collection matrices.
+const MAX_SPARSE_DIM: usize = 1 << 28;
+
+/// Largest dense matrix, in values, that a coordinate file may expand to
+/// without listing that many entries: 2^28 f64s is 2 GiB. A 3-line file
+/// could otherwise claim a 10^9 x 10^9 matrix and abort on allocation.
+const MAX_DENSE_FILL: usize = 1 << 28;
+
+fn sparse_dims(rows: usize, cols: usize) -> Result<(), IoError> {
+    if rows > MAX_SPARSE_DIM || cols > MAX_SPARSE_DIM {
+        return Err(IoError::Unsupported(format!("a {rows}x{cols} sparse matrix exceeds the {MAX_SPARSE_DIM} row/column limit")));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------- Matrix Market
+
+/// Entries read from a Matrix Market file, symmetry already expanded
+struct Market {
+    rows: usize,
+    cols: usize,
+    /// 0-based (row, col, value); duplicates are summed by the caller
+    entries: Vec<(usize, usize, f64)>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Symmetry { General, Symmetric, Skew }
+
+fn read_market(reader: impl BufRead) -> Result<Market, IoError> {
+    let mut lines = reader.lines().enumerate().map(|(i, l)| l.map(|l| (i + 1, l)));
+    let (_, banner) = lines.next().ok_or_else(|| parse_err(1, "empty file"))??;
+    let banner = banner.to_ascii_lowercase();
+    let fields: Vec<&str> = banner.split_whitespace().collect();
+    if fields.first() != Some(&"%%matrixmarket") || fields.len() != 5 {
+        return Err(parse_err(1, "expected '%%MatrixMarket matrix <format> <field> <symmetry>'"));
+    }
+    if fields[1] != "matrix" {
+        return Err(IoError::Unsupported(format!("Matrix Market object '{}' (only 'matrix')", fields[1])));
+    }
+    let coordinate = match fields[2] {
+        "coordinate" => true,
+        "array" => false,
+        other => return Err(parse_err(1, format!("unknown Matrix Market format '{other}'"))),
+    };
+    let pattern = match fields[3] {
+        "real" | "double" | "integer" => false,
+        "pattern" if coordinate => true,
+        "pattern" => return Err(parse_err(1, "'pattern' is only valid with the coordinate format")),
+        "complex" => return Err(IoError::Unsupported("complex Matrix Market files (Matrix holds real values)".into())),
+        other => return Err(parse_err(1, format!("unknown Matrix Market field '{other}'"))),
+    };
+    let symmetry = match fields[4] {
+        "general" => Symmetry::General,
+        "symmetric" => Symmetry::Symmetric,
+        "skew-symmetric" => Symmetry::Skew,
+        "hermitian" => return Err(IoError::Unsupported("hermitian Matrix Market files (Matrix holds real values)".into())),
+        other => return Err(parse_err(1, format!("unknown Matrix Market symmetry '{other}'"))),
+    };
+
+    // Comments (%) and blank lines may appear anywhere after the banner
+    let mut data = lines.filter(|l| match l {
+        Ok((_, text)) => !text.trim().is_empty() && !text.trim_start().starts_with('%'),
+        Err(_) => true,
+    });
+    let (size_line, size) = data.next().ok_or_else(|| parse_err(2, "missing size line"))??;
+    let dims = size.split_whitespace().map(|t| parse_usize(t, size_line)).collect::<Result<Vec<_>, _>>()?;
+    let (rows, cols, declared) = match (coordinate, dims.as_slice()) {
+        (true, &[r, c, nnz]) => (r, c, nnz),
+        (false, &[r, c]) => (r, c, 0),
+        _ => {
+            let want = if coordinate { "'rows cols nnz'" } else { "'rows cols'" };
+            return Err(parse_err(size_line, format!("expected size line {want}")));
+        }
+    };
+    if symmetry != Symmetry::General && rows != cols {
+        return Err(parse_err(size_line, format!("a symmetric matrix must be square, got {rows}x{cols}")));
+    }
+
+    // Grown as entries arrive: the size line is not trusted to allocate by
+    let mut entries = Vec::new();
+    let mut push = |r: usize, c: usize, v: f64| {
+        entries.push((r, c, v));
+        match symmetry {
+            Symmetry::General => {}
+            Symmetry::Symmetric if r != c => entries.push((c, r, v)),
+            Symmetry::Skew if r != c => entries.push((c, r, -v)),
+            _ => {}
+        }
+    };
+
+    let mut count = 0;
+    if coordinate {
+        for line in data {
+            let (n, text) = line?;
+            let tokens: Vec<&str> = text.split_whitespace().collect();
+            let (r, c, v) = match (pattern, tokens.as_slice()) {
+                (true, &[r, c]) => (parse_usize(r, n)?, parse_usize(c, n)?, 1.0),
+                (false, &[r, c, v]) => (parse_usize(r, n)?, parse_usize(c, n)?, parse_f64(v, n)?),
+                _ => return Err(parse_err(n, format!("expected '{}'", if pattern { "row col" } else { "row col value" }))),
+            };
+            if r == 0 || c == 0 || r > rows || c > cols {
+                return Err(parse_err(n, format!("entry ({r}, {c}) outside a {rows}x{cols} matrix (indices are 1-based)")));
+            }
+            count += 1;
+            if count > declared { return Err(parse_err(n, format!("more than the declared {declared} entries"))); }
+            push(r - 1, c - 1, v);
+        }
+    } else {
+        // Column-major; symmetric files store only the lower triangle, skew ones without the diagonal
+        let expected = match symmetry {
+            Symmetry::General => Some(dense_len(rows, cols)?),
+            Symmetry::Symmetric => rows.checked_add(1).and_then(|r1| rows.checked_mul(r1)).map(|n| n / 2),
+            Symmetry::Skew => rows.checked_mul(rows.saturating_sub(1)).map(|n| n / 2),
+        }
+        .ok_or_else(|| IoError::Unsupported(format!("a {rows}x{cols} dense matrix is too large")))?;
+        let mut slots = (0..cols).flat_map(|c| {
+            let from = match symmetry { Symmetry::General => 0, Symmetry::Symmetric => c, Symmetry::Skew => c + 1 };
+            (from..rows).map(move |r| (r, c))
+        });
+        for line in data {
+            let (n, text) = line?;
+            for token in text.split_whitespace() {
+                let (r, c) = slots.next().ok_or_else(|| parse_err(n, format!("more than the expected {expected} values")))?;
+                push(r, c, parse_f64(token, n)?);
+                count += 1;
+            }
+        }
+        if count < expected {
+            return Err(parse_err(0, format!("expected {expected} values, found {count}")));
+        }
+        return Ok(Market { rows, cols, entries });
+    }
+    if count < declared {
+        return Err(parse_err(0, format!("expected {declared} entries, found {count}")));
+    }
+    Ok(Market { rows, cols, entries })
+}
+
+impl Matrix {
+    /// Read a Matrix Market file in either layout. `coordinate` files are
+    /// expanded to dense, with repeated entries summed.
+    pub fn read_matrix_market(reader: impl BufRead) -> Result<Matrix, IoError> {
+        let market = read_market(reader)?;
+        let len = dense_len(market.rows, market.cols)?;
+        // Array files hold every value, so their size is already backed by data
+        if len > MAX_DENSE_FILL && len > market.entries.len() {
+            return Err(IoError::Unsupported(format!(
+                "a {}x{} dense matrix from {} entries (densifying is capped at {MAX_DENSE_FILL} values; read it as a CsrMatrix)",
+                market.rows, market.cols, market.entries.len()
+            )));
+        }
+        let mut m = Matrix::new(market.rows, market.cols);
+        for (r, c, v) in market.entries { m[(r, c)] += v; }
+        Ok(m)
+    }
+
+    /// Write as a dense `array real general` Matrix Market file
+    pub fn write_matrix_market(&self, mut writer: impl Write) -> Result<(), IoError> {
+        writeln!(writer, "%%MatrixMarket matrix array real general")?;
+        writeln!(writer, "{} {}", self.rows, self.cols)?;
+        for c in 0..self.cols {
+            for r in 0..self.rows { writeln!(writer, "{:?}", self[(r, c)])?; }
+        }
+        Ok(writer.flush()?)
+    }
+}
+
+impl CsrMatrix {
+    /// Read a Matrix Market file in either layout without densifying.
+    /// Repeated coordinates are summed and explicit zeros are dropped.
+    pub fn read_matrix_market(reader: impl BufRead) -> Result<CsrMatrix, IoError> {
+        let market = read_market(reader)?;
+        sparse_dims(market.rows, market.cols)?;
+        let entries: Vec<_> = market.entries.into_iter().filter(|&(_, _, v)| v != 0.0).collect();
+        // read_market has already checked every index against the size line
+        Ok(CsrMatrix::from_triplets(market.rows, market.cols, &entries).expect("entries are in bounds"))
+    }
+
+    /// Write as a `coordinate real general` Matrix Market file, row by row
+    pub fn write_matrix_market(&self, mut writer: impl Write) -> Result<(), IoError> {
+        writeln!(writer, "%%MatrixMarket matrix coordinate real general")?;
+        writeln!(writer, "{} {} {}", self.rows, self.cols, self.nnz())?;
+        for r in 0..self.rows {
+            for (c, v) in self.row(r) { writeln!(writer, "{} {} {:?}", r + 1, c + 1, v)?; }
+        }
+        Ok(writer.flush()?)
+    }
+}
+
+// ------------------------------------------------------------------------- CSV
+
+impl Matrix {
+    /// Read delimited text, one row per line. Blank lines and lines starting
+    /// with `#` are skipped. With `has_header`, the first remaining line is
+    /// taken as column names and ignored, though it still fixes the field
+    /// count; every other line must be all numbers. Every row must have the
+    /// same number of fields.
+    pub fn read_csv(reader: impl BufRead, delimiter: char, has_header: bool) -> Result<Matrix, IoError> {
+        let mut data = Vec::new();
+        let (mut rows, mut cols) = (0, None);
+        let mut header_pending = has_header;
+        for (i, line) in reader.lines().enumerate() {
+            let (n, line) = (i + 1, line?);
+            let text = line.trim();
+            if text.is_empty() || text.starts_with('#') { continue; }
+            let fields: Vec<&str> = text.split(delimiter).collect();
+            if header_pending {
+                header_pending = false;
+                cols = Some(fields.len());
+                continue;
+            }
+            let values = fields.iter().map(|f| parse_f64(f, n)).collect::<Result<Vec<f64>, _>>()?;
+            match cols {
+                Some(c) if c != values.len() => {
+                    return Err(parse_err(n, format!("expected {c} fields, found {}", values.len())));
+                }
+                _ => cols = Some(values.len()),
+            }
+            data.extend(values);
+            rows += 1;
+        }
+        let cols = if rows == 0 { 0 } else { cols.unwrap_or(0) };
+        Ok(Matrix::from_vec(rows, cols, data))
+    }
+
+    pub fn write_csv(&self, mut writer: impl Write, delimiter: char) -> Result<(), IoError> {
+        for row in self.row_iter() {
+            for (c, v) in row.iter().enumerate() {
+                if c > 0 { write!(writer, "{delimiter}")?; }
+                write!(writer, "{v:?}")?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(writer.flush()?)
+    }
+}
+
+// ------------------------------------------------------------------------- .npy
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Element types a .npy file may hold that convert losslessly (or, for the
+/// 64-bit integers, as `as f64` does) to f64
+#[derive(Clone, Copy)]
+enum Dtype { F8, F4, I8, I4, I2, I1, U8, U4, U2, U1 }
+
+impl Dtype {
+    fn parse(descr: &str) -> Result<(Dtype, bool), IoError> {
+        // The header is untrusted: split on a char, not a byte, so a
+        // multi-byte first character is an error rather than a panic
+        let mut chars = descr.chars();
+        let big_endian = match chars.next() {
+            Some('<' | '|') => false,
+            Some('>') => true,
+            _ => return Err(parse_err(0, format!("unrecognised dtype '{descr}'"))),
+        };
+        let code = chars.as_str();
+        let dtype = match code {
+            "f8" => Dtype::F8, "f4" => Dtype::F4,
+            "i8" => Dtype::I8, "i4" => Dtype::I4, "i2" => Dtype::I2, "i1" => Dtype::I1,
+            "u8" => Dtype::U8, "u4" => Dtype::U4, "u2" => Dtype::U2, "u1" => Dtype::U1,
+            _ => return Err(IoError::Unsupported(format!(
+                "npy dtype '{descr}' (expected a real float or integer type such as '<f8')"
+            ))),
+        };
+        Ok((dtype, big_endian))
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Dtype::F8 | Dtype::I8 | Dtype::U8 => 8,
+            Dtype::F4 | Dtype::I4 | Dtype::U4 => 4,
+            Dtype::I2 | Dtype::U2 => 2,
+            Dtype::I1 | Dtype::U1 => 1,
+        }
+    }
+
+    fn decode(self, bytes: &[u8], big_endian: bool) -> f64 {
+        let mut b = [0u8; 8];
+        let n = self.size();
+        b[..n].copy_from_slice(bytes);
+        if big_endian { b[..n].reverse(); }
+        let (b2, b4) = ([b[0], b[1]], [b[0], b[1], b[2], b[3]]);
+        match self {
+            Dtype::F8 => f64::from_le_bytes(b),
+            Dtype::F4 => f32::from_le_bytes(b4) as f64,
+            Dtype::I8 => i64::from_le_bytes(b) as f64,
+            Dtype::I4 => i32::from_le_bytes(b4) as f64,
+            Dtype::I2 => i16::from_le_bytes(b2) as f64,
+            Dtype::I1 => b[0] as i8 as f64,
+            Dtype::U8 => u64::from_le_bytes(b) as f64,
+            Dtype::U4 => u32::from_le_bytes(b4) as f64,
+            Dtype::U2 => u16::from_le_bytes(b2) as f64,
+            Dtype::U1 => b[0] as f64,
+        }
+    }
+}
+
+/// Value of `'key': ...` in the header's Python dict literal
+fn header_field<'h>(header: &'h str, key: &str) -> Result<&'h str, IoError> {
+    let missing = || parse_err(0, format!("npy header has no '{key}' field"));
+    let start = header.find(&format!("'{key}'")).ok_or_else(missing)? + key.len() + 2;
+    let rest = header[start..].trim_start().strip_prefix(':').ok_or_else(missing)?.trim_start();
+    // The value is a quoted string, a bool or a parenthesised tuple
+    let end = match rest.chars().next() {
+        Some('(') => rest.find(')').map(|i| i + 1),
+        Some('\'') => rest[1..].find('\'').map(|i| i + 2),
+        _ => rest.find([',', '}']),
+    };
+    Ok(rest[..end.ok_or_else(missing)?].trim())
+}
+
+impl Matrix {
+    /// Read a NumPy `.npy` array of up to two dimensions. A 1-D array of
+    /// length n becomes an n×1 column and a 0-D array a 1×1 matrix. Any real
+    /// float or integer dtype of either byte order is converted to f64;
+    /// Fortran-ordered (column-major) data is reordered to row-major.
+    pub fn read_npy(mut reader: impl Read) -> Result<Matrix, IoError> {
+        let mut preamble = [0u8; 8];
+        reader.read_exact(&mut preamble)?;
+        if &preamble[..6] != NPY_MAGIC {
+            return Err(parse_err(0, "not an .npy file (bad magic string)"));
+        }
+        let header_len = match preamble[6] {
+            1 => {
+                let mut len = [0u8; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            }
+            2 | 3 => {
+                let mut len = [0u8; 4];
+                reader.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            }
+            v => return Err(IoError::Unsupported(format!("npy format version {v}.{}", preamble[7]))),
+        };
+        let mut header = Vec::new();
+        reader.by_ref().take(header_len as u64).read_to_end(&mut header)?;
+        if header.len() < header_len {
+            return Err(parse_err(0, format!("npy header ends after {} of {header_len} bytes", header.len())));
+        }
+        let header = String::from_utf8(header).map_err(|_| parse_err(0, "npy header is not valid text"))?;
+
+        let descr = header_field(&header, "descr")?.trim_matches('\'');
+        let (dtype, big_endian) = Dtype::parse(descr)?;
+        let fortran = match header_field(&header, "fortran_order")? {
+            "True" => true,
+            "False" => false,
+            other => return Err(parse_err(0, format!("bad fortran_order '{other}'"))),
+        };
+        let shape_text = header_field(&header, "shape")?;
+        let shape = shape_text
+            .trim_matches(|c| c == '(' || c == ')')
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(|t| parse_usize(t, 0))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (rows, cols) = match *shape.as_slice() {
+            [] => (1, 1),
+            [n] => (n, 1),
+            [r, c] => (r, c),
+            _ => return Err(IoError::Unsupported(format!("npy array of shape {shape_text} (at most 2 dimensions)"))),
+        };
+
+        let size = dtype.size();
+        let len = dense_len(rows, cols)?
+            .checked_mul(size)
+            .ok_or_else(|| IoError::Unsupported(format!("npy array of shape {shape_text} is too large")))?;
+        // Read what is actually there rather than allocating what the header claims
+        let mut bytes = Vec::new();
+        reader.take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() < len {
+            return Err(parse_err(0, format!("npy data ends before {rows}x{cols} values")));
+        }
+        let values: Vec<f64> = bytes.chunks_exact(size).map(|b| dtype.decode(b, big_endian)).collect();
+        if !fortran { return Ok(Matrix::from_vec(rows, cols, values)); }
+        let mut m = Matrix::new(rows, cols);
+        for (i, v) in values.into_iter().enumerate() { m[(i % rows, i / rows)] = v; }
+        Ok(m)
+    }
+
+    /// Write as a version 1.0 `.npy` file of little-endian f64, C order,
+    /// readable with `np.load`
+    pub fn write_npy(&self, mut writer: impl Write) -> Result<(), IoError> {
+        let dict = format!("{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, {}), }}", self.rows, self.cols);
+        // Pad with spaces and a newline so the data starts on a 64-byte boundary
+        let unpadded = NPY_MAGIC.len() + 2 + 2 + dict.len() + 1;
+        let header = format!("{dict}{}\n", " ".repeat((64 - unpadded % 64) % 64));
+        writer.write_all(NPY_MAGIC)?;
+        writer.write_all(&[1, 0])?;
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+        writer.write_all(header.as_bytes())?;
+        for v in &self.data { writer.write_all(&v.to_le_bytes())?; }
+        Ok(writer.flush()?)
+    }
+}
+
+// ------------------------------------------------------------------- by path
+
+impl Matrix {
+    /// Read a file, choosing the format from its extension:
+    /// `.mtx`, `.csv`, `.tsv` or `.npy`. CSV and TSV files are read without
+    /// a header row, as `save` writes them; call `read_csv` for one with a header.
+    pub fn load(path: impl AsRef<Path>) -> Result<Matrix, IoError> {
+        let path = path.as_ref();
+        let format = Format::of(path)?;
+        let file = File::open(path)?;
+        match format {
+            Format::MatrixMarket => Matrix::read_matrix_market(BufReader::new(file)),
+            Format::Csv => Matrix::read_csv(BufReader::new(file), ',', false),
+            Format::Tsv => Matrix::read_csv(BufReader::new(file), '\t', false),
+            Format::Npy => Matrix::read_npy(BufReader::new(file)),
+        }
+    }
+
+    /// Write a file in the format named by its extension (see `load`)
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), IoError> {
+        let path = path.as_ref();
+        let format = Format::of(path)?;
+        let file = BufWriter::new(File::create(path)?);
+        match format {
+            Format::MatrixMarket => self.write_matrix_market(file),
+            Format::Csv => self.write_csv(file, ','),
+            Format::Tsv => self.write_csv(file, '\t'),
+            Format::Npy => self.write_npy(file),
+        }
+    }
+}
+
+enum Format { MatrixMarket, Csv, Tsv, Npy }
+
+impl Format {
+    fn of(path: &Path) -> Result<Format, IoError> {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+        match ext.as_str() {
+            "mtx" => Ok(Format::MatrixMarket),
+            "csv" => Ok(Format::Csv),
+            "tsv" | "tab" => Ok(Format::Tsv),
+            "npy" => Ok(Format::Npy),
+            _ => Err(IoError::Unsupported(format!(
+                "file extension of {} (expected .mtx, .csv, .tsv or .npy)", path.display()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Awkward values: negatives, tiny and huge magnitudes, non-terminating binary fractions
+    fn sample() -> Matrix {
+        Matrix::from_vec(3, 2, vec![1.0, -2.5, 0.1, 1e-300, 6.02214076e23, -0.0])
+    }
+
+    #[test]
+    fn matrix_market_dense_round_trip() {
+        let mut buf = Vec::new();
+        sample().write_matrix_market(&mut buf).unwrap();
+        let text = String::from_utf8(buf.clone()).unwrap();
+        assert!(text.starts_with("%%MatrixMarket matrix array real general\n3 2\n1.0\n0.1\n"));
+        assert_eq!(Matrix::read_matrix_market(&buf[..]).unwrap(), sample());
+    }
+
+    #[test]
+    fn matrix_market_coordinate_round_trip_and_symmetry() {
+        let a = CsrMatrix::from_triplets(3, 4, &[(0, 1, 2.0), (2, 3, -1.5), (1, 0, 0.25)]).unwrap();
+        let mut buf = Vec::new();
+        a.write_matrix_market(&mut buf).unwrap();
+        assert_eq!(CsrMatrix::read_matrix_market(&buf[..]).unwrap(), a);
+
+        // Lower triangle only, 1-based, comments anywhere after the banner
+        let sym = "%%MatrixMarket matrix coordinate real symmetric\n% made by hand\n3 3 3\n1 1 4\n3 1 -1\n\n3 3 2\n";
+        let d = Matrix::read_matrix_market(sym.as_bytes()).unwrap();
+        assert_eq!(d, Matrix::from_vec(3, 3, vec![4.0, 0.0, -1.0, 0.0, 0.0, 0.0, -1.0, 0.0, 2.0]));
+
+        let skew = "%%MatrixMarket matrix array real skew-symmetric\n2 2\n3\n";
+        assert_eq!(Matrix::read_matrix_market(skew.as_bytes()).unwrap(), Matrix::from_vec(2, 2, vec![0.0, -3.0, 3.0, 0.0]));
+        let pattern = "%%MatrixMarket matrix coordinate pattern general\n2 2 1\n2 1\n";
+        assert_eq!(Matrix::read_matrix_market(pattern.as_bytes()).unwrap()[(1, 0)], 1.0);
+    }
+
+    #[test]
+    fn matrix_market_errors_point_at_the_problem() {
+        let complex = "%%MatrixMarket matrix coordinate complex general\n1 1 1\n1 1 1 0\n";
+        assert!(matches!(Matrix::read_matrix_market(complex.as_bytes()), Err(IoError::Unsupported(_))));
+        let outside = "%%MatrixMarket matrix coordinate real general\n2 2 1\n3 1 1.0\n";
+        let err = Matrix::read_matrix_market(outside.as_bytes()).unwrap_err();
+        assert!(matches!(err, IoError::Parse { line: 3, .. }), "{err}");
+        let short = "%%MatrixMarket matrix array real general\n2 2\n1\n2\n3\n";
+        assert!(Matrix::read_matrix_market(short.as_bytes()).unwrap_err().to_string().contains("expected 4 values"));
+        let not_square = "%%MatrixMarket matrix array real symmetric\n2 3\n";
+        assert!(matches!(Matrix::read_matrix_market(not_square.as_bytes()), Err(IoError::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn csv_round_trip_with_header_and_tabs() {
+        for delimiter in [',', '\t'] {
+            let mut buf = Vec::new();
+            sample().write_csv(&mut buf, delimiter).unwrap();
+            assert_eq!(Matrix::read_csv(&buf[..], delimiter, false).unwrap(), sample());
+        }
+        let pandas = "a,b,c\n1,2,3\n\n# comment\n4, 5 ,6\n";
+        assert_eq!(Matrix::read_csv(pandas.as_bytes(), ',', true).unwrap(), Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    }
+
+    #[test]
+    fn csv_rejects_ragged_rows_and_bad_numbers() {
+        let ragged = "1,2,3\n4,5\n";
+        let err = Matrix::read_csv(ragged.as_bytes(), ',', false).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected 3 fields, found 2");
+        let bad = "1,2\n3,x\n";
+        assert!(matches!(Matrix::read_csv(bad.as_bytes(), ',', false), Err(IoError::Parse { line: 2, .. })));
+        // Without has_header a typo in the first row is an error, not a dropped header
+        let typo = "1.0,x2,3.0\n4,5,6\n";
+        assert!(matches!(Matrix::read_csv(typo.as_bytes(), ',', false), Err(IoError::Parse { line: 1, .. })));
+        let wide_header = "a,b,c\n1,2\n";
+        assert!(Matrix::read_csv(wide_header.as_bytes(), ',', true).unwrap_err().to_string().contains("expected 3 fields"));
+        assert_eq!(Matrix::read_csv("".as_bytes(), ',', false).unwrap(), Matrix::new(0, 0));
+    }
+
+    #[test]
+    fn npy_round_trip_and_header_alignment() {
+        let mut buf = Vec::new();
+        sample().write_npy(&mut buf).unwrap();
+        let header_len = u16::from_le_bytes([buf[8], buf[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        assert_eq!(buf.len(), 10 + header_len + 6 * 8);
+        assert_eq!(Matrix::read_npy(&buf[..]).unwrap(), sample());
+    }
+
+    /// Hand-built .npy, laid out exactly as `np.save` writes it
+    fn npy(descr: &str, fortran: bool, shape: &str, data: &[u8]) -> Vec<u8> {
+        let dict = format!("{{'descr': '{descr}', 'fortran_order': {}, 'shape': {shape}, }}", if fortran { "True" } else { "False" });
+        let mut out = b"\x93NUMPY\x01\x00".to_vec();
+        out.extend((dict.len() as u16 + 1).to_le_bytes());
+        out.extend(dict.bytes());
+        out.push(b'\n');
+        out.extend(data);
+        out
+    }
+
+    #[test]
+    fn npy_reads_other_dtypes_orders_and_shapes() {
+        let ints: Vec<u8> = [1i32, 2, 3, 4, 5, 6].iter().flat_map(|v| v.to_le_bytes()).collect();
+        // Fortran order: the bytes are column-major
+        let m = Matrix::read_npy(&npy("<i4", true, "(2, 3)", &ints)[..]).unwrap();
+        assert_eq!(m, Matrix::from_vec(2, 3, vec![1.0, 3.0, 5.0, 2.0, 4.0, 6.0]));
+
+        let big: Vec<u8> = [0.5f32, -1.0].iter().flat_map(|v| v.to_be_bytes()).collect();
+        let v = Matrix::read_npy(&npy(">f4", false, "(2,)", &big)[..]).unwrap();
+        assert_eq!(v, Matrix::from_vec(2, 1, vec![0.5, -1.0]));
+
+        let scalar = Matrix::read_npy(&npy("|u1", false, "()", &[7])[..]).unwrap();
+        assert_eq!(scalar, Matrix::from_vec(1, 1, vec![7.0]));
+    }
+
+    #[test]
+    fn npy_errors_for_unsupported_dtypes_and_shapes() {
+        let complex = npy("<c16", false, "(1,)", &[0; 16]);
+        let err = Matrix::read_npy(&complex[..]).unwrap_err();
+        assert!(matches!(err, IoError::Unsupported(_)) && err.to_string().contains("<c16"), "{err}");
+        let cube = npy("<f8", false, "(2, 2, 2)", &[0; 64]);
+        assert!(matches!(Matrix::read_npy(&cube[..]), Err(IoError::Unsupported(_))));
+        let truncated = npy("<f8", false, "(2, 2)", &[0; 24]);
+        assert!(Matrix::read_npy(&truncated[..]).unwrap_err().to_string().contains("ends before"));
+        assert!(matches!(Matrix::read_npy(&b"PK\x03\x04zipfile"[..]), Err(IoError::Parse { .. })));
+        let non_ascii = npy("é8", false, "(1,)", &[0; 8]);
+        assert!(Matrix::read_npy(&non_ascii[..]).unwrap_err().to_string().contains("unrecognised dtype"));
+    }
+
+    #[test]
+    fn hostile_header_sizes_fail_without_allocating() {
+        // Declared counts far beyond the data must neither abort nor overflow
+        let nnz = "%%MatrixMarket matrix coordinate real general\n1000 1000 100000000000000\n1 1 1.0\n";
+        assert!(Matrix::read_matrix_market(nnz.as_bytes()).unwrap_err().to_string().contains("found 1"));
+        let array = "%%MatrixMarket matrix array real general\n4294967296 4294967296\n1\n";
+        assert!(matches!(Matrix::read_matrix_market(array.as_bytes()), Err(IoError::Unsupported(_))));
+        let symmetric = format!("%%MatrixMarket matrix array real symmetric\n{0} {0}\n1\n", usize::MAX);
+        assert!(matches!(Matrix::read_matrix_market(symmetric.as_bytes()), Err(IoError::Unsupported(_))));
+        let sparse = "%%MatrixMarket matrix coordinate real general\n4294967296 4294967296 1\n1 1 1.0\n";
+        assert!(matches!(Matrix::read_matrix_market(sparse.as_bytes()), Err(IoError::Unsupported(_))));
+        let dense = "%%MatrixMarket matrix coordinate real general\n1000000000 1000000000 1\n1 1 1.0\n";
+        assert!(matches!(Matrix::read_matrix_market(dense.as_bytes()), Err(IoError::Unsupported(_))));
+        let csr = "%%MatrixMarket matrix coordinate real general\n1099511627776 1099511627776 1\n1 1 1.0\n";
+        assert!(matches!(CsrMatrix::read_matrix_market(csr.as_bytes()), Err(IoError::Unsupported(_))));
+        let csr_max = format!("%%MatrixMarket matrix coordinate real general\n{0} {0} 1\n1 1 1.0\n", usize::MAX);
+        assert!(matches!(CsrMatrix::read_matrix_market(csr_max.as_bytes()), Err(IoError::Unsupported(_))));
+
+        let overflow = npy("<f8", false, "(4294967296, 4294967296)", &[0; 16]);
+        assert!(matches!(Matrix::read_npy(&overflow[..]), Err(IoError::Unsupported(_))));
+        let huge = npy("<f8", false, "(100000, 100000)", &[0; 16]);
+        assert!(Matrix::read_npy(&huge[..]).unwrap_err().to_string().contains("ends before"));
+        let mut long_header = b"\x93NUMPY\x02\x00".to_vec();
+        long_header.extend(u32::MAX.to_le_bytes());
+        long_header.extend(b"{'descr': '<f8'");
+        assert!(Matrix::read_npy(&long_header[..]).unwrap_err().to_string().contains("header ends"));
+    }
+
+    #[test]
+    fn load_and_save_dispatch_on_extension() {
+        let dir = std::env::temp_dir().join(format!("matrix-math-io-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for ext in ["mtx", "csv", "tsv", "npy"] {
+            let path = dir.join(format!("m.{ext}"));
+            sample().save(&path).unwrap();
+            assert_eq!(Matrix::load(&path).unwrap(), sample(), "{ext}");
+        }
+        assert!(matches!(sample().save(dir.join("m.xlsx")), Err(IoError::Unsupported(_))));
+        assert!(matches!(Matrix::load(dir.join("missing.csv")), Err(IoError::Io(_))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
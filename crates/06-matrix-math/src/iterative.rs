@@ -0,0 +1,290 @@
+//! Krylov-subspace solvers for large sparse systems A x = b.
+//!
+//! Neither method ever factorises A - each iteration needs one sparse
+//! matrix-vector product, so memory stays O(nnz) where LU would fill in.
+//! Both stop once the relative residual ‖b - A x‖ / ‖b‖ drops below the
+//! configured tolerance.
+
+use crate::CsrMatrix;
+
+/// Stopping rules shared by the solvers
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IterativeConfig {
+    /// Stop once ‖b - A x‖ / ‖b‖ <= tolerance
+    pub tolerance: f64,
+    pub max_iterations: usize,
+    /// GMRES only: Krylov basis size before restarting
+    pub restart: usize,
+}
+
+impl Default for IterativeConfig {
+    fn default() -> Self {
+        Self { tolerance: 1e-10, max_iterations: 1000, restart: 30 }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IterativeResult {
+    pub x: Vec<f64>,
+    /// Krylov steps taken, each costing one matrix-vector product. Products
+    /// spent checking the true residual (CG's replacement step, the residual
+    /// at each GMRES restart) are not counted.
+    pub iterations: usize,
+    /// Final relative residual ‖b - A x‖ / ‖b‖, recomputed from x
+    pub residual: f64,
+    pub converged: bool,
+}
+
+/// Conjugate gradient for symmetric positive-definite A.
+/// Returns None if A is not square or `b` has the wrong length.
+/// Stops early (unconverged) if it meets a direction with pᵀAp <= 0,
+/// which proves A is not positive definite.
+///
+/// The recurrence for r drifts from b - A x by rounding, so once it claims
+/// convergence the true residual is computed and, if it is not yet small
+/// enough, replaces r and the iteration carries on. That check costs an
+/// extra matrix-vector product outside the `iterations` count.
+pub fn conjugate_gradient(a: &CsrMatrix, b: &[f64], config: &IterativeConfig) -> Option<IterativeResult> {
+    let n = check_system(a, b)?;
+    let b_norm = norm(b);
+    let mut x = vec![0.0; n];
+    if b_norm == 0.0 { return Some(finish(a, b, x, 0, config)); }
+
+    let mut r = b.to_vec();
+    let mut p = r.clone();
+    let mut ap = vec![0.0; n];
+    let mut rr = dot(&r, &r);
+    let mut iterations = 0;
+    while iterations < config.max_iterations && rr.sqrt() / b_norm > config.tolerance {
+        a.mul_vec_into(&p, &mut ap);
+        iterations += 1;
+        let pap = dot(&p, &ap);
+        if pap <= 0.0 { break; }
+        let alpha = rr / pap;
+        for i in 0..n {
+            x[i] += alpha * p[i];
+            r[i] -= alpha * ap[i];
+        }
+        let mut rr_next = dot(&r, &r);
+        if rr_next.sqrt() / b_norm <= config.tolerance {
+            a.mul_vec_into(&x, &mut ap);
+            for i in 0..n { r[i] = b[i] - ap[i]; }
+            rr_next = dot(&r, &r);
+        }
+        let beta = rr_next / rr;
+        for i in 0..n { p[i] = r[i] + beta * p[i]; }
+        rr = rr_next;
+    }
+    Some(finish(a, b, x, iterations, config))
+}
+
+/// Restarted GMRES(m) for general square A, with modified Gram–Schmidt
+/// Arnoldi and Givens rotations so the residual is known at every step
+/// without forming x. Returns None if A is not square or `b` has the wrong length.
+/// Stops early (unconverged) if the Krylov subspace holds no better x, as
+/// happens when b has a component outside the range of a singular A.
+pub fn gmres(a: &CsrMatrix, b: &[f64], config: &IterativeConfig) -> Option<IterativeResult> {
+    let n = check_system(a, b)?;
+    let b_norm = norm(b);
+    let mut x = vec![0.0; n];
+    if b_norm == 0.0 { return Some(finish(a, b, x, 0, config)); }
+
+    let m = config.restart.max(1);
+    let mut iterations = 0;
+    let mut w = vec![0.0; n];
+    loop {
+        // r = b - A x
+        a.mul_vec_into(&x, &mut w);
+        let r: Vec<f64> = b.iter().zip(&w).map(|(bi, wi)| bi - wi).collect();
+        let beta = norm(&r);
+        if beta / b_norm <= config.tolerance || iterations >= config.max_iterations { break; }
+
+        let mut basis: Vec<Vec<f64>> = vec![r.iter().map(|v| v / beta).collect()];
+        let mut h = vec![vec![0.0; m]; m + 1];
+        let (mut cs, mut sn) = (vec![0.0; m], vec![0.0; m]);
+        let mut g = vec![0.0; m + 1];
+        g[0] = beta;
+
+        let mut k = 0;
+        let mut stalled = false;
+        while k < m && iterations < config.max_iterations {
+            a.mul_vec_into(&basis[k], &mut w);
+            iterations += 1;
+            for (i, v) in basis.iter().enumerate() {
+                h[i][k] = dot(&w, v);
+                for (wj, vj) in w.iter_mut().zip(v) { *wj -= h[i][k] * vj; }
+            }
+            let w_norm = norm(&w);
+            h[k + 1][k] = w_norm;
+            let column_norm = (0..=k + 1).map(|i| h[i][k] * h[i][k]).sum::<f64>().sqrt();
+
+            for i in 0..k {
+                let t = cs[i] * h[i][k] + sn[i] * h[i + 1][k];
+                h[i + 1][k] = -sn[i] * h[i][k] + cs[i] * h[i + 1][k];
+                h[i][k] = t;
+            }
+            let d = h[k][k].hypot(h[k + 1][k]);
+            // The new column vanishes (to rounding) after rotation: A maps the
+            // basis back into the span already used, so no step along it
+            // lowers the residual and dividing by d would blow up
+            if d <= 64.0 * f64::EPSILON * column_norm {
+                stalled = true;
+                break;
+            }
+            (cs[k], sn[k]) = (h[k][k] / d, h[k + 1][k] / d);
+            h[k][k] = d;
+            h[k + 1][k] = 0.0;
+            g[k + 1] = -sn[k] * g[k];
+            g[k] *= cs[k];
+            k += 1;
+
+            // w_norm == 0 is a "lucky breakdown": x is exact in this subspace
+            if g[k].abs() / b_norm <= config.tolerance || w_norm == 0.0 { break; }
+            basis.push(w.iter().map(|v| v / w_norm).collect());
+        }
+
+        // Back-substitute the k×k triangle H y = g and update x += V y
+        let mut y = g[..k].to_vec();
+        for i in (0..k).rev() {
+            for j in i + 1..k { y[i] -= h[i][j] * y[j]; }
+            y[i] /= h[i][i];
+        }
+        for (v, yi) in basis.iter().zip(&y) {
+            for (xj, vj) in x.iter_mut().zip(v) { *xj += yi * vj; }
+        }
+        if stalled { break; }
+    }
+    Some(finish(a, b, x, iterations, config))
+}
+
+fn check_system(a: &CsrMatrix, b: &[f64]) -> Option<usize> {
+    if a.rows != a.cols || b.len() != a.rows { return None; }
+    Some(a.rows)
+}
+
+fn finish(a: &CsrMatrix, b: &[f64], x: Vec<f64>, iterations: usize, config: &IterativeConfig) -> IterativeResult {
+    let ax = a.mul_vec(&x).expect("x has one entry per column");
+    let b_norm = norm(b);
+    let r_norm = b.iter().zip(&ax).map(|(bi, axi)| (bi - axi).powi(2)).sum::<f64>().sqrt();
+    let residual = if b_norm == 0.0 { r_norm } else { r_norm / b_norm };
+    let converged = residual <= config.tolerance;
+    IterativeResult { x, iterations, residual, converged }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn norm(v: &[f64]) -> f64 {
+    dot(v, v).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1-D Poisson matrix tridiag(-1, 2, -1): SPD, condition number ~ n²
+    fn poisson_1d(n: usize) -> CsrMatrix {
+        let mut t = Vec::new();
+        for i in 0..n {
+            t.push((i, i, 2.0));
+            if i > 0 { t.push((i, i - 1, -1.0)); }
+            if i + 1 < n { t.push((i, i + 1, -1.0)); }
+        }
+        CsrMatrix::from_triplets(n, n, &t).unwrap()
+    }
+
+    /// Upwinded convection-diffusion: tridiag(-1 - c, 2 + c, -1), non-symmetric
+    fn convection_diffusion(n: usize, c: f64) -> CsrMatrix {
+        let mut t = Vec::new();
+        for i in 0..n {
+            t.push((i, i, 2.0 + c));
+            if i > 0 { t.push((i, i - 1, -1.0 - c)); }
+            if i + 1 < n { t.push((i, i + 1, -1.0)); }
+        }
+        CsrMatrix::from_triplets(n, n, &t).unwrap()
+    }
+
+    fn max_error(x: &[f64], expected: &[f64]) -> f64 {
+        x.iter().zip(expected).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn cg_solves_poisson_within_n_iterations() {
+        let n = 100;
+        let a = poisson_1d(n);
+        let truth: Vec<f64> = (0..n).map(|i| (i as f64 * 0.1).sin()).collect();
+        let b = a.mul_vec(&truth).unwrap();
+        let r = conjugate_gradient(&a, &b, &IterativeConfig::default()).unwrap();
+        assert!(r.converged, "residual {}", r.residual);
+        assert!(r.residual <= IterativeConfig::default().tolerance);
+        assert!(r.iterations <= n);
+        assert!(max_error(&r.x, &truth) < 1e-7);
+    }
+
+    #[test]
+    fn gmres_solves_non_symmetric_system() {
+        let n = 200;
+        let a = convection_diffusion(n, 0.5);
+        let truth: Vec<f64> = (0..n).map(|i| 1.0 + (i % 5) as f64).collect();
+        let b = a.mul_vec(&truth).unwrap();
+        let config = IterativeConfig { restart: 50, max_iterations: 5000, ..IterativeConfig::default() };
+        let r = gmres(&a, &b, &config).unwrap();
+        assert!(r.converged, "residual {} after {}", r.residual, r.iterations);
+        assert!(max_error(&r.x, &truth) < 1e-6);
+    }
+
+    #[test]
+    fn gmres_without_restart_matches_cg_on_spd() {
+        let a = poisson_1d(40);
+        let b = vec![1.0; 40];
+        let config = IterativeConfig { restart: 40, ..IterativeConfig::default() };
+        let g = gmres(&a, &b, &config).unwrap();
+        let c = conjugate_gradient(&a, &b, &config).unwrap();
+        assert!(g.converged && c.converged);
+        assert!(max_error(&g.x, &c.x) < 1e-7);
+    }
+
+    #[test]
+    fn iteration_cap_reports_unconverged() {
+        let a = poisson_1d(100);
+        let b = vec![1.0; 100];
+        let config = IterativeConfig { max_iterations: 5, ..IterativeConfig::default() };
+        for r in [conjugate_gradient(&a, &b, &config).unwrap(), gmres(&a, &b, &config).unwrap()] {
+            assert!(!r.converged);
+            assert_eq!(r.iterations, 5);
+            assert!(r.residual > 1e-3);
+        }
+    }
+
+    #[test]
+    fn zero_rhs_and_bad_shapes() {
+        let a = poisson_1d(3);
+        let r = conjugate_gradient(&a, &[0.0; 3], &IterativeConfig::default()).unwrap();
+        assert!(r.converged && r.iterations == 0 && r.x == vec![0.0; 3]);
+        assert!(gmres(&a, &[1.0; 4], &IterativeConfig::default()).is_none());
+        let rect = CsrMatrix::from_triplets(2, 3, &[(0, 0, 1.0)]).unwrap();
+        assert!(conjugate_gradient(&rect, &[1.0; 2], &IterativeConfig::default()).is_none());
+    }
+
+    #[test]
+    fn cg_stops_on_indefinite_matrix() {
+        let a = CsrMatrix::from_triplets(2, 2, &[(0, 0, 1.0), (1, 1, -1.0)]).unwrap();
+        let r = conjugate_gradient(&a, &[1.0, 1.0], &IterativeConfig::default()).unwrap();
+        assert!(!r.converged);
+    }
+
+    #[test]
+    fn gmres_stops_when_a_singular_matrix_cannot_reach_b() {
+        // b = [1, 1] has a component outside range(A) = span{e₀}
+        let a = CsrMatrix::from_triplets(2, 2, &[(0, 0, 2.0)]).unwrap();
+        let r = gmres(&a, &[1.0, 1.0], &IterativeConfig::default()).unwrap();
+        assert!(!r.converged);
+        assert!(r.x.iter().all(|v| v.is_finite()), "{:?}", r.x);
+        assert!(r.iterations < 10, "{} iterations", r.iterations);
+        // The best it can do: solve the reachable part and leave the rest
+        assert!((r.x[0] - 0.5).abs() < 1e-12 && (r.residual - 0.5f64.sqrt()).abs() < 1e-12, "{r:?}");
+        let zero = CsrMatrix::from_triplets(2, 2, &[]).unwrap();
+        assert!(!gmres(&zero, &[0.0, 1.0], &IterativeConfig::default()).unwrap().converged);
+    }
+}
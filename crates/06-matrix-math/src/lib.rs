@@ -6,96 +6,154 @@
 //
 //  Implement:
 //    - transpose: swap rows and columns
-//    - matmul: matrix multiplication (return None if incompatible)
 //    - mul_vec: multiply matrix by column vector
 //    - inverse: Gaussian elimination with partial pivoting
 //    - determinant: via inverse
 //
 //  The constructors (new, from_vec, identity) and index operators
 //  are already provided - use self[(r, c)] in your implementations.
+//  transpose and mul_vec are generic over the element type T: build
+//  results with Matrix::zeros, which works for f32, f64 and complex.
+//  matmul is provided too: it is the hot path for every crate built on
+//  this one, so it runs on the cache-blocked kernel in gemm.rs. Write
+//  the naive triple loop first, then read gemm.rs to see why it is slow.
 // ============================================================
 
+mod decomposition;
+mod eigen;
+mod gemm;
+mod io;
+mod iterative;
+mod norm;
+mod ops;
+mod scalar;
+mod smatrix;
+mod sparse;
+mod svd;
+mod view;
+
 use std::fmt;
-use std::ops::{Add, Index, IndexMut, Mul};
+use std::ops::{Index, IndexMut};
+
+pub use decomposition::{Cholesky, Lu, Qr};
+pub use eigen::SymmetricEigen;
+pub use io::IoError;
+pub use iterative::{conjugate_gradient, gmres, IterativeConfig, IterativeResult};
+pub use norm::Norm;
+pub use num_complex::{Complex, Complex32, Complex64};
+pub use ops::ShapeError;
+pub use scalar::Scalar;
+pub use smatrix::{Matrix2, Matrix3, Matrix4, SMatrix};
+pub use sparse::{CooMatrix, CsrMatrix};
+pub use svd::Svd;
+pub use view::MatrixView;
 
+/// Dense row-major matrix. The element type defaults to `f64`, which every
+/// factorisation and solver works in; `f32` and complex matrices (any
+/// [`Scalar`]) get the arithmetic, transpose and multiplication.
 #[derive(Clone, Debug, PartialEq)]
-pub struct Matrix {
+pub struct Matrix<T = f64> {
     pub rows: usize,
     pub cols: usize,
-    data: Vec<f64>,
+    data: Vec<T>,
 }
 
 impl Matrix {
     pub fn new(rows: usize, cols: usize) -> Self {
-        Self { rows, cols, data: vec![0.0; rows * cols] }
+        Self::zeros(rows, cols)
     }
 
     pub fn identity(n: usize) -> Self {
-        let mut m = Self::new(n, n);
-        for i in 0..n { m[(i, i)] = 1.0; }
+        Self::from_diagonal(&vec![1.0; n])
+    }
+}
+
+impl<T: Scalar> Matrix<T> {
+    /// `new` for any element type: `Matrix::<f32>::zeros(2, 3)`
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, data: vec![T::ZERO; rows * cols] }
+    }
+
+    /// Square matrix with `diag` on the diagonal - `identity` for any element type
+    pub fn from_diagonal(diag: &[T]) -> Self {
+        let mut m = Self::zeros(diag.len(), diag.len());
+        for (i, &d) in diag.iter().enumerate() { m[(i, i)] = d; }
         m
     }
 
-    pub fn from_vec(rows: usize, cols: usize, data: Vec<f64>) -> Self {
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Self {
         assert_eq!(data.len(), rows * cols, "data length must equal rows x cols");
         Self { rows, cols, data }
     }
 
     pub fn transpose(&self) -> Self {
-        todo!()
+        let mut t = Matrix::zeros(self.cols, self.rows);
+        for r in 0..self.rows { for c in 0..self.cols { t[(c, r)] = self[(r, c)]; } }
+        t
     }
 
     /// Matrix multiplication. Returns None if dimensions are incompatible.
-    pub fn matmul(&self, rhs: &Matrix) -> Option<Matrix> {
-        todo!()
+    /// Uses a cache-blocked, packed kernel (parallel with the `rayon` feature).
+    pub fn matmul(&self, rhs: &Matrix<T>) -> Option<Matrix<T>> {
+        if self.cols != rhs.rows { return None; }
+        let mut out = Matrix::zeros(self.rows, rhs.cols);
+        gemm::gemm(self.rows, self.cols, rhs.cols, &self.data, &rhs.data, &mut out.data);
+        Some(out)
     }
 
     /// Multiply matrix by column vector. Returns None if dimensions incompatible.
-    pub fn mul_vec(&self, v: &[f64]) -> Option<Vec<f64>> {
-        todo!()
+    pub fn mul_vec(&self, v: &[T]) -> Option<Vec<T>> {
+        if self.cols != v.len() { return None; }
+        Some((0..self.rows).map(|i| (0..self.cols).map(|k| self[(i, k)] * v[k]).sum()).collect())
     }
 
+    /// Apply `f` to every element, e.g. to change precision: `m.map(|x| x as f32)`
+    pub fn map<U: Scalar>(&self, f: impl Fn(T) -> U) -> Matrix<U> {
+        Matrix { rows: self.rows, cols: self.cols, data: self.data.iter().map(|&x| f(x)).collect() }
+    }
+
+    /// Conjugate transpose Aᴴ - the plain transpose for real matrices
+    pub fn adjoint(&self) -> Self {
+        self.transpose().map(T::conj)
+    }
+}
+
+impl Matrix {
     /// Gaussian elimination with partial pivoting - returns (inverse, determinant).
     /// Returns None if matrix is singular or non-square.
     pub fn inverse(&self) -> Option<(Matrix, f64)> {
-        todo!()
+        if self.rows != self.cols { return None; }
+        let n = self.rows; let mut a = self.clone(); let mut inv = Matrix::identity(n); let mut det = 1.0;
+        for k in 0..n {
+            let p = (k..n).max_by(|&x, &y| a[(x, k)].abs().partial_cmp(&a[(y, k)].abs()).unwrap()).unwrap();
+            if a[(p, k)].abs() < 1e-10 { return None; }
+            if p != k { for c in 0..n { let t = a[(k, c)]; a[(k, c)] = a[(p, c)]; a[(p, c)] = t; let t = inv[(k, c)]; inv[(k, c)] = inv[(p, c)]; inv[(p, c)] = t; } det = -det; }
+            let piv = a[(k, k)]; det *= piv;
+            for c in 0..n { a[(k, c)] /= piv; inv[(k, c)] /= piv; }
+            for r in 0..n { if r != k { let f = a[(r, k)]; for c in 0..n { a[(r, c)] -= f * a[(k, c)]; inv[(r, c)] -= f * inv[(k, c)]; } } }
+        }
+        Some((inv, det))
     }
 
     pub fn determinant(&self) -> Option<f64> {
-        todo!()
+        self.inverse().map(|(_, d)| d)
     }
 }
 
-impl Index<(usize, usize)> for Matrix {
-    type Output = f64;
-    fn index(&self, (r, c): (usize, usize)) -> &f64 {
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+    fn index(&self, (r, c): (usize, usize)) -> &T {
         &self.data[r * self.cols + c]
     }
 }
 
-impl IndexMut<(usize, usize)> for Matrix {
-    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut f64 {
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
         &mut self.data[r * self.cols + c]
     }
 }
 
-impl Add for Matrix {
-    type Output = Option<Matrix>;
-    fn add(self, rhs: Matrix) -> Option<Matrix> {
-        if self.rows != rhs.rows || self.cols != rhs.cols { return None; }
-        let data = self.data.iter().zip(&rhs.data).map(|(a, b)| a + b).collect();
-        Some(Matrix { rows: self.rows, cols: self.cols, data })
-    }
-}
-
-impl Mul for Matrix {
-    type Output = Option<Matrix>;
-    fn mul(self, rhs: Matrix) -> Option<Matrix> {
-        self.matmul(&rhs)
-    }
-}
-
-impl fmt::Display for Matrix {
+impl<T: Scalar + fmt::Display> fmt::Display for Matrix<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for r in 0..self.rows {
             write!(f, "[")?;
@@ -189,4 +247,24 @@ mod tests {
         let b = Matrix::new(2, 3);
         assert!(a.matmul(&b).is_none());
     }
+
+    #[test]
+    fn f32_matrices_multiply_like_f64() {
+        let a = Matrix::from_vec(2, 2, vec![1.0_f32, 2.0, 3.0, 4.0]);
+        let b = Matrix::<f32>::from_diagonal(&[2.0, 2.0]);
+        assert_eq!(a.matmul(&b).unwrap(), Matrix::from_vec(2, 2, vec![2.0, 4.0, 6.0, 8.0]));
+        let wide = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]).map(|x: f64| x as f32);
+        assert_eq!(wide, a);
+    }
+
+    #[test]
+    fn complex_product_and_adjoint() {
+        let i = Complex64::new(0.0, 1.0);
+        let one = Complex64::ONE;
+        // Pauli Y is Hermitian and squares to the identity
+        let y = Matrix::from_vec(2, 2, vec![Complex64::ZERO, -i, i, Complex64::ZERO]);
+        assert_eq!(y.adjoint(), y);
+        assert_eq!(y.matmul(&y).unwrap(), Matrix::from_diagonal(&[one, one]));
+        assert_eq!(y.mul_vec(&[one, i]).unwrap(), vec![one, i]);
+    }
 }
@@ -1,4 +1,4 @@
-use matrix_math::Matrix;
+use matrix_math::{conjugate_gradient, gmres, CooMatrix, IterativeConfig, Matrix, Matrix2, Norm};
 
 fn main() {
     // --- 2D rotation transform (robotics / graphics) ---
@@ -14,6 +14,11 @@ fn main() {
     let rotated = rot.mul_vec(&point).unwrap();
     println!("Rotating [1, 0] by 45° → [{:.4}, {:.4}]\n", rotated[0], rotated[1]);
 
+    // Same transform as a stack-allocated Matrix2: sizes are checked at compile time
+    let eighth = Matrix2::rotation(theta);
+    let [x, y] = (eighth * eighth).mul_vec([1.0, 0.0]);
+    println!("Two 45° Matrix2 rotations compose to 90°: [1, 0] → [{:.4}, {:.4}]\n", x, y);
+
     // --- Matrix inverse and determinant ---
     println!("=== Inverse & Determinant ===\n");
     let m = Matrix::from_vec(3, 3, vec![
@@ -46,4 +51,63 @@ fn main() {
     let (xtx_inv, _) = xtx.inverse().unwrap();
     let beta = xtx_inv.mul_vec(&xty).unwrap();
     println!("For y = 2x + 3: fitted β₀ = {:.4}, β₁ = {:.4}", beta[0], beta[1]);
+
+    // Same fit without forming (XᵀX)⁻¹: QR works on X directly, so the
+    // condition number is not squared
+    let beta_qr = x.least_squares(&y).unwrap();
+    println!("Via Householder QR:      β₀ = {:.4}, β₁ = {:.4}\n", beta_qr[0], beta_qr[1]);
+
+    // --- Factorise once, solve many (LU / Cholesky) ---
+    println!("=== Factorisations: solve without inverting ===\n");
+    let lu = m.lu().unwrap();
+    let x1 = lu.solve(&[1.0, 2.0, 3.0]).unwrap();
+    let x2 = lu.solve(&[3.0, 5.0, 3.0]).unwrap();
+    println!("LU:       M x = [1, 2, 3] → x = [{:.4}, {:.4}, {:.4}]", x1[0], x1[1], x1[2]);
+    println!("          M x = [3, 5, 3] → x = [{:.4}, {:.4}, {:.4}]", x2[0], x2[1], x2[2]);
+    // M is symmetric positive definite, so Cholesky applies too
+    let chol = m.cholesky().unwrap();
+    println!("Cholesky: L =\n{}", chol.l());
+    println!("det(M) via Cholesky = {:.4}", chol.determinant());
+
+    // --- Eigendecomposition and SVD ---
+    println!("\n=== Eigenvalues & Singular Values ===\n");
+    let eig = m.symmetric_eigen().unwrap();
+    println!("eig(M) = {:.4?}  (Jacobi; sum = trace = {:.1})", eig.values, eig.values.iter().sum::<f64>());
+    let svd = x.svd();
+    let sv = &svd.singular_values;
+    println!("σ(X)   = {:.4?}  (one-sided Jacobi; cond = {:.2})", sv, sv[0] / sv[sv.len() - 1]);
+
+    // --- Conditioning: how much can we trust a solve? ---
+    println!("\n=== Condition Numbers ===\n");
+    for n in [4, 8, 12] {
+        let h = Matrix::from_vec(n, n, (0..n * n).map(|k| 1.0 / ((k / n + k % n) as f64 + 1.0)).collect());
+        println!("Hilbert {n:>2}x{n:<2}  κ₁ ≈ {:.2e}  κ₂ = {:.2e}  numerical rank {}",
+            h.condition_estimate(), h.condition_number(Norm::Two), h.rank());
+    }
+
+    // --- Sparse systems: 2-D Poisson equation on a grid ---
+    println!("\n=== Sparse 2-D Poisson (5-point Laplacian) ===\n");
+    let side = 50;
+    let n = side * side;
+    let mut coo = CooMatrix::new(n, n);
+    for r in 0..side {
+        for c in 0..side {
+            let i = r * side + c;
+            coo.push(i, i, 4.0);
+            if r > 0 { coo.push(i, i - side, -1.0); }
+            if r + 1 < side { coo.push(i, i + side, -1.0); }
+            if c > 0 { coo.push(i, i - 1, -1.0); }
+            if c + 1 < side { coo.push(i, i + 1, -1.0); }
+        }
+    }
+    let laplacian = coo.to_csr();
+    println!("{n} unknowns, {} non-zeros ({:.2}% of the {} dense entries)",
+        laplacian.nnz(), 100.0 * laplacian.nnz() as f64 / (n * n) as f64, n * n);
+    let heat = vec![1.0; n];
+    let config = IterativeConfig::default();
+    let cg = conjugate_gradient(&laplacian, &heat, &config).unwrap();
+    println!("CG:    {:>4} iterations, relative residual {:.1e}", cg.iterations, cg.residual);
+    let gm = gmres(&laplacian, &heat, &config).unwrap();
+    println!("GMRES: {:>4} iterations, relative residual {:.1e} (restart {})", gm.iterations, gm.residual, config.restart);
+    println!("Peak of the solution (grid centre): {:.4}", cg.x[(side / 2) * side + side / 2]);
 }
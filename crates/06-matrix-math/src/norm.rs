@@ -0,0 +1,217 @@
+//! Matrix norms and the diagnostics built on them: condition number,
+//! numerical rank and the Moore–Penrose pseudo-inverse.
+//!
+//! The condition number κ(A) = ‖A‖ ‖A⁻¹‖ bounds how much a relative error in
+//! b can grow in the solution of A x = b: expect to lose about log₁₀ κ digits.
+//! Once κ nears 1/ε ≈ 10¹⁶ the matrix is singular as far as f64 can tell,
+//! even if elimination still finds non-zero pivots and returns a finite,
+//! meaningless inverse.
+
+use crate::{Lu, Matrix, Svd};
+
+/// Which matrix norm to take
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Norm {
+    /// Largest absolute column sum
+    One,
+    /// Largest singular value - the spectral norm
+    Two,
+    /// Largest absolute row sum
+    Inf,
+    /// Square root of the sum of squares of every element
+    Frobenius,
+}
+
+impl Matrix {
+    pub fn norm(&self, kind: Norm) -> f64 {
+        match kind {
+            Norm::One => (0..self.cols)
+                .map(|c| (0..self.rows).map(|r| self[(r, c)].abs()).sum::<f64>())
+                .fold(0.0, f64::max),
+            Norm::Inf => self.row_iter().map(|row| row.iter().map(|x| x.abs()).sum::<f64>()).fold(0.0, f64::max),
+            Norm::Frobenius => self.data.iter().map(|x| x * x).sum::<f64>().sqrt(),
+            Norm::Two => self.svd().singular_values.first().copied().unwrap_or(0.0),
+        }
+    }
+
+    /// κ(A) = ‖A‖ ‖A⁺‖ in the chosen norm - for an invertible matrix A⁺ = A⁻¹.
+    /// The 2-norm version is σ_max / σ_min from the SVD and also serves
+    /// rectangular matrices. Returns `f64::INFINITY` for a singular matrix.
+    /// Costs O(n³); see `condition_estimate` for a cheaper 1-norm figure.
+    pub fn condition_number(&self, kind: Norm) -> f64 {
+        if self.rows == 0 || self.cols == 0 { return 0.0; }
+        if kind == Norm::Two || self.rows != self.cols {
+            let svd = self.svd();
+            if kind == Norm::Two { return svd.condition_number(); }
+            if svd.rank(svd.default_tolerance()) < self.rows.min(self.cols) { return f64::INFINITY; }
+            return self.norm(kind) * svd.pseudo_inverse(0.0).norm(kind);
+        }
+        match Lu::new(self) {
+            Some(lu) => self.norm(kind) * lu.inverse().norm(kind),
+            None => f64::INFINITY,
+        }
+    }
+
+    /// O(n²) estimate of the 1-norm condition number from an LU factorisation
+    /// (see `Lu::condition_estimate`). Returns `f64::INFINITY` if the matrix is
+    /// singular or not square.
+    pub fn condition_estimate(&self) -> f64 {
+        Lu::new(self).map_or(f64::INFINITY, |lu| lu.condition_estimate())
+    }
+
+    /// Number of singular values above the default tolerance
+    /// σ₁ · max(m, n) · ε, the same cut-off NumPy's `matrix_rank` uses.
+    pub fn rank(&self) -> usize {
+        let svd = self.svd();
+        svd.rank(svd.default_tolerance())
+    }
+
+    /// Number of singular values strictly greater than `tolerance`
+    pub fn rank_with_tolerance(&self, tolerance: f64) -> usize {
+        self.svd().rank(tolerance)
+    }
+
+    /// Moore–Penrose pseudo-inverse A⁺ (n×m for an m×n A), discarding
+    /// singular values at or below the default tolerance of `rank`.
+    /// For a full-column-rank A, A⁺ b is the least-squares solution of A x = b.
+    pub fn pseudo_inverse(&self) -> Matrix {
+        let svd = self.svd();
+        svd.pseudo_inverse(svd.default_tolerance())
+    }
+
+    /// Pseudo-inverse treating singular values <= `tolerance` as zero -
+    /// truncated SVD, which regularises an ill-conditioned inverse.
+    pub fn pseudo_inverse_with_tolerance(&self, tolerance: f64) -> Matrix {
+        self.svd().pseudo_inverse(tolerance)
+    }
+}
+
+impl Svd {
+    /// σ₁ · max(m, n) · ε: singular values below this are rounding noise
+    pub fn default_tolerance(&self) -> f64 {
+        let largest = self.singular_values.first().copied().unwrap_or(0.0);
+        largest * self.u.rows.max(self.v.rows) as f64 * f64::EPSILON
+    }
+
+    pub fn rank(&self, tolerance: f64) -> usize {
+        self.singular_values.iter().filter(|&&s| s > tolerance).count()
+    }
+
+    /// σ_max / σ_min - infinite once σ_min is down at the default tolerance,
+    /// where the matrix is numerically rank-deficient and the ratio is noise
+    pub fn condition_number(&self) -> f64 {
+        match (self.singular_values.first(), self.singular_values.last()) {
+            (Some(&max), Some(&min)) if min > self.default_tolerance() => max / min,
+            (Some(_), Some(_)) => f64::INFINITY,
+            _ => 0.0,
+        }
+    }
+
+    /// V Σ⁺ Uᵀ, where Σ⁺ inverts the singular values above `tolerance` and zeroes the rest
+    pub fn pseudo_inverse(&self, tolerance: f64) -> Matrix {
+        let (m, n) = (self.u.rows, self.v.rows);
+        let mut out = Matrix::new(n, m);
+        for (i, &sigma) in self.singular_values.iter().enumerate() {
+            if sigma <= tolerance { continue; }
+            for r in 0..n {
+                let vr = self.v[(r, i)] / sigma;
+                for c in 0..m { out[(r, c)] += vr * self.u[(c, i)]; }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hilbert(n: usize) -> Matrix {
+        Matrix::from_vec(n, n, (0..n * n).map(|k| 1.0 / ((k / n + k % n) as f64 + 1.0)).collect())
+    }
+
+    fn assert_close(a: &Matrix, b: &Matrix, tol: f64) {
+        assert_eq!((a.rows, a.cols), (b.rows, b.cols));
+        for (x, y) in a.data.iter().zip(&b.data) {
+            assert!((x - y).abs() <= tol, "{x} vs {y}\n{a}\n{b}");
+        }
+    }
+
+    #[test]
+    fn norms_of_a_small_matrix() {
+        let a = Matrix::from_vec(2, 2, vec![1.0, -2.0, 3.0, 4.0]);
+        assert_eq!(a.norm(Norm::One), 6.0);
+        assert_eq!(a.norm(Norm::Inf), 7.0);
+        assert_eq!(a.norm(Norm::Frobenius), 30.0_f64.sqrt());
+        // Reference value from 40-digit arithmetic
+        assert!((a.norm(Norm::Two) - 5.116672736016927).abs() < 1e-14);
+        assert_eq!(Matrix::new(0, 3).norm(Norm::One), 0.0);
+    }
+
+    #[test]
+    fn hilbert_condition_numbers_match_reference() {
+        // κ(H₆) from 40-digit arithmetic; H₆ is notoriously ill-conditioned
+        let h = hilbert(6);
+        let cases = [(Norm::Two, 14951058.640131216), (Norm::One, 29070279.0), (Norm::Inf, 29070279.0), (Norm::Frobenius, 15118987.12639029)];
+        for (kind, want) in cases {
+            let got = h.condition_number(kind);
+            assert!((got - want).abs() / want < 1e-7, "{kind:?}: {got} vs {want}");
+        }
+        assert_eq!(Matrix::identity(4).condition_number(Norm::Two), 1.0);
+    }
+
+    #[test]
+    fn condition_estimate_is_a_tight_lower_bound() {
+        for n in [2, 5, 8] {
+            let h = hilbert(n);
+            let exact = h.condition_number(Norm::One);
+            let estimate = h.condition_estimate();
+            assert!(estimate <= exact * (1.0 + 1e-6) && estimate >= exact / 3.0, "n={n}: {estimate} vs {exact}");
+        }
+        assert_eq!(Matrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 4.0]).condition_estimate(), f64::INFINITY);
+    }
+
+    #[test]
+    fn rank_detects_dependent_columns() {
+        // Third column = first + second
+        let a = Matrix::from_vec(4, 3, vec![1.0, 2.0, 3.0, 0.0, 1.0, 1.0, 2.0, 0.0, 2.0, 1.0, 1.0, 2.0]);
+        assert_eq!(a.rank(), 2);
+        assert_eq!(a.condition_number(Norm::Two), f64::INFINITY);
+        assert_eq!(hilbert(5).rank(), 5);
+        // A loose tolerance counts tiny singular values as zero
+        assert!(hilbert(8).rank_with_tolerance(1e-6) < 8);
+    }
+
+    #[test]
+    fn pseudo_inverse_satisfies_the_penrose_conditions() {
+        let a = Matrix::from_vec(4, 3, vec![1.0, 2.0, 3.0, 0.0, 1.0, 1.0, 2.0, 0.0, 2.0, 1.0, 1.0, 2.0]);
+        let p = a.pseudo_inverse();
+        assert_eq!((p.rows, p.cols), (3, 4));
+        let ap = (&a * &p).unwrap();
+        let pa = (&p * &a).unwrap();
+        assert_close(&(&ap * &a).unwrap(), &a, 1e-12);
+        assert_close(&(&pa * &p).unwrap(), &p, 1e-12);
+        assert_close(&ap, &ap.transpose(), 1e-12);
+        assert_close(&pa, &pa.transpose(), 1e-12);
+    }
+
+    #[test]
+    fn pseudo_inverse_of_full_rank_matrices() {
+        let m = Matrix::from_vec(3, 3, vec![4.0, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 2.0]);
+        assert_close(&m.pseudo_inverse(), &m.lu().unwrap().inverse(), 1e-14);
+        // Tall: A⁺ b is the least-squares solution
+        let x = Matrix::from_vec(4, 2, vec![1.0, 1.0, 1.0, 2.0, 1.0, 3.0, 1.0, 4.0]);
+        let y = [5.1, 6.9, 8.9, 11.1];
+        let beta = x.pseudo_inverse().mul_vec(&y).unwrap();
+        for (b, ls) in beta.iter().zip(x.least_squares(&y).unwrap()) {
+            assert!((b - ls).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn rectangular_condition_numbers_use_the_pseudo_inverse() {
+        let x = Matrix::from_vec(3, 2, vec![1.0, 0.0, 0.0, 2.0, 0.0, 0.0]);
+        assert!((x.condition_number(Norm::Two) - 2.0).abs() < 1e-15);
+        assert!((x.condition_number(Norm::One) - 2.0).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,228 @@
+//! Arithmetic on `Matrix`: matrix ± matrix and matrix × matrix by value or by
+//! reference, scalar broadcasting, element-wise products and stacking.
+//!
+//! Anything that needs two shapes to agree returns `Result<_, ShapeError>`, so
+//! `(&a * &b)?` reports which operation failed and on what sizes. Operations
+//! that cannot fail (scaling, negation) return the matrix directly.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{Matrix, Scalar};
+
+/// Two matrices whose dimensions do not fit the operation applied to them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShapeError {
+    /// The operation that was attempted, e.g. "add" or "hstack"
+    pub op: &'static str,
+    /// (rows, cols) of the left operand, or of the first matrix stacked
+    pub left: (usize, usize),
+    /// (rows, cols) of the operand that did not fit
+    pub right: (usize, usize),
+}
+
+impl ShapeError {
+    fn new<T>(op: &'static str, left: &Matrix<T>, right: &Matrix<T>) -> Self {
+        Self { op, left: (left.rows, left.cols), right: (right.rows, right.cols) }
+    }
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f, "cannot {} a {}x{} matrix and a {}x{} matrix",
+            self.op, self.left.0, self.left.1, self.right.0, self.right.1,
+        )
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+impl<T: Scalar> Matrix<T> {
+    /// Combine two same-shaped matrices element by element
+    pub fn zip_map(&self, rhs: &Matrix<T>, f: impl Fn(T, T) -> T) -> Result<Matrix<T>, ShapeError> {
+        self.elementwise("combine", rhs, f)
+    }
+
+    /// Element-wise (Hadamard) product A ∘ B
+    pub fn mul_elementwise(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.elementwise("multiply element-wise", rhs, |a, b| a * b)
+    }
+
+    /// Element-wise quotient; dividing by a zero element follows the element type
+    pub fn div_elementwise(&self, rhs: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.elementwise("divide element-wise", rhs, |a, b| a / b)
+    }
+
+    /// Place matrices side by side. Every one must have the same number of rows.
+    pub fn hstack(parts: &[&Matrix<T>]) -> Result<Matrix<T>, ShapeError> {
+        let Some(first) = parts.first() else { return Ok(Matrix::zeros(0, 0)) };
+        if let Some(bad) = parts.iter().find(|m| m.rows != first.rows) {
+            return Err(ShapeError::new("hstack", first, bad));
+        }
+        let cols = parts.iter().map(|m| m.cols).sum();
+        let mut data = Vec::with_capacity(first.rows * cols);
+        for r in 0..first.rows {
+            for m in parts { data.extend_from_slice(m.row(r)); }
+        }
+        Ok(Matrix { rows: first.rows, cols, data })
+    }
+
+    /// Place matrices one above the other. Every one must have the same number of columns.
+    pub fn vstack(parts: &[&Matrix<T>]) -> Result<Matrix<T>, ShapeError> {
+        let Some(first) = parts.first() else { return Ok(Matrix::zeros(0, 0)) };
+        if let Some(bad) = parts.iter().find(|m| m.cols != first.cols) {
+            return Err(ShapeError::new("vstack", first, bad));
+        }
+        let data = parts.iter().flat_map(|m| m.data.iter().copied()).collect();
+        Ok(Matrix { rows: parts.iter().map(|m| m.rows).sum(), cols: first.cols, data })
+    }
+
+    fn elementwise(&self, op: &'static str, rhs: &Matrix<T>, f: impl Fn(T, T) -> T) -> Result<Matrix<T>, ShapeError> {
+        if (self.rows, self.cols) != (rhs.rows, rhs.cols) { return Err(ShapeError::new(op, self, rhs)); }
+        let data = self.data.iter().zip(&rhs.data).map(|(&a, &b)| f(a, b)).collect();
+        Ok(Matrix { rows: self.rows, cols: self.cols, data })
+    }
+}
+
+/// Matrix ⊕ matrix for every owned / borrowed combination, all delegating to
+/// the `&Matrix ⊕ &Matrix` case.
+macro_rules! matrix_op {
+    ($Op:ident, $method:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl<T: Scalar> $Op<&Matrix<T>> for &Matrix<T> {
+            type Output = Result<Matrix<T>, ShapeError>;
+            fn $method(self, rhs: &Matrix<T>) -> Self::Output {
+                let ($a, $b) = (self, rhs);
+                $body
+            }
+        }
+        impl<T: Scalar> $Op<Matrix<T>> for Matrix<T> {
+            type Output = Result<Matrix<T>, ShapeError>;
+            fn $method(self, rhs: Matrix<T>) -> Self::Output { (&self).$method(&rhs) }
+        }
+        impl<T: Scalar> $Op<&Matrix<T>> for Matrix<T> {
+            type Output = Result<Matrix<T>, ShapeError>;
+            fn $method(self, rhs: &Matrix<T>) -> Self::Output { (&self).$method(rhs) }
+        }
+        impl<T: Scalar> $Op<Matrix<T>> for &Matrix<T> {
+            type Output = Result<Matrix<T>, ShapeError>;
+            fn $method(self, rhs: Matrix<T>) -> Self::Output { self.$method(&rhs) }
+        }
+    };
+}
+
+matrix_op!(Add, add, |a, b| a.elementwise("add", b, |x, y| x + y));
+matrix_op!(Sub, sub, |a, b| a.elementwise("subtract", b, |x, y| x - y));
+matrix_op!(Mul, mul, |a, b| a.matmul(b).ok_or_else(|| ShapeError::new("multiply", a, b)));
+
+/// Matrix ⊕ scalar, applied to every element, by value (reusing the
+/// allocation) or by reference, plus the compound-assignment form.
+macro_rules! scalar_op {
+    ($Op:ident, $method:ident, $OpAssign:ident, $assign:ident, $op:tt) => {
+        impl<T: Scalar> $Op<T> for Matrix<T> {
+            type Output = Matrix<T>;
+            fn $method(mut self, k: T) -> Matrix<T> {
+                self.$assign(k);
+                self
+            }
+        }
+        impl<T: Scalar> $Op<T> for &Matrix<T> {
+            type Output = Matrix<T>;
+            fn $method(self, k: T) -> Matrix<T> { self.map(|x| x $op k) }
+        }
+        impl<T: Scalar> $OpAssign<T> for Matrix<T> {
+            fn $assign(&mut self, k: T) {
+                for x in &mut self.data { *x = *x $op k; }
+            }
+        }
+    };
+}
+
+scalar_op!(Add, add, AddAssign, add_assign, +);
+scalar_op!(Sub, sub, SubAssign, sub_assign, -);
+scalar_op!(Mul, mul, MulAssign, mul_assign, *);
+scalar_op!(Div, div, DivAssign, div_assign, /);
+
+impl<T: Scalar> Neg for Matrix<T> {
+    type Output = Matrix<T>;
+    fn neg(self) -> Matrix<T> { self * -T::ONE }
+}
+
+impl<T: Scalar> Neg for &Matrix<T> {
+    type Output = Matrix<T>;
+    fn neg(self) -> Matrix<T> { self * -T::ONE }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(rows: usize, cols: usize, data: &[f64]) -> Matrix {
+        Matrix::from_vec(rows, cols, data.to_vec())
+    }
+
+    #[test]
+    fn borrowed_arithmetic_leaves_operands_usable() {
+        let a = m(2, 2, &[1.0, 2.0, 3.0, 4.0]);
+        let b = m(2, 2, &[5.0, 6.0, 7.0, 8.0]);
+        assert_eq!((&a + &b).unwrap(), m(2, 2, &[6.0, 8.0, 10.0, 12.0]));
+        assert_eq!((&b - &a).unwrap(), m(2, 2, &[4.0; 4]));
+        assert_eq!((&a * &b).unwrap(), m(2, 2, &[19.0, 22.0, 43.0, 50.0]));
+        // Owned and mixed forms agree with the borrowed one
+        assert_eq!((a.clone() * &b).unwrap(), (a * b).unwrap());
+    }
+
+    #[test]
+    fn shape_mismatch_names_the_operation_and_sizes() {
+        let a = m(2, 3, &[0.0; 6]);
+        let b = m(3, 2, &[0.0; 6]);
+        let err = (&a + &b).unwrap_err();
+        assert_eq!(err, ShapeError { op: "add", left: (2, 3), right: (3, 2) });
+        assert_eq!(err.to_string(), "cannot add a 2x3 matrix and a 3x2 matrix");
+        assert_eq!((&a * &a).unwrap_err().op, "multiply");
+        assert!((&a * &b).is_ok());
+    }
+
+    #[test]
+    fn scalar_ops_broadcast_over_every_element() {
+        let a = m(1, 3, &[1.0, 2.0, 4.0]);
+        assert_eq!(&a * 2.0, m(1, 3, &[2.0, 4.0, 8.0]));
+        assert_eq!(&a / 2.0, m(1, 3, &[0.5, 1.0, 2.0]));
+        assert_eq!(&a + 1.0, m(1, 3, &[2.0, 3.0, 5.0]));
+        assert_eq!(-&a, m(1, 3, &[-1.0, -2.0, -4.0]));
+        let mut b = a - 1.0;
+        b *= 3.0;
+        assert_eq!(b, m(1, 3, &[0.0, 3.0, 9.0]));
+    }
+
+    #[test]
+    fn elementwise_product_and_quotient() {
+        let a = m(2, 2, &[1.0, 2.0, 3.0, 4.0]);
+        let b = m(2, 2, &[2.0, 2.0, 0.5, 4.0]);
+        assert_eq!(a.mul_elementwise(&b).unwrap(), m(2, 2, &[2.0, 4.0, 1.5, 16.0]));
+        assert_eq!(a.div_elementwise(&b).unwrap(), m(2, 2, &[0.5, 1.0, 6.0, 1.0]));
+        assert_eq!(a.zip_map(&b, f64::max).unwrap(), m(2, 2, &[2.0, 2.0, 3.0, 4.0]));
+        assert_eq!(a.mul_elementwise(&m(1, 1, &[1.0])).unwrap_err().op, "multiply element-wise");
+    }
+
+    #[test]
+    fn stacking_checks_the_shared_dimension() {
+        let a = m(2, 1, &[1.0, 2.0]);
+        let b = m(2, 2, &[3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(Matrix::hstack(&[&a, &b]).unwrap(), m(2, 3, &[1.0, 3.0, 4.0, 2.0, 5.0, 6.0]));
+        assert_eq!(Matrix::vstack(&[&b, &b.transpose()]).unwrap().rows, 4);
+        assert_eq!(
+            Matrix::vstack(&[&b, &a]).unwrap_err(),
+            ShapeError { op: "vstack", left: (2, 2), right: (2, 1) },
+        );
+        assert_eq!(Matrix::<f64>::hstack(&[]).unwrap(), Matrix::zeros(0, 0));
+    }
+
+    #[test]
+    fn question_mark_propagates_shape_errors() {
+        fn gram(x: &Matrix) -> Result<Matrix, Box<dyn std::error::Error>> {
+            Ok((&x.transpose() * x)?)
+        }
+        assert_eq!(gram(&m(2, 1, &[3.0, 4.0])).unwrap(), m(1, 1, &[25.0]));
+    }
+}
@@ -0,0 +1,66 @@
+//! The numeric trait behind `Matrix<T>` and `SMatrix<R, C, T>`.
+
+use std::fmt::Debug;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num_complex::Complex;
+
+/// An element type closed under + - × ÷ with additive and multiplicative identities.
+///
+/// Implemented for `f32`, `f64` and `Complex<f32>` / `Complex<f64>`. Only the
+/// operations every element type shares live here; anything that needs an
+/// ordering (pivoting, convergence tests) stays on `Matrix<f64>`.
+pub trait Scalar:
+    Copy
+    + PartialEq
+    + Debug
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + Sum
+    + 'static
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Complex conjugate; the identity for real types
+    fn conj(self) -> Self {
+        self
+    }
+}
+
+impl Scalar for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+}
+
+impl Scalar for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+}
+
+impl Scalar for Complex<f32> {
+    const ZERO: Self = Complex::new(0.0, 0.0);
+    const ONE: Self = Complex::new(1.0, 0.0);
+
+    fn conj(self) -> Self {
+        Complex::conj(&self)
+    }
+}
+
+impl Scalar for Complex<f64> {
+    const ZERO: Self = Complex::new(0.0, 0.0);
+    const ONE: Self = Complex::new(1.0, 0.0);
+
+    fn conj(self) -> Self {
+        Complex::conj(&self)
+    }
+}
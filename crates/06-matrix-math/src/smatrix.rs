@@ -0,0 +1,245 @@
+//! Fixed-size, stack-allocated matrices with dimensions in the type.
+//!
+//! `SMatrix<2, 3> * SMatrix<3, 4>` is an `SMatrix<2, 4>`; multiplying an
+//! `SMatrix<2, 3>` by another `SMatrix<2, 3>` does not compile. There is no
+//! heap allocation and no `Option` to unwrap - the right tool for the 2×2 /
+//! 3×3 / 4×4 transforms of graphics and robotics.
+
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+use crate::{Matrix, Scalar, ShapeError};
+
+/// R×C matrix stored inline as `[[T; C]; R]`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SMatrix<const R: usize, const C: usize, T = f64> {
+    data: [[T; C]; R],
+}
+
+pub type Matrix2<T = f64> = SMatrix<2, 2, T>;
+pub type Matrix3<T = f64> = SMatrix<3, 3, T>;
+pub type Matrix4<T = f64> = SMatrix<4, 4, T>;
+
+impl<const R: usize, const C: usize, T: Scalar> SMatrix<R, C, T> {
+    /// Build from rows: `SMatrix::new([[1.0, 2.0], [3.0, 4.0]])`
+    pub const fn new(rows: [[T; C]; R]) -> Self {
+        Self { data: rows }
+    }
+
+    pub fn zeros() -> Self {
+        Self { data: [[T::ZERO; C]; R] }
+    }
+
+    pub fn transpose(&self) -> SMatrix<C, R, T> {
+        let mut t = SMatrix::<C, R, T>::zeros();
+        for r in 0..R {
+            for c in 0..C { t.data[c][r] = self.data[r][c]; }
+        }
+        t
+    }
+
+    pub fn mul_vec(&self, v: [T; C]) -> [T; R] {
+        let mut out = [T::ZERO; R];
+        for (o, row) in out.iter_mut().zip(&self.data) {
+            *o = row.iter().zip(&v).map(|(&a, &b)| a * b).sum();
+        }
+        out
+    }
+
+    pub fn scale(&self, k: T) -> Self {
+        let mut out = *self;
+        for x in out.data.iter_mut().flatten() { *x *= k; }
+        out
+    }
+
+    pub fn rows(&self) -> &[[T; C]; R] {
+        &self.data
+    }
+}
+
+impl<const N: usize, T: Scalar> SMatrix<N, N, T> {
+    pub fn identity() -> Self {
+        let mut m = Self::zeros();
+        for i in 0..N { m.data[i][i] = T::ONE; }
+        m
+    }
+
+    pub fn trace(&self) -> T {
+        (0..N).map(|i| self.data[i][i]).sum()
+    }
+}
+
+impl<T: Scalar> Matrix2<T> {
+    pub fn determinant(&self) -> T {
+        let [[a, b], [c, d]] = self.data;
+        a * d - b * c
+    }
+}
+
+impl<T: Scalar> Matrix3<T> {
+    pub fn determinant(&self) -> T {
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.data;
+        a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g)
+    }
+}
+
+impl Matrix2 {
+    /// Counter-clockwise rotation by `theta` radians
+    pub fn rotation(theta: f64) -> Self {
+        let (s, c) = theta.sin_cos();
+        Self::new([[c, -s], [s, c]])
+    }
+}
+
+impl Matrix4 {
+    /// Homogeneous transform: rotate by `theta` about the z axis, then translate
+    pub fn rotation_z_translation(theta: f64, [x, y, z]: [f64; 3]) -> Self {
+        let (s, c) = theta.sin_cos();
+        Self::new([
+            [c, -s, 0.0, x],
+            [s, c, 0.0, y],
+            [0.0, 0.0, 1.0, z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+impl<const R: usize, const C: usize, const K: usize, T: Scalar> Mul<SMatrix<C, K, T>> for SMatrix<R, C, T> {
+    type Output = SMatrix<R, K, T>;
+    fn mul(self, rhs: SMatrix<C, K, T>) -> SMatrix<R, K, T> {
+        let mut out = SMatrix::<R, K, T>::zeros();
+        for r in 0..R {
+            for k in 0..K {
+                out.data[r][k] = (0..C).map(|c| self.data[r][c] * rhs.data[c][k]).sum();
+            }
+        }
+        out
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> Add for SMatrix<R, C, T> {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self {
+        for (a, &b) in self.data.iter_mut().flatten().zip(rhs.data.iter().flatten()) { *a += b; }
+        self
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> Sub for SMatrix<R, C, T> {
+    type Output = Self;
+    fn sub(mut self, rhs: Self) -> Self {
+        for (a, &b) in self.data.iter_mut().flatten().zip(rhs.data.iter().flatten()) { *a -= b; }
+        self
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> Neg for SMatrix<R, C, T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.scale(-T::ONE)
+    }
+}
+
+impl<const R: usize, const C: usize, T> Index<(usize, usize)> for SMatrix<R, C, T> {
+    type Output = T;
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        &self.data[r][c]
+    }
+}
+
+impl<const R: usize, const C: usize, T> IndexMut<(usize, usize)> for SMatrix<R, C, T> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
+        &mut self.data[r][c]
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> From<SMatrix<R, C, T>> for Matrix<T> {
+    fn from(m: SMatrix<R, C, T>) -> Self {
+        Matrix::from_vec(R, C, m.data.iter().flatten().copied().collect())
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> TryFrom<&Matrix<T>> for SMatrix<R, C, T> {
+    /// `left` is the dynamic matrix's actual shape, `right` the (R, C) wanted
+    type Error = ShapeError;
+    fn try_from(m: &Matrix<T>) -> Result<Self, ShapeError> {
+        if (m.rows, m.cols) != (R, C) {
+            return Err(ShapeError { op: "convert between", left: (m.rows, m.cols), right: (R, C) });
+        }
+        let mut out = Self::zeros();
+        for r in 0..R {
+            for c in 0..C { out.data[r][c] = m[(r, c)]; }
+        }
+        Ok(out)
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar + fmt::Display> fmt::Display for SMatrix<R, C, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Matrix::from(*self), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Complex64;
+
+    #[test]
+    fn product_dimensions_are_checked_by_the_type_system() {
+        let a = SMatrix::<2, 3>::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let b = SMatrix::<3, 2>::new([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+        let c: SMatrix<2, 2> = a * b;
+        assert_eq!(c, Matrix2::new([[58.0, 64.0], [139.0, 154.0]]));
+        assert_eq!(a.transpose().transpose(), a);
+        assert_eq!(Matrix::from(c), Matrix::from_vec(2, 2, vec![58.0, 64.0, 139.0, 154.0]));
+    }
+
+    #[test]
+    fn rotations_compose_and_preserve_determinant() {
+        let quarter = Matrix2::rotation(std::f64::consts::FRAC_PI_2);
+        let half = quarter * quarter;
+        let p = half.mul_vec([1.0, 0.0]);
+        assert!((p[0] + 1.0).abs() < 1e-15 && p[1].abs() < 1e-15);
+        assert!((quarter.determinant() - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn homogeneous_transform_moves_points() {
+        let t = Matrix4::rotation_z_translation(std::f64::consts::FRAC_PI_2, [1.0, 2.0, 3.0]);
+        let p = t.mul_vec([1.0, 0.0, 0.0, 1.0]);
+        for (got, want) in p.iter().zip([1.0, 3.0, 3.0, 1.0]) {
+            assert!((got - want).abs() < 1e-15, "{p:?}");
+        }
+        assert_eq!(Matrix4::identity() * t, t);
+    }
+
+    #[test]
+    fn determinant_trace_and_elementwise_ops() {
+        let m = Matrix3::new([[2.0, 0.0, 1.0], [1.0, 3.0, 2.0], [1.0, 1.0, 2.0]]);
+        assert_eq!(m.determinant(), 6.0);
+        assert_eq!(m.trace(), 7.0);
+        assert_eq!(m + m, m.scale(2.0));
+        assert_eq!(m - m, Matrix3::zeros());
+        assert_eq!(-m, m.scale(-1.0));
+    }
+
+    #[test]
+    fn converts_from_dynamic_matrix_only_when_shapes_agree() {
+        let d = Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+        let s = Matrix2::try_from(&d).unwrap();
+        assert_eq!(s[(1, 0)], 3.0);
+        let err = Matrix3::try_from(&d).unwrap_err();
+        assert_eq!((err.left, err.right), ((2, 2), (3, 3)));
+        assert_eq!(err.to_string(), "cannot convert between a 2x2 matrix and a 3x3 matrix");
+    }
+
+    #[test]
+    fn works_over_f32_and_complex() {
+        let m = Matrix2::<f32>::new([[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(m.determinant(), -2.0_f32);
+        let i = Complex64::new(0.0, 1.0);
+        let z = Matrix2::new([[i, Complex64::ZERO], [Complex64::ZERO, i]]);
+        assert_eq!((z * z).trace(), Complex64::new(-2.0, 0.0));
+    }
+}
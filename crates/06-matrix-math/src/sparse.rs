@@ -0,0 +1,244 @@
+//! Sparse matrices in coordinate (COO) and compressed sparse row (CSR) form.
+//!
+//! COO is the easy way to build a matrix - append (row, col, value) triplets
+//! in any order. CSR is the fast way to use one: each row's non-zeros sit
+//! contiguously, so a matrix-vector product touches only the stored entries.
+
+use crate::Matrix;
+
+/// Coordinate-format builder: an unordered list of (row, col, value) triplets.
+/// Duplicate positions are summed when converting to CSR. The fields are
+/// private so that `push`, which checks bounds, is the only way in.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CooMatrix {
+    rows: usize,
+    cols: usize,
+    triplets: Vec<(usize, usize, f64)>,
+}
+
+impl CooMatrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, triplets: Vec::new() }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Stored (row, col, value) entries in insertion order
+    pub fn triplets(&self) -> &[(usize, usize, f64)] {
+        &self.triplets
+    }
+
+    /// Append an entry. Returns false (and stores nothing) if it is out of bounds.
+    pub fn push(&mut self, row: usize, col: usize, value: f64) -> bool {
+        if row >= self.rows || col >= self.cols { return false; }
+        self.triplets.push((row, col, value));
+        true
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix {
+        CsrMatrix::from_triplets(self.rows, self.cols, &self.triplets)
+            .expect("push keeps every triplet in bounds")
+    }
+}
+
+/// Compressed sparse row matrix. Row r's entries are
+/// `values[indptr[r]..indptr[r + 1]]`, in column order given by `indices`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsrMatrix {
+    pub rows: usize,
+    pub cols: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl CsrMatrix {
+    /// Build from (row, col, value) triplets in any order; duplicates are summed.
+    /// Returns None if any triplet lies outside rows × cols, or rows + 1 overflows.
+    pub fn from_triplets(rows: usize, cols: usize, triplets: &[(usize, usize, f64)]) -> Option<Self> {
+        if triplets.iter().any(|&(r, c, _)| r >= rows || c >= cols) { return None; }
+        let mut sorted = triplets.to_vec();
+        sorted.sort_by_key(|&(r, c, _)| (r, c));
+
+        let mut indptr = vec![0; rows.checked_add(1)?];
+        let mut indices: Vec<usize> = Vec::with_capacity(sorted.len());
+        let mut values: Vec<f64> = Vec::with_capacity(sorted.len());
+        let mut last = None;
+        for (r, c, v) in sorted {
+            if last == Some((r, c)) {
+                *values.last_mut().unwrap() += v;
+                continue;
+            }
+            indices.push(c);
+            values.push(v);
+            indptr[r + 1] += 1;
+            last = Some((r, c));
+        }
+        for r in 0..rows { indptr[r + 1] += indptr[r]; }
+        Some(Self { rows, cols, indptr, indices, values })
+    }
+
+    /// Keep every non-zero entry of a dense matrix
+    pub fn from_dense(m: &Matrix) -> Self {
+        let mut triplets = Vec::new();
+        for r in 0..m.rows {
+            for c in 0..m.cols {
+                if m[(r, c)] != 0.0 { triplets.push((r, c, m[(r, c)])); }
+            }
+        }
+        Self::from_triplets(m.rows, m.cols, &triplets).expect("indices come from the matrix shape")
+    }
+
+    pub fn identity(n: usize) -> Self {
+        Self {
+            rows: n,
+            cols: n,
+            indptr: (0..=n).collect(),
+            indices: (0..n).collect(),
+            values: vec![1.0; n],
+        }
+    }
+
+    /// Number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Value at (row, col); zero if not stored. Binary search within the row.
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        let (start, end) = (self.indptr[row], self.indptr[row + 1]);
+        match self.indices[start..end].binary_search(&col) {
+            Ok(i) => self.values[start + i],
+            Err(_) => 0.0,
+        }
+    }
+
+    /// Stored (col, value) pairs of one row
+    pub fn row(&self, row: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.indptr[row]..self.indptr[row + 1];
+        self.indices[range.clone()].iter().copied().zip(self.values[range].iter().copied())
+    }
+
+    /// Sparse matrix × dense vector. Returns None if dimensions are incompatible.
+    pub fn mul_vec(&self, x: &[f64]) -> Option<Vec<f64>> {
+        if x.len() != self.cols { return None; }
+        let mut y = vec![0.0; self.rows];
+        self.mul_vec_into(x, &mut y);
+        Some(y)
+    }
+
+    /// y = A x into a caller-provided buffer - the allocation-free hot path of the solvers
+    pub(crate) fn mul_vec_into(&self, x: &[f64], y: &mut [f64]) {
+        for (r, yr) in y.iter_mut().enumerate() {
+            *yr = self.row(r).map(|(c, v)| v * x[c]).sum();
+        }
+    }
+
+    /// Transpose in O(nnz) by counting entries per column (a CSR → CSC conversion)
+    pub fn transpose(&self) -> Self {
+        let mut indptr = vec![0; self.cols + 1];
+        for &c in &self.indices { indptr[c + 1] += 1; }
+        for c in 0..self.cols { indptr[c + 1] += indptr[c]; }
+
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.nnz()];
+        let mut values = vec![0.0; self.nnz()];
+        for r in 0..self.rows {
+            for (c, v) in self.row(r) {
+                indices[next[c]] = r;
+                values[next[c]] = v;
+                next[c] += 1;
+            }
+        }
+        Self { rows: self.cols, cols: self.rows, indptr, indices, values }
+    }
+
+    pub fn to_dense(&self) -> Matrix {
+        let mut m = Matrix::new(self.rows, self.cols);
+        for r in 0..self.rows {
+            for (c, v) in self.row(r) { m[(r, c)] = v; }
+        }
+        m
+    }
+
+    pub fn to_coo(&self) -> CooMatrix {
+        let mut coo = CooMatrix::new(self.rows, self.cols);
+        for r in 0..self.rows {
+            coo.triplets.extend(self.row(r).map(|(c, v)| (r, c, v)));
+        }
+        coo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> CsrMatrix {
+        // [[4, 0, 1],
+        //  [0, 0, 2],
+        //  [3, 5, 0]]
+        CsrMatrix::from_triplets(3, 3, &[(2, 1, 5.0), (0, 0, 4.0), (1, 2, 2.0), (0, 2, 1.0), (2, 0, 3.0)]).unwrap()
+    }
+
+    #[test]
+    fn triplets_are_sorted_into_rows() {
+        let a = example();
+        assert_eq!(a.nnz(), 5);
+        assert_eq!(a.indptr, vec![0, 2, 3, 5]);
+        assert_eq!(a.indices, vec![0, 2, 2, 0, 1]);
+        assert_eq!(a.get(2, 1), 5.0);
+        assert_eq!(a.get(1, 1), 0.0);
+    }
+
+    #[test]
+    fn duplicates_are_summed_and_out_of_bounds_rejected() {
+        let a = CsrMatrix::from_triplets(2, 2, &[(0, 1, 1.5), (0, 1, 2.5), (1, 0, 1.0)]).unwrap();
+        assert_eq!(a.nnz(), 2);
+        assert_eq!(a.get(0, 1), 4.0);
+        assert!(CsrMatrix::from_triplets(2, 2, &[(2, 0, 1.0)]).is_none());
+
+        let mut coo = CooMatrix::new(2, 2);
+        assert!(coo.push(1, 1, 3.0));
+        assert!(!coo.push(0, 5, 3.0));
+        assert_eq!(coo.triplets(), &[(1, 1, 3.0)]);
+        assert_eq!((coo.rows(), coo.cols()), (2, 2));
+        assert_eq!(coo.to_csr().to_dense(), Matrix::from_vec(2, 2, vec![0.0, 0.0, 0.0, 3.0]));
+    }
+
+    #[test]
+    fn mul_vec_matches_dense() {
+        let a = example();
+        let x = [1.0, -2.0, 0.5];
+        assert_eq!(a.mul_vec(&x).unwrap(), vec![4.5, 1.0, -7.0]);
+        assert!(a.mul_vec(&[1.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn transpose_matches_dense_transpose() {
+        let a = example();
+        let t = a.transpose();
+        for r in 0..3 {
+            for c in 0..3 { assert_eq!(t.get(r, c), a.get(c, r)); }
+        }
+        assert_eq!(t.transpose(), a);
+
+        let wide = CsrMatrix::from_triplets(2, 4, &[(0, 3, 1.0), (1, 0, 2.0)]).unwrap().transpose();
+        assert_eq!((wide.rows, wide.cols), (4, 2));
+        assert_eq!(wide.get(3, 0), 1.0);
+    }
+
+    #[test]
+    fn dense_and_coo_round_trips() {
+        let a = example();
+        assert_eq!(CsrMatrix::from_dense(&a.to_dense()), a);
+        assert_eq!(a.to_coo().to_csr(), a);
+        assert_eq!(CsrMatrix::identity(3).to_dense(), Matrix::identity(3));
+    }
+}
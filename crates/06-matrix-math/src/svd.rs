@@ -0,0 +1,204 @@
+//! Singular value decomposition by one-sided (Hestenes) Jacobi.
+//!
+//! Rather than forming AᵀA - which squares the condition number - the columns
+//! of A are rotated in pairs until they are mutually orthogonal. Their norms
+//! are then the singular values.
+
+use crate::eigen::rotation;
+use crate::Matrix;
+
+const MAX_SWEEPS: usize = 100;
+
+/// Thin SVD A = U diag(σ) Vᵀ of an m×n matrix, with k = min(m, n)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Svd {
+    /// m×k, orthonormal columns (a column is zero where σ = 0)
+    pub u: Matrix,
+    /// σ₁ >= σ₂ >= ... >= σₖ >= 0
+    pub singular_values: Vec<f64>,
+    /// n×k, orthonormal columns
+    pub v: Matrix,
+    /// False if the columns were still being rotated after `MAX_SWEEPS`
+    /// sweeps (as with NaN entries); the factors are then only approximate
+    pub converged: bool,
+}
+
+impl Svd {
+    pub fn new(a: &Matrix) -> Self {
+        if a.rows < a.cols {
+            // Decompose Aᵀ = V Σ Uᵀ and swap the roles
+            let t = Self::new(&transposed(a));
+            return Self { u: t.v, singular_values: t.singular_values, v: t.u, converged: t.converged };
+        }
+        let (m, n) = (a.rows, a.cols);
+        let mut u = a.clone();
+        let mut v = Matrix::identity(n);
+
+        let mut converged = false;
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    let (mut alpha, mut beta, mut gamma) = (0.0, 0.0, 0.0);
+                    for k in 0..m {
+                        alpha += u[(k, p)] * u[(k, p)];
+                        beta += u[(k, q)] * u[(k, q)];
+                        gamma += u[(k, p)] * u[(k, q)];
+                    }
+                    if gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() { continue; }
+                    rotated = true;
+                    // Same 2×2 symmetric problem as eigen-Jacobi, on [[α, γ], [γ, β]]
+                    let (c, s) = rotation(alpha, beta, gamma);
+                    for k in 0..m {
+                        let (ukp, ukq) = (u[(k, p)], u[(k, q)]);
+                        u[(k, p)] = c * ukp - s * ukq;
+                        u[(k, q)] = s * ukp + c * ukq;
+                    }
+                    for k in 0..n {
+                        let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                        v[(k, p)] = c * vkp - s * vkq;
+                        v[(k, q)] = s * vkp + c * vkq;
+                    }
+                }
+            }
+            if !rotated {
+                converged = true;
+                break;
+            }
+        }
+
+        let norms: Vec<f64> = (0..n).map(|c| (0..m).map(|r| u[(r, c)] * u[(r, c)]).sum::<f64>().sqrt()).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| norms[j].total_cmp(&norms[i]));
+
+        let mut su = Matrix::new(m, n);
+        let mut sv = Matrix::new(n, n);
+        for (c, &src) in order.iter().enumerate() {
+            let sigma = norms[src];
+            for r in 0..m { su[(r, c)] = if sigma > 0.0 { u[(r, src)] / sigma } else { 0.0 }; }
+            for r in 0..n { sv[(r, c)] = v[(r, src)]; }
+        }
+        Self { u: su, singular_values: order.iter().map(|&i| norms[i]).collect(), v: sv, converged }
+    }
+
+    /// Best rank-`k` approximation in both the 2-norm and Frobenius norm (Eckart–Young)
+    pub fn low_rank(&self, k: usize) -> Matrix {
+        let k = k.min(self.singular_values.len());
+        let mut out = Matrix::new(self.u.rows, self.v.rows);
+        for (i, &sigma) in self.singular_values.iter().enumerate().take(k) {
+            for r in 0..self.u.rows {
+                let ur = self.u[(r, i)] * sigma;
+                for c in 0..self.v.rows { out[(r, c)] += ur * self.v[(c, i)]; }
+            }
+        }
+        out
+    }
+}
+
+impl Matrix {
+    pub fn svd(&self) -> Svd {
+        Svd::new(self)
+    }
+}
+
+fn transposed(a: &Matrix) -> Matrix {
+    let mut t = Matrix::new(a.cols, a.rows);
+    for r in 0..a.rows {
+        for c in 0..a.cols { t[(c, r)] = a[(r, c)]; }
+    }
+    t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Matrix, b: &Matrix, tol: f64) {
+        assert_eq!((a.rows, a.cols), (b.rows, b.cols));
+        for r in 0..a.rows {
+            for c in 0..a.cols {
+                assert!((a[(r, c)] - b[(r, c)]).abs() <= tol, "[{r},{c}] {} vs {}", a[(r, c)], b[(r, c)]);
+            }
+        }
+    }
+
+    /// Householder reflector I - 2 w wᵀ / wᵀw, an exactly-known orthogonal matrix
+    fn reflector(w: &[f64]) -> Matrix {
+        let n = w.len();
+        let ww: f64 = w.iter().map(|x| x * x).sum();
+        let mut h = Matrix::identity(n);
+        for r in 0..n {
+            for c in 0..n { h[(r, c)] -= 2.0 * w[r] * w[c] / ww; }
+        }
+        h
+    }
+
+    #[test]
+    fn tall_matrix_known_singular_values() {
+        // Reference values from 40-digit arithmetic
+        let a = Matrix::from_vec(4, 3, vec![2.0, 0.0, 1.0, 1.0, 3.0, 0.0, 0.0, 1.0, 4.0, 1.0, 1.0, 1.0]);
+        let svd = a.svd();
+        for (got, want) in svd.singular_values.iter().zip([4.680722868313052, 3.0514902588349828, 1.9440269108956393]) {
+            assert!((got - want).abs() < 1e-14, "{got} vs {want}");
+        }
+        assert_close(&svd.low_rank(3), &a, 1e-13);
+        assert_close(&svd.u.transpose().matmul(&svd.u).unwrap(), &Matrix::identity(3), 1e-14);
+        assert_close(&svd.v.transpose().matmul(&svd.v).unwrap(), &Matrix::identity(3), 1e-14);
+    }
+
+    #[test]
+    fn wide_matrix_swaps_u_and_v() {
+        let a = Matrix::from_vec(2, 3, vec![3.0, 2.0, 2.0, 2.0, 3.0, -2.0]);
+        let svd = a.svd();
+        assert_eq!((svd.u.rows, svd.u.cols, svd.v.rows, svd.v.cols), (2, 2, 3, 2));
+        // Classic example: σ = 5, 3
+        assert!((svd.singular_values[0] - 5.0).abs() < 1e-14);
+        assert!((svd.singular_values[1] - 3.0).abs() < 1e-14);
+        assert_close(&svd.low_rank(2), &a, 1e-14);
+        assert!(svd.converged);
+    }
+
+    #[test]
+    fn nan_entries_report_non_convergence_instead_of_panicking() {
+        for a in [Matrix::from_vec(2, 2, vec![1.0, f64::NAN, 0.0, 1.0]), Matrix::from_vec(2, 3, vec![f64::NAN; 6])] {
+            let svd = a.svd();
+            assert!(!svd.converged);
+            assert_eq!(svd.singular_values.len(), 2);
+        }
+    }
+
+    #[test]
+    fn ill_conditioned_singular_values_spanning_twelve_decades() {
+        let sigma = [1.0, 1e-4, 1e-8, 1e-12];
+        let u = reflector(&[1.0, 2.0, -1.0, 0.5]);
+        let v = reflector(&[0.3, -1.0, 2.0, 1.0]);
+        let mut d = Matrix::new(4, 4);
+        for i in 0..4 { d[(i, i)] = sigma[i]; }
+        let a = u.matmul(&d).unwrap().matmul(&v.transpose()).unwrap();
+        let svd = a.svd();
+        for (got, want) in svd.singular_values.iter().zip(sigma) {
+            // Rounding A itself perturbs σ by ~ε‖A‖, so check absolute error
+            assert!((got - want).abs() < 1e-15, "{got} vs {want}");
+        }
+    }
+
+    #[test]
+    fn rank_deficient_matrix_has_zero_singular_value() {
+        let a = Matrix::from_vec(3, 3, vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 0.0, 1.0]);
+        let svd = a.svd();
+        assert!(svd.singular_values[2] < 1e-15 * svd.singular_values[0]);
+        assert_close(&svd.low_rank(2), &a, 1e-13);
+    }
+
+    #[test]
+    fn low_rank_keeps_dominant_component() {
+        // Rank-one outer product plus a small perturbation
+        let a = Matrix::from_vec(3, 2, vec![1.0, 2.0, 2.0, 4.001, 3.0, 6.0]);
+        let svd = a.svd();
+        let r1 = svd.low_rank(1);
+        let err: f64 = (0..3).flat_map(|r| (0..2).map(move |c| (r, c)))
+            .map(|(r, c)| (a[(r, c)] - r1[(r, c)]).powi(2)).sum::<f64>().sqrt();
+        // Eckart–Young: the Frobenius error equals the discarded singular value
+        assert!((err - svd.singular_values[1]).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,198 @@
+//! Borrowed windows into a `Matrix`: rows, columns and rectangular blocks
+//! that point at the original storage instead of copying it.
+//!
+//! A view is a slice plus a row stride. Row r of the view starts at
+//! `r * stride`, so a block taken from the middle of a wide matrix skips the
+//! columns it does not cover. Rows are contiguous and come back as plain
+//! `&[T]`; columns are one-wide views.
+
+use std::ops::{Index, Range};
+
+use crate::Matrix;
+
+/// Read-only rectangular window into a `Matrix`, cheap to copy
+#[derive(Debug)]
+pub struct MatrixView<'a, T = f64> {
+    pub rows: usize,
+    pub cols: usize,
+    /// Distance in `data` between the starts of consecutive rows
+    stride: usize,
+    data: &'a [T],
+}
+
+// Manual impls: a view is a borrow, so it is Copy whatever T is
+impl<T> Clone for MatrixView<'_, T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T> Copy for MatrixView<'_, T> {}
+
+impl<'a, T> MatrixView<'a, T> {
+    /// Block `rows` × `cols` of the row-major buffer `data` with row length `stride`
+    fn block(data: &'a [T], stride: usize, rows: Range<usize>, cols: Range<usize>) -> Self {
+        let (h, w) = (rows.len(), cols.len());
+        if h == 0 || w == 0 {
+            return Self { rows: h, cols: w, stride: 0, data: &[] };
+        }
+        let start = rows.start * stride + cols.start;
+        Self { rows: h, cols: w, stride, data: &data[start..start + (h - 1) * stride + w] }
+    }
+
+    /// Sub-block of this view. Panics if either range falls outside it,
+    /// the same way slicing a `Vec` out of range does.
+    pub fn view(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'a, T> {
+        assert!(
+            rows.start <= rows.end && rows.end <= self.rows && cols.start <= cols.end && cols.end <= self.cols,
+            "view {rows:?} x {cols:?} out of bounds for a {}x{} matrix", self.rows, self.cols,
+        );
+        Self::block(self.data, self.stride, rows, cols)
+    }
+
+    /// Row `r` as a contiguous slice
+    pub fn row(&self, r: usize) -> &'a [T] {
+        assert!(r < self.rows, "row {r} out of bounds for {} rows", self.rows);
+        &self.data[r * self.stride..][..self.cols]
+    }
+
+    /// Column `c` as an `rows`×1 view
+    pub fn column(&self, c: usize) -> MatrixView<'a, T> {
+        self.view(0..self.rows, c..c + 1)
+    }
+
+    pub fn row_iter(&self) -> impl ExactSizeIterator<Item = &'a [T]> + 'a {
+        let v = *self;
+        (0..v.rows).map(move |r| v.row(r))
+    }
+
+    pub fn column_iter(&self) -> impl ExactSizeIterator<Item = MatrixView<'a, T>> + 'a {
+        let v = *self;
+        (0..v.cols).map(move |c| v.column(c))
+    }
+
+    /// Elements in row-major order; for a column view, top to bottom
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        self.row_iter().flatten()
+    }
+}
+
+impl<T: Copy> MatrixView<'_, T> {
+    /// Copy the viewed elements into an owned matrix
+    pub fn to_matrix(&self) -> Matrix<T> {
+        Matrix { rows: self.rows, cols: self.cols, data: self.iter().copied().collect() }
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatrixView<'_, T> {
+    type Output = T;
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        &self.row(r)[c]
+    }
+}
+
+impl<T> Matrix<T> {
+    /// The whole matrix as a view
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView { rows: self.rows, cols: self.cols, stride: self.cols, data: &self.data }
+    }
+
+    /// Rows `rows`, columns `cols`, without copying: `m.view(1..3, 0..2)`.
+    /// Panics if either range is out of bounds.
+    pub fn view(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'_, T> {
+        self.as_view().view(rows, cols)
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        self.as_view().row(r)
+    }
+
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        assert!(r < self.rows, "row {r} out of bounds for {} rows", self.rows);
+        &mut self.data[r * self.cols..][..self.cols]
+    }
+
+    /// Column `c` as an `rows`×1 view - iterate it with `.iter()`
+    pub fn column(&self, c: usize) -> MatrixView<'_, T> {
+        self.as_view().column(c)
+    }
+
+    pub fn row_iter(&self) -> impl ExactSizeIterator<Item = &[T]> + '_ {
+        self.as_view().row_iter()
+    }
+
+    pub fn column_iter(&self) -> impl ExactSizeIterator<Item = MatrixView<'_, T>> + '_ {
+        self.as_view().column_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Matrix {
+        // Element (r, c) = 10r + c
+        Matrix::from_vec(3, 4, (0..3).flat_map(|r| (0..4).map(move |c| (10 * r + c) as f64)).collect())
+    }
+
+    #[test]
+    fn rows_and_columns_borrow_the_original_storage() {
+        let m = grid();
+        assert_eq!(m.row(1), &[10.0, 11.0, 12.0, 13.0]);
+        assert!(std::ptr::eq(&m.row(2)[0], &m[(2, 0)]));
+        let col: Vec<f64> = m.column(2).iter().copied().collect();
+        assert_eq!(col, vec![2.0, 12.0, 22.0]);
+        assert!(std::ptr::eq(&m.column(3)[(1, 0)], &m[(1, 3)]));
+    }
+
+    #[test]
+    fn sub_matrix_views_nest_and_copy_out() {
+        let m = grid();
+        let block = m.view(1..3, 1..4);
+        assert_eq!((block.rows, block.cols), (2, 3));
+        assert_eq!(block[(1, 0)], 21.0);
+        let inner = block.view(0..2, 1..3);
+        assert_eq!(inner.to_matrix(), Matrix::from_vec(2, 2, vec![12.0, 13.0, 22.0, 23.0]));
+        assert_eq!(m.as_view().to_matrix(), m);
+    }
+
+    #[test]
+    fn iterators_cover_every_row_and_column() {
+        let m = grid();
+        let sums: Vec<f64> = m.row_iter().map(|r| r.iter().sum()).collect();
+        assert_eq!(sums, vec![6.0, 46.0, 86.0]);
+        let col_max: Vec<f64> = m.column_iter().map(|c| c.iter().copied().fold(f64::MIN, f64::max)).collect();
+        assert_eq!(col_max, vec![20.0, 21.0, 22.0, 23.0]);
+        assert_eq!(m.column_iter().len(), 4);
+    }
+
+    #[test]
+    fn row_mut_writes_through() {
+        let mut m = grid();
+        m.row_mut(0).fill(-1.0);
+        assert_eq!(m.row(0), &[-1.0; 4]);
+        assert_eq!(m[(1, 0)], 10.0);
+    }
+
+    #[test]
+    fn empty_views_are_allowed() {
+        let m = grid();
+        let v = m.view(3..3, 0..4);
+        assert_eq!((v.rows, v.cols), (0, 4));
+        assert_eq!(v.row_iter().count(), 0);
+        let thin = m.view(0..2, 4..4);
+        assert_eq!(thin.row(1), &[] as &[f64]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn out_of_range_view_panics() {
+        grid().view(0..4, 0..1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_index_is_checked_against_the_view_not_the_parent() {
+        // (0, 2) of a 2-wide block would land inside the parent's storage
+        let m = grid();
+        let _ = m.view(0..2, 0..2)[(0, 2)];
+    }
+}
@@ -0,0 +1,384 @@
+//! Inference for an OLS fit: how sure can we be of each coefficient?
+//!
+//! Under the classical assumptions - independent errors with constant
+//! variance σ² - the estimate β̂ has covariance σ²(XᵀX)⁻¹, with σ² estimated
+//! by RSS / (n − k) for k coefficients including the intercept. Standard
+//! errors come from its diagonal, and each β̂ⱼ / se(β̂ⱼ) follows Student's t
+//! with n − k degrees of freedom.
+//!
+//! When the error variance changes with x (heteroskedasticity) the classical
+//! standard errors are wrong, usually too small. The White "sandwich"
+//!
+//! ```text
+//!   (XᵀX)⁻¹ ( Σᵢ wᵢ xᵢ xᵢᵀ ) (XᵀX)⁻¹
+//! ```
+//!
+//! stays consistent whatever the variance pattern. HC0–HC3 differ only in the
+//! weights wᵢ built from the residual eᵢ and the leverage hᵢ; HC3 is the
+//! safest choice in small samples.
+
+use std::fmt;
+
+use matrix_math::{Cholesky, Matrix};
+use statistics_core::{f_sf, student_t_quantile, student_t_sf};
+
+use crate::{check_shapes, FitError, LinearModel};
+
+/// How to estimate the covariance of the coefficients
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CovarianceType {
+    /// σ̂²(XᵀX)⁻¹ - assumes constant error variance
+    Classical,
+    /// White's estimator: wᵢ = eᵢ²
+    HC0,
+    /// HC0 scaled by n / (n − k) for the degrees of freedom spent on β̂
+    HC1,
+    /// wᵢ = eᵢ² / (1 − hᵢ)
+    HC2,
+    /// wᵢ = eᵢ² / (1 − hᵢ)² - approximately the jackknife
+    HC3,
+}
+
+impl fmt::Display for CovarianceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CovarianceType::Classical => write!(f, "classical"),
+            CovarianceType::HC0       => write!(f, "HC0"),
+            CovarianceType::HC1       => write!(f, "HC1"),
+            CovarianceType::HC2       => write!(f, "HC2"),
+            CovarianceType::HC3       => write!(f, "HC3"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InferenceConfig {
+    pub covariance: CovarianceType,
+    /// Coverage of the confidence intervals, in (0, 1)
+    pub confidence: f64,
+}
+
+impl Default for InferenceConfig {
+    fn default() -> Self {
+        Self { covariance: CovarianceType::Classical, confidence: 0.95 }
+    }
+}
+
+/// One row of the coefficient table
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoefficientStats {
+    pub estimate: f64,
+    pub std_error: f64,
+    /// estimate / std_error, the statistic for H₀: coefficient = 0
+    pub t_statistic: f64,
+    /// Two-sided p-value of `t_statistic` on `df_residual` degrees of freedom
+    pub p_value: f64,
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+/// Everything a regression summary table reports. `Display` prints it.
+#[derive(Clone, Debug)]
+pub struct Inference {
+    /// Row labels for the table: "const", then "x1", "x2", ... until renamed by `with_names`
+    pub names: Vec<String>,
+    /// Intercept first, in the order of `LinearModel::coefficients`
+    pub coefficients: Vec<CoefficientStats>,
+    pub covariance: CovarianceType,
+    pub confidence: f64,
+    pub n: usize,
+    /// Number of slopes tested by the F-statistic (k − 1)
+    pub df_model: usize,
+    /// n − k
+    pub df_residual: usize,
+    pub r_squared: f64,
+    /// 1 − (1 − R²)(n − 1)/(n − k): only rises when a feature earns its place
+    pub adj_r_squared: f64,
+    /// Wald F for H₀: every slope is zero, using the chosen covariance.
+    /// With `Classical` it is the textbook (ESS / (k − 1)) / (RSS / (n − k)).
+    /// NaN for an intercept-only model.
+    pub f_statistic: f64,
+    pub f_p_value: f64,
+    /// Residual standard error √(RSS / (n − k))
+    pub sigma: f64,
+    /// Gaussian log-likelihood at the maximum-likelihood σ² = RSS / n
+    pub log_likelihood: f64,
+    /// −2 ln L + 2k
+    pub aic: f64,
+    /// −2 ln L + k ln n
+    pub bic: f64,
+}
+
+/// Standard errors, tests and information criteria for an OLS `model`
+/// fitted to `x` and `y`. Needs more observations than coefficients, so that
+/// there are residual degrees of freedom left to estimate σ² from, and
+/// residuals that are not all zero: an exact fit has σ̂ = 0, infinite t
+/// statistics and an unbounded likelihood, so it is reported as
+/// `FitError::PerfectFit` rather than as a table of infinities.
+pub fn inference(x: &[Vec<f64>], y: &[f64], model: &LinearModel, config: &InferenceConfig) -> Result<Inference, FitError> {
+    let (n, p) = check_shapes(x, y)?;
+    let k = p + 1;
+    if model.coefficients.len() != k || model.residuals.len() != n { return Err(FitError::DimensionMismatch); }
+    if !(config.confidence > 0.0 && config.confidence < 1.0) {
+        return Err(FitError::InvalidParameter("confidence must be in (0, 1)"));
+    }
+    if n <= k { return Err(FitError::InsufficientData { needed: k + 1, got: n }); }
+
+    let design = |i: usize, j: usize| if j == 0 { 1.0 } else { x[i][j - 1] };
+    let mut gram = Matrix::new(k, k);
+    for i in 0..n {
+        for a in 0..k {
+            for b in 0..=a { gram[(a, b)] += design(i, a) * design(i, b); }
+        }
+    }
+    for a in 0..k {
+        for b in 0..a { gram[(b, a)] = gram[(a, b)]; }
+    }
+    let bread = Cholesky::new(&gram).ok_or(FitError::SingularMatrix)?.inverse();
+
+    let e = &model.residuals;
+    let rss: f64 = e.iter().map(|r| r * r).sum();
+    // Residuals at the level of rounding in y count as zero
+    let y_scale = y.iter().fold(0.0, |m: f64, v| m.max(v.abs()));
+    if rss <= n as f64 * (16.0 * f64::EPSILON * y_scale).powi(2) { return Err(FitError::PerfectFit); }
+    let df_residual = n - k;
+    let cov = match config.covariance {
+        CovarianceType::Classical => &bread * (rss / df_residual as f64),
+        robust => {
+            let mut meat = Matrix::new(k, k);
+            for (i, ei) in e.iter().enumerate() {
+                let h = (0..k).map(|a| (0..k).map(|b| design(i, a) * bread[(a, b)] * design(i, b)).sum::<f64>()).sum::<f64>();
+                let corrected = matches!(robust, CovarianceType::HC2 | CovarianceType::HC3);
+                if corrected && 1.0 - h <= f64::EPSILON.sqrt() { return Err(FitError::FullLeverage { row: i }); }
+                let w = match robust {
+                    CovarianceType::HC2 => ei * ei / (1.0 - h),
+                    CovarianceType::HC3 => ei * ei / ((1.0 - h) * (1.0 - h)),
+                    _ => ei * ei,
+                };
+                for a in 0..k {
+                    for b in 0..k { meat[(a, b)] += w * design(i, a) * design(i, b); }
+                }
+            }
+            let sandwich = bread.matmul(&meat).and_then(|bm| bm.matmul(&bread)).expect("all three are k×k");
+            if robust == CovarianceType::HC1 { sandwich * (n as f64 / df_residual as f64) } else { sandwich }
+        }
+    };
+
+    let df = df_residual as f64;
+    let critical = student_t_quantile(0.5 + 0.5 * config.confidence, df)?;
+    let coefficients = model.coefficients.iter().enumerate().map(|(j, &estimate)| {
+        let std_error = cov[(j, j)].max(0.0).sqrt();
+        let t_statistic = estimate / std_error;
+        Ok(CoefficientStats {
+            estimate,
+            std_error,
+            t_statistic,
+            p_value: 2.0 * student_t_sf(t_statistic.abs(), df)?,
+            ci_low: estimate - critical * std_error,
+            ci_high: estimate + critical * std_error,
+        })
+    }).collect::<Result<Vec<_>, FitError>>()?;
+
+    let (f_statistic, f_p_value) = wald_f(&model.coefficients[1..], &cov, df)?;
+    let nf = n as f64;
+    let y_mean = y.iter().sum::<f64>() / nf;
+    let ss_tot: f64 = y.iter().map(|v| (v - y_mean).powi(2)).sum();
+    let r_squared = if ss_tot == 0.0 { 1.0 } else { 1.0 - rss / ss_tot };
+    let log_likelihood = -0.5 * nf * ((2.0 * std::f64::consts::PI).ln() + (rss / nf).ln() + 1.0);
+
+    Ok(Inference {
+        names: std::iter::once("const".to_string()).chain((1..k).map(|j| format!("x{j}"))).collect(),
+        coefficients,
+        covariance: config.covariance,
+        confidence: config.confidence,
+        n,
+        df_model: p,
+        df_residual,
+        r_squared,
+        adj_r_squared: 1.0 - (1.0 - r_squared) * (nf - 1.0) / df,
+        f_statistic,
+        f_p_value,
+        sigma: (rss / df).sqrt(),
+        log_likelihood,
+        aic: -2.0 * log_likelihood + 2.0 * k as f64,
+        bic: -2.0 * log_likelihood + k as f64 * nf.ln(),
+    })
+}
+
+impl Inference {
+    /// Label the slopes for the summary table; the intercept stays "const".
+    /// Extra names are ignored and missing ones keep their default.
+    pub fn with_names<S: AsRef<str>>(mut self, features: &[S]) -> Self {
+        for (label, name) in self.names[1..].iter_mut().zip(features) {
+            *label = name.as_ref().to_string();
+        }
+        self
+    }
+}
+
+/// F = β̂ₛᵀ Vₛ⁻¹ β̂ₛ / q over the q slopes, with Vₛ their block of the covariance
+fn wald_f(slopes: &[f64], cov: &Matrix, df_residual: f64) -> Result<(f64, f64), FitError> {
+    let q = slopes.len();
+    if q == 0 { return Ok((f64::NAN, f64::NAN)); }
+    let mut block = Matrix::new(q, q);
+    for a in 0..q {
+        for b in 0..q { block[(a, b)] = cov[(a + 1, b + 1)]; }
+    }
+    let solved = Cholesky::new(&block).and_then(|c| c.solve(slopes)).ok_or(FitError::SingularMatrix)?;
+    let f = slopes.iter().zip(&solved).map(|(b, s)| b * s).sum::<f64>() / q as f64;
+    Ok((f, f_sf(f, q as f64, df_residual)?))
+}
+
+/// p-values below 0.001 in scientific notation, the rest to three places
+fn p_value(p: f64) -> String {
+    if p < 1e-3 { format!("{p:.2e}") } else { format!("{p:.3}") }
+}
+
+impl fmt::Display for Inference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "OLS regression: {} observations, {} residual df, {} standard errors", self.n, self.df_residual, self.covariance)?;
+        let f_label = format!("F({}, {})", self.df_model, self.df_residual);
+        writeln!(f, "  {:<10} {:>10.4}    {:<10} {:>10.4}", "R²", self.r_squared, "Adj. R²", self.adj_r_squared)?;
+        writeln!(f, "  {:<10} {:>10.3}    {:<10} {:>10}", f_label, self.f_statistic, "Prob > F", p_value(self.f_p_value))?;
+        writeln!(f, "  {:<10} {:>10.3}    {:<10} {:>10.4}", "Log-lik.", self.log_likelihood, "Resid. SE", self.sigma)?;
+        writeln!(f, "  {:<10} {:>10.3}    {:<10} {:>10.3}", "AIC", self.aic, "BIC", self.bic)?;
+        writeln!(f)?;
+
+        let name_w = self.names.iter().map(|s| s.chars().count()).max().unwrap_or(0).max(5);
+        let quantile = |q: f64| format!("{q:.4}").trim_end_matches('0').trim_end_matches('.').to_string();
+        let lower = format!("[{}", quantile((1.0 - self.confidence) / 2.0));
+        let upper = format!("{}]", quantile((1.0 + self.confidence) / 2.0));
+        writeln!(f, "  {:<name_w$} {:>12} {:>12} {:>9} {:>9} {:>12} {:>12}", "", "coef", "std err", "t", "P>|t|", lower, upper)?;
+        for (name, c) in self.names.iter().zip(&self.coefficients) {
+            let pad = name_w - name.chars().count();
+            writeln!(f, "  {name}{:pad$} {:>12.4} {:>12.4} {:>9.3} {:>9} {:>12.4} {:>12.4}",
+                "", c.estimate, c.std_error, c.t_statistic, p_value(c.p_value), c.ci_low, c.ci_high)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_from_coefficients;
+
+    // Reference values throughout from 40-digit arithmetic
+    fn data() -> (Vec<Vec<f64>>, Vec<f64>, LinearModel) {
+        let x = vec![
+            vec![1.0, 3.5], vec![2.0, 1.0], vec![3.0, 4.2], vec![4.0, 2.8], vec![5.0, 6.1],
+            vec![6.0, 4.9], vec![7.0, 8.3], vec![8.0, 6.0], vec![9.0, 9.4], vec![10.0, 7.7],
+        ];
+        let y = vec![4.1, 3.9, 7.8, 6.2, 11.5, 9.8, 15.9, 12.1, 17.6, 14.8];
+        let model = model_from_coefficients(&x, &y, vec![0.5454515031819176, 0.49795493197278917, 1.3146189927583938]);
+        (x, y, model)
+    }
+
+    fn close(a: f64, b: f64, rel: f64) -> bool {
+        (a - b).abs() <= rel * b.abs()
+    }
+
+    fn with(covariance: CovarianceType) -> Inference {
+        let (x, y, model) = data();
+        inference(&x, &y, &model, &InferenceConfig { covariance, ..Default::default() }).unwrap()
+    }
+
+    #[test]
+    fn classical_standard_errors_and_tests() {
+        let inf = with(CovarianceType::Classical);
+        let se = [0.67268968045399, 0.18346465117166794, 0.2116859966772935];
+        for (c, want) in inf.coefficients.iter().zip(se) {
+            assert!(close(c.std_error, want, 1e-10), "{} vs {want}", c.std_error);
+        }
+        let slope = &inf.coefficients[1];
+        assert!(close(slope.t_statistic, 2.714173704812774, 1e-10));
+        assert!(close(slope.p_value, 0.0300173227064946, 1e-9));
+        // t_{0.975, 7} = 2.3646242515927853
+        assert!(close(slope.ci_high - slope.estimate, 2.3646242515927853 * se[1], 1e-10));
+        assert!(close(slope.estimate - slope.ci_low, slope.ci_high - slope.estimate, 1e-12));
+        assert_eq!((inf.n, inf.df_model, inf.df_residual), (10, 2, 7));
+    }
+
+    #[test]
+    fn goodness_of_fit_and_information_criteria() {
+        let inf = with(CovarianceType::Classical);
+        assert!(close(inf.r_squared, 0.9738766977841012, 1e-12));
+        assert!(close(inf.adj_r_squared, 0.9664128971509873, 1e-12));
+        assert!(close(inf.f_statistic, 130.47999881767961, 1e-9));
+        assert!(close(inf.f_p_value, 2.8813649008964186e-06, 1e-8));
+        assert!(close(inf.sigma, 0.889978794739721, 1e-12));
+        assert!(close(inf.log_likelihood, -11.24043418560363, 1e-12));
+        assert!(close(inf.aic, 28.48086837120726, 1e-12));
+        assert!(close(inf.bic, 29.3886236501894, 1e-12));
+    }
+
+    #[test]
+    fn robust_standard_errors_match_reference() {
+        let cases = [
+            (CovarianceType::HC0, [0.6951148177753423, 0.20625359928357445, 0.20072108265244296], 150.28491068053356),
+            (CovarianceType::HC1, [0.830821116977353, 0.24652020264191998, 0.23990758048277327], 105.19943747637348),
+            (CovarianceType::HC2, [0.9286120634282911, 0.27429397767427005, 0.26167505411604575], 88.66511216526197),
+            (CovarianceType::HC3, [1.2421902315709645, 0.3659765054353272, 0.34294866395015694], 52.04520074745102),
+        ];
+        for (kind, se, f) in cases {
+            let inf = with(kind);
+            for (c, want) in inf.coefficients.iter().zip(se) {
+                assert!(close(c.std_error, want, 1e-9), "{kind}: {} vs {want}", c.std_error);
+            }
+            assert!(close(inf.f_statistic, f, 1e-9), "{kind}: F {} vs {f}", inf.f_statistic);
+        }
+    }
+
+    #[test]
+    fn too_few_observations_is_an_error() {
+        let x = vec![vec![1.0], vec![2.0]];
+        let y = vec![1.0, 3.0];
+        let model = model_from_coefficients(&x, &y, vec![-1.0, 2.0]);
+        let err = inference(&x, &y, &model, &InferenceConfig::default()).unwrap_err();
+        assert_eq!(err, FitError::InsufficientData { needed: 3, got: 2 });
+    }
+
+    #[test]
+    fn an_exact_fit_is_reported_not_divided_by() {
+        let x: Vec<Vec<f64>> = (0..6).map(|i| vec![i as f64]).collect();
+        let y: Vec<f64> = x.iter().map(|r| 1.0 + 2.0 * r[0]).collect();
+        let model = crate::fit(&x, &y).unwrap();
+        for covariance in [CovarianceType::Classical, CovarianceType::HC3] {
+            let config = InferenceConfig { covariance, ..Default::default() };
+            assert_eq!(inference(&x, &y, &model, &config).unwrap_err(), FitError::PerfectFit);
+        }
+    }
+
+    #[test]
+    fn hc2_and_hc3_refuse_a_point_with_leverage_one() {
+        // The second feature is an indicator for row 3 alone, so the fit must pass through it
+        let x: Vec<Vec<f64>> = (0..6).map(|i| vec![i as f64, if i == 3 { 1.0 } else { 0.0 }]).collect();
+        let y = vec![1.0, 2.5, 2.9, 7.0, 5.2, 5.8];
+        let model = crate::fit(&x, &y).unwrap();
+        for covariance in [CovarianceType::HC2, CovarianceType::HC3] {
+            let config = InferenceConfig { covariance, ..Default::default() };
+            assert_eq!(inference(&x, &y, &model, &config).unwrap_err(), FitError::FullLeverage { row: 3 });
+        }
+        let config = InferenceConfig { covariance: CovarianceType::HC0, ..Default::default() };
+        assert!(inference(&x, &y, &model, &config).is_ok());
+    }
+
+    #[test]
+    fn confidence_level_is_validated() {
+        let (x, y, model) = data();
+        for confidence in [0.0, 1.0, f64::NAN] {
+            let config = InferenceConfig { confidence, ..Default::default() };
+            assert!(matches!(inference(&x, &y, &model, &config), Err(FitError::InvalidParameter(_))));
+        }
+    }
+
+    #[test]
+    fn summary_table_lists_every_coefficient() {
+        let table = with(CovarianceType::HC3).with_names(&["temperature", "price"]).to_string();
+        assert!(table.contains("HC3 standard errors"));
+        for label in ["const", "temperature", "price", "[0.025", "0.975]", "F(2, 7)"] {
+            assert!(table.contains(label), "missing {label}:\n{table}");
+        }
+        assert_eq!(table.lines().filter(|l| l.starts_with("  const") || l.starts_with("  temperature")).count(), 2);
+    }
+}
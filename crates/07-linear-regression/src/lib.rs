@@ -1,24 +1,51 @@
 // ============================================================
 //  YOUR CHALLENGE - implement Ordinary Least Squares regression.
 //
-//  OLS via normal equations: beta = (X'X)^{-1} X'y
+//  OLS as a least-squares solve: minimise |X beta - y|^2
 //  where X is the design matrix with a bias column of 1s prepended.
 //
-//  Steps:
-//    1. Build design matrix: prepend a column of 1.0 to x
-//    2. Compute X'X and X'y
-//    3. Invert X'X (use matrix_math::Matrix)
-//    4. Compute beta = (X'X)^{-1} * X'y
-//    5. Compute residuals and R^2
+//  Implement:
+//    - predict: beta[0] + x . beta[1..]
 //
-//  Use matrix_math::Matrix for all matrix operations.
+//  fit is provided: it is fit_with(.., Solver::Qr), which
+//    1. builds the design matrix, prepending a column of 1.0 to x
+//    2. factors X = QR (Matrix::least_squares does Householder QR
+//       and back substitution on R beta = Q'y)
+//    3. returns FitError::SingularMatrix if X is rank deficient
+//    4. computes residuals and R^2
+//  Work through those steps yourself, then compare with solver.rs.
+//
+//  Don't form X'X: that squares the condition number. See
+//  solver.rs for the trade-offs and the SVD alternative.
 //  coefficients[0] is the intercept, coefficients[1..] are slopes.
 // ============================================================
 
-use matrix_math::Matrix;
-use statistics_core::{mean, StatsError};
+mod glm;
+mod inference;
+mod preprocessing;
+mod recursive;
+mod regularised;
+mod robust;
+mod solver;
+mod validation;
+
+use statistics_core::StatsError;
+
+pub use glm::{fit_glm, Family, GlmModel, Irls};
+pub use inference::{inference, CoefficientStats, CovarianceType, Inference, InferenceConfig};
+pub use preprocessing::{
+    FittedPipeline, MinMaxScaler, OneHotEncoder, Pipeline, PolynomialFeatures, StandardScaler, Transformer,
+};
+pub use recursive::{RecursiveLeastSquares, RlsConfig};
+pub use regularised::{elastic_net, elastic_net_path, lambda_grid, lambda_max, lasso, ridge, CoordinateDescent, PathPoint};
+pub use robust::{m_estimate, ransac, weighted_least_squares, Loss, Ransac, RansacFit, RobustFit};
+pub use solver::{fit_with, Solver};
+pub use validation::{
+    cross_validate, grid_search, k_fold, mae, mape, r_squared, rmse, time_series_split, train_test_split, CvScores, Fold,
+    FoldData, GridSearch, Metric, Regressor,
+};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct LinearModel {
     /// Fitted coefficients: beta[0] = intercept, beta[1..] = feature weights
     pub coefficients: Vec<f64>,
@@ -26,16 +53,16 @@ pub struct LinearModel {
     pub residuals: Vec<f64>,
 }
 
-/// Fit OLS via normal equations: beta = (X'X)^{-1}X'y
+/// Fit OLS by QR least squares: beta minimises |X beta - y|^2
 /// `x` is a 2D slice in row-major order (n_samples x n_features, no bias column).
 /// A bias column of 1s is prepended automatically.
 pub fn fit(x: &[Vec<f64>], y: &[f64]) -> Result<LinearModel, FitError> {
-    todo!()
+    fit_with(x, y, Solver::Qr)
 }
 
 /// Predict using a fitted model
 pub fn predict(model: &LinearModel, x: &[f64]) -> f64 {
-    todo!()
+    model.coefficients[0] + x.iter().zip(&model.coefficients[1..]).map(|(a, b)| a * b).sum::<f64>()
 }
 
 #[derive(Debug, PartialEq)]
@@ -43,6 +70,25 @@ pub enum FitError {
     DimensionMismatch,
     SingularMatrix,
     EmptyData,
+    /// A tuning parameter is out of range; names the rule it broke
+    InvalidParameter(&'static str),
+    /// An iterative solver hit its sweep limit before meeting its tolerance
+    NotConverged { sweeps: usize },
+    /// Too few observations for the number of coefficients
+    InsufficientData { needed: usize, got: usize },
+    /// A hyperplane separates the 0s from the 1s, so the logistic fit diverges
+    PerfectSeparation,
+    /// A transformer was applied before it learnt its parameters
+    NotFitted,
+    /// An observation is NaN or infinite
+    NonFinite,
+    /// The residuals are all zero, leaving no error variance to test against
+    PerfectFit,
+    /// Observation `row` has leverage 1: the fit passes through it whatever
+    /// its y, so HC2 and HC3 cannot scale its residual by 1 / (1 − h)
+    FullLeverage { row: usize },
+    /// A one-hot encoded column holds a value that was not seen during fit
+    UnknownCategory { column: usize, value: f64 },
 }
 
 impl From<StatsError> for FitError {
@@ -52,13 +98,50 @@ impl From<StatsError> for FitError {
 impl std::fmt::Display for FitError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            FitError::DimensionMismatch => write!(f, "X rows must equal y length"),
-            FitError::SingularMatrix    => write!(f, "design matrix is singular (linearly dependent features)"),
-            FitError::EmptyData         => write!(f, "empty data"),
+            FitError::DimensionMismatch       => write!(f, "X rows must equal y length"),
+            FitError::SingularMatrix          => write!(f, "design matrix is singular (linearly dependent features)"),
+            FitError::EmptyData               => write!(f, "empty data"),
+            FitError::InvalidParameter(rule)  => write!(f, "invalid parameter: {rule}"),
+            FitError::NotConverged { sweeps } => write!(f, "did not converge within {sweeps} sweeps"),
+            FitError::InsufficientData { needed, got } => write!(f, "need at least {needed} observations, got {got}"),
+            FitError::PerfectSeparation       => write!(f, "classes are perfectly separated; coefficients diverge"),
+            FitError::NotFitted               => write!(f, "transformer used before fit"),
+            FitError::NonFinite               => write!(f, "observations must be finite"),
+            FitError::PerfectFit              => write!(f, "residuals are all zero; there is no error variance to infer from"),
+            FitError::FullLeverage { row }    => write!(f, "observation {row} has leverage 1; HC2 and HC3 are undefined"),
+            FitError::UnknownCategory { column, value } => write!(f, "column {column}: category {value} was not seen during fit"),
         }
     }
 }
 
+impl std::error::Error for FitError {}
+
+/// Shape checks shared by the fitting routines: every row of `x` has the same
+/// number of features and there is one `y` per row. Returns (samples, features).
+pub(crate) fn check_shapes(x: &[Vec<f64>], y: &[f64]) -> Result<(usize, usize), FitError> {
+    if x.is_empty() || y.is_empty() { return Err(FitError::EmptyData); }
+    let p = x[0].len();
+    if x.len() != y.len() || x.iter().any(|row| row.len() != p) { return Err(FitError::DimensionMismatch); }
+    Ok((x.len(), p))
+}
+
+/// Refuse NaN or infinite observations before a fit starts, where they would
+/// otherwise surface as NaN coefficients or a panic in a later median.
+pub(crate) fn check_finite(x: &[Vec<f64>], y: &[f64]) -> Result<(), FitError> {
+    if y.iter().chain(x.iter().flatten()).all(|v| v.is_finite()) { Ok(()) } else { Err(FitError::NonFinite) }
+}
+
+/// Residuals and R² for `coefficients` (intercept first) on the training data
+pub(crate) fn model_from_coefficients(x: &[Vec<f64>], y: &[f64], coefficients: Vec<f64>) -> LinearModel {
+    let residuals: Vec<f64> = x.iter().zip(y)
+        .map(|(row, yi)| yi - coefficients[0] - row.iter().zip(&coefficients[1..]).map(|(a, b)| a * b).sum::<f64>())
+        .collect();
+    let y_mean = y.iter().sum::<f64>() / y.len() as f64;
+    let ss_tot: f64 = y.iter().map(|v| (v - y_mean).powi(2)).sum();
+    let ss_res: f64 = residuals.iter().map(|r| r * r).sum();
+    LinearModel { coefficients, r_squared: if ss_tot == 0.0 { 1.0 } else { 1.0 - ss_res / ss_tot }, residuals }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,107 @@
-use linear_regression::{fit, predict};
-use rand::SeedableRng;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, ValueEnum};
+use linear_regression::{
+    elastic_net_path, fit, fit_glm, inference, lambda_grid, lambda_max, predict, ridge, CoordinateDescent, CovarianceType,
+    cross_validate, fit_with, grid_search, k_fold, m_estimate, ransac, time_series_split, Family, InferenceConfig, Irls,
+    Loss, Metric, Pipeline, PolynomialFeatures, Ransac, RecursiveLeastSquares, RlsConfig, Solver, StandardScaler,
+};
+use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
-use rand_distr::{Distribution, Normal};
+use rand_distr::{Distribution, Normal, Poisson};
+use statistics_core::{delimiter_for, Table};
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Covariance {
+    Classical,
+    Hc0,
+    Hc1,
+    Hc2,
+    Hc3,
+}
+
+impl From<Covariance> for CovarianceType {
+    fn from(c: Covariance) -> Self {
+        match c {
+            Covariance::Classical => CovarianceType::Classical,
+            Covariance::Hc0       => CovarianceType::HC0,
+            Covariance::Hc1       => CovarianceType::HC1,
+            Covariance::Hc2       => CovarianceType::HC2,
+            Covariance::Hc3       => CovarianceType::HC3,
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "linear-regression", about = "Ordinary least squares with a regression summary table")]
+struct Args {
+    /// CSV/TSV file to regress ('-' for stdin). Runs the demand-forecasting demo when omitted.
+    input: Option<PathBuf>,
+
+    /// Response column, by header name or 0-based index (required with an input file)
+    #[arg(short, long)]
+    target: Option<String>,
+
+    /// Feature columns, by header name or 0-based index (default: every other column)
+    #[arg(short, long, value_delimiter = ',')]
+    features: Vec<String>,
+
+    /// Standard errors: classical, or heteroskedasticity-robust HC0-HC3
+    #[arg(short, long, value_enum, default_value_t = Covariance::Classical)]
+    covariance: Covariance,
+
+    /// Coverage of the confidence intervals
+    #[arg(long, default_value_t = 0.95)]
+    confidence: f64,
+
+    /// Field delimiter: a single character or 'tab' (default: tab for .tsv/.tab files, else ',')
+    #[arg(short, long)]
+    delimiter: Option<String>,
+}
 
 fn main() {
+    let args = Args::parse();
+    let result = match &args.input {
+        Some(path) => run_csv(&args, path),
+        None => { run_demo(&args); Ok(()) }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// Regress the target column on the feature columns, dropping any row with
+/// a missing or non-numeric value in one of them.
+fn run_csv(args: &Args, path: &Path) -> Result<(), Box<dyn Error>> {
+    let delimiter = delimiter_for(args.delimiter.as_deref(), path)?;
+    let table = Table::open(path, delimiter, true)?;
+    let headers = &table.headers;
+
+    let target = table.resolve(args.target.as_deref().ok_or("--target is required with an input file")?)?;
+    let features: Vec<usize> = if args.features.is_empty() {
+        (0..headers.len()).filter(|&c| c != target).collect()
+    } else {
+        args.features.iter().map(|f| table.resolve(f)).collect::<Result<_, _>>()?
+    };
+    if features.is_empty() { return Err("no feature columns".into()); }
+
+    let columns: Vec<usize> = std::iter::once(target).chain(features.iter().copied()).collect();
+    let (rows, skipped) = table.complete_rows(&columns);
+    let y: Vec<f64> = rows.iter().map(|r| r[0]).collect();
+    let x: Vec<Vec<f64>> = rows.into_iter().map(|r| r[1..].to_vec()).collect();
+    if skipped > 0 { eprintln!("warning: skipped {skipped} rows with missing or non-numeric values"); }
+
+    let model = fit(&x, &y)?;
+    let config = InferenceConfig { covariance: args.covariance.into(), confidence: args.confidence };
+    let names: Vec<&str> = features.iter().map(|&c| headers[c].as_str()).collect();
+    println!("Dependent variable: {}\n", headers[target]);
+    print!("{}", inference(&x, &y, &model, &config)?.with_names(&names));
+    Ok(())
+}
+
+fn run_demo(args: &Args) {
     let mut rng = StdRng::seed_from_u64(42);
     let noise = Normal::new(0.0_f64, 50.0).unwrap();
 
@@ -38,4 +136,126 @@ fn main() {
     for (label, feats) in &scenarios {
         println!("    {:<28} → {:.0} units", label, predict(&model, feats));
     }
+
+    let config = InferenceConfig { covariance: args.covariance.into(), confidence: args.confidence };
+    match inference(&x, &y, &model, &config) {
+        Ok(summary) => println!("\n{}", summary.with_names(&["temperature", "price_delta"])),
+        Err(e) => println!("\n  Inference failed: {e}"),
+    }
+
+    // Curvature and interactions without touching the raw rows
+    println!("\n=== Pipeline: degree-2 features, standardised ===\n");
+    let pipeline = Pipeline::new().then(PolynomialFeatures::new(2)).then(StandardScaler::new());
+    let fitted = pipeline.fit_model(&x, &y, fit).expect("pipeline fit should succeed");
+    let names = fitted.feature_names(&["temp".to_string(), "price".to_string()]).expect("two raw features");
+    println!("  Features: {}", names.join(", "));
+    println!("  R² {:.4} (linear {:.4}) - the true model has no curvature to find", fitted.model.r_squared, model.r_squared);
+    println!("  Hot day, no discount → {:.0} units", fitted.predict(&[35.0, 0.0]).expect("two raw features"));
+
+    // Same data plus the temperature in Fahrenheit: perfectly collinear with °C
+    println!("\n=== Collinear features: °C and °F together ===\n");
+    let x_f: Vec<Vec<f64>> = x.iter().map(|r| vec![r[0], r[1], 32.0 + 1.8 * r[0]]).collect();
+    match fit(&x_f, &y) {
+        Ok(_) => println!("  OLS: unexpectedly succeeded"),
+        Err(e) => println!("  OLS:   {e}"),
+    }
+    let r = ridge(&x_f, &y, 0.1).expect("ridge handles collinearity");
+    println!("  Ridge (λ = 0.1): °C {:>6.2}, price {:>6.2}, °F {:>6.2}  - the temperature effect is shared",
+        r.coefficients[1], r.coefficients[2], r.coefficients[3]);
+
+    println!("\n  Lasso path (features enter as λ falls):");
+    let lambdas = lambda_grid(lambda_max(&x_f, &y, 1.0).unwrap(), 8, 1e-3).unwrap();
+    let path = elastic_net_path(&x_f, &y, 1.0, &lambdas, &CoordinateDescent::default()).expect("path converges");
+    println!("    {:>10}  {:>8}  {:>8}  {:>8}  {:>6}  {:>6}", "λ", "°C", "price", "°F", "R²", "sweeps");
+    for point in &path {
+        let c = &point.model.coefficients;
+        println!("    {:>10.4}  {:>8.3}  {:>8.3}  {:>8.3}  {:>6.3}  {:>6}", point.lambda, c[1], c[2], c[3], point.model.r_squared, point.sweeps);
+    }
+
+    // A few corrupted readings: OLS follows them, robust fits do not
+    println!("\n=== Robust fits: 10 of 200 readings corrupted by +3000 ===\n");
+    let y_bad: Vec<f64> = y.iter().enumerate().map(|(i, &v)| if i % 20 == 3 { v + 3000.0 } else { v }).collect();
+    let ols_bad = fit(&x, &y_bad).expect("fit should succeed");
+    let huber = m_estimate(&x, &y_bad, Loss::huber(), &Irls::default()).expect("Huber converges");
+    let tukey = m_estimate(&x, &y_bad, Loss::tukey(), &Irls::default()).expect("Tukey converges");
+    let consensus = ransac(&x, &y_bad, &Ransac::new(150.0)).expect("RANSAC finds a consensus");
+    println!("    {:<8} {:>10} {:>8} {:>8}", "", "intercept", "temp", "price");
+    for (label, c) in [("OLS", &ols_bad.coefficients), ("Huber", &huber.model.coefficients),
+                       ("Tukey", &tukey.model.coefficients), ("RANSAC", &consensus.model.coefficients)] {
+        println!("    {:<8} {:>10.1} {:>8.2} {:>8.2}", label, c[0], c[1], c[2]);
+    }
+    let rejected = tukey.weights.iter().filter(|&&w| w == 0.0).count();
+    let outliers = consensus.inliers.iter().filter(|&&b| !b).count();
+    println!("    Tukey gave {rejected} rows zero weight; RANSAC flagged {outliers} outliers in {} trials", consensus.trials);
+
+    // Out-of-sample error: the same folds for every candidate
+    println!("\n=== Cross-validation ===\n");
+    let folds = k_fold(x.len(), 5, Some(7)).expect("200 rows split five ways");
+    let ols = cross_validate(&x, &y, &folds, fit, Metric::Rmse).expect("every fold fits");
+    println!("  OLS, 5-fold RMSE:        {:>7.2} ± {:.2}  (noise sd 50)", ols.mean, ols.std_dev);
+    let forward = time_series_split(x.len(), 4, 0).expect("200 rows split four ways");
+    let ts = cross_validate(&x, &y, &forward, fit, Metric::Mape).expect("every fold fits");
+    println!("  OLS, forward-chained MAPE: {:>5.2}%", ts.mean);
+    let lambdas = lambda_grid(lambda_max(&x_f, &y, 1.0).unwrap(), 6, 1e-4).unwrap();
+    let search = grid_search(&x_f, &y, &folds, &lambdas, |&l, x, y| ridge(x, y, l), Metric::Rmse).expect("ridge fits");
+    println!("  Ridge with °F added, RMSE by λ:");
+    for (i, (lambda, scores)) in search.results.iter().enumerate() {
+        println!("    λ = {:>9.4}  {:>7.2}{}", lambda, scores.mean, if i == search.best { "  ← best" } else { "" });
+    }
+
+    // Binary and count targets: the same design matrix, a different link
+    println!("\n=== Conversion (logistic) and incident counts (Poisson) ===\n");
+    let discounts: Vec<Vec<f64>> = (0..400).map(|i| vec![(i % 21) as f64]).collect();
+    let converted: Vec<f64> = discounts.iter().map(|r| {
+        let p = 1.0 / (1.0 + (2.0 - 0.15 * r[0]).exp());
+        if rng.gen::<f64>() < p { 1.0 } else { 0.0 }
+    }).collect();
+    let logit = fit_glm(&discounts, &converted, Family::Binomial, &Irls::default()).expect("logistic fit converges");
+    println!("  Logistic: logit P(convert) = {:.3} + {:.3} × discount%  (true: -2 + 0.15)", logit.coefficients[0], logit.coefficients[1]);
+    println!("    odds ratio per point of discount {:.3}, deviance {:.1} (null {:.1}), {} iterations",
+        logit.coefficients[1].exp(), logit.deviance, logit.null_deviance, logit.iterations);
+    for d in [0.0, 10.0, 20.0] {
+        println!("    P(convert | {d:>2}% off) = {:.3}", logit.predict_proba(&[d]));
+    }
+
+    let load: Vec<Vec<f64>> = (0..200).map(|i| vec![(i % 50) as f64]).collect();
+    let incidents: Vec<f64> = load.iter()
+        .map(|r| Poisson::new((0.5 + 0.03 * r[0]).exp()).unwrap().sample(&mut rng))
+        .collect();
+    let pois = fit_glm(&load, &incidents, Family::Poisson, &Irls::default()).expect("poisson fit converges");
+    println!("\n  Poisson: log E[incidents] = {:.3} + {:.4} × load  (true: 0.5 + 0.03)", pois.coefficients[0], pois.coefficients[1]);
+    println!("    rate ratio per unit load {:.4} ± {:.4}, pseudo-R² {:.3}",
+        pois.coefficients[1].exp(), pois.std_errors[1] * pois.coefficients[1].exp(), pois.pseudo_r_squared());
+    println!("    expected incidents at load 40: {:.2}", pois.predict_proba(&[40.0]));
+
+    // Ill-conditioned design: exact data, so every digit lost is the solver's fault
+    println!("\n=== Solvers on a degree-8 polynomial (true coefficients all 1) ===\n");
+    let poly: Vec<Vec<f64>> = (0..50).map(|i| (1..=8).map(|d| (i as f64 / 49.0).powi(d)).collect()).collect();
+    let y_poly: Vec<f64> = poly.iter().map(|r| 1.0 + r.iter().sum::<f64>()).collect();
+    for solver in [Solver::Qr, Solver::Svd, Solver::NormalEquations] {
+        match fit_with(&poly, &y_poly, solver) {
+            Ok(m) => {
+                let worst = m.coefficients.iter().map(|c| (c - 1.0).abs()).fold(0.0, f64::max);
+                println!("    {:<16} worst coefficient error {:.1e}", format!("{solver:?}"), worst);
+            }
+            Err(e) => println!("    {:<16} {e}", format!("{solver:?}")),
+        }
+    }
+
+    // A pressure sensor whose gain drifts: recalibrate on every reading
+    println!("\n=== Streaming calibration: gain drifts from 1.00 to 1.20 ===\n");
+    let mut steady = RecursiveLeastSquares::new(1, &RlsConfig::default()).unwrap();
+    let mut tracking = RecursiveLeastSquares::new(1, &RlsConfig { forgetting: 0.98, ..Default::default() }).unwrap();
+    let sensor_noise = Normal::new(0.0, 0.5).unwrap();
+    println!("    {:>6} {:>10} {:>12} {:>12}", "step", "true gain", "λ = 1", "λ = 0.98");
+    for step in 0..2000 {
+        let gain = 1.0 + 0.2 * step as f64 / 1999.0;
+        let reference = rng.gen_range(0.0..100.0);
+        let reading = 2.0 + gain * reference + sensor_noise.sample(&mut rng);
+        steady.update(&[reference], reading).expect("one feature");
+        tracking.update(&[reference], reading).expect("one feature");
+        if (step + 1) % 400 == 0 {
+            println!("    {:>6} {:>10.3} {:>12.3} {:>12.3}", step + 1, gain, steady.coefficients[1], tracking.coefficients[1]);
+        }
+    }
 }
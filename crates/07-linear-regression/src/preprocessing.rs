@@ -0,0 +1,444 @@
+//! Feature preprocessing: transformers that learn their parameters from
+//! training rows and then apply exactly the same mapping to every later row.
+//!
+//! Learning a scaler's mean on the full data set and then cross-validating
+//! leaks the test rows into training. Fitting each transformer on the
+//! training rows only, and reusing its stored parameters at predict time,
+//! avoids that. `Pipeline` chains transformers with an estimator so the two
+//! can never drift apart.
+
+use std::fmt;
+
+use crate::{FitError, LinearModel};
+
+/// A learnt feature mapping, rows in and rows out
+pub trait Transformer: fmt::Debug {
+    /// Learn the parameters from the training rows, replacing any learnt before
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), FitError>;
+
+    /// Map one row with the learnt parameters
+    fn transform_row(&self, row: &[f64]) -> Result<Vec<f64>, FitError>;
+
+    /// Names of the output columns, given the names of the input ones
+    fn feature_names(&self, input: &[String]) -> Result<Vec<String>, FitError> {
+        Ok(input.to_vec())
+    }
+
+    fn transform(&self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, FitError> {
+        x.iter().map(|row| self.transform_row(row)).collect()
+    }
+
+    fn fit_transform(&mut self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, FitError> {
+        self.fit(x)?;
+        self.transform(x)
+    }
+}
+
+/// Number of features, checking the rows are non-empty and rectangular
+fn check_rows(x: &[Vec<f64>]) -> Result<usize, FitError> {
+    let p = x.first().ok_or(FitError::EmptyData)?.len();
+    if x.iter().any(|row| row.len() != p) { return Err(FitError::DimensionMismatch); }
+    Ok(p)
+}
+
+/// Rows must match the width seen by `fit`; an unfitted transformer has none
+fn check_width(width: Option<usize>, row: &[f64]) -> Result<(), FitError> {
+    match width {
+        None => Err(FitError::NotFitted),
+        Some(p) if p != row.len() => Err(FitError::DimensionMismatch),
+        Some(_) => Ok(()),
+    }
+}
+
+/// `check_width` for a list of column names
+fn check_names(width: Option<usize>, input: &[String]) -> Result<(), FitError> {
+    match width {
+        None => Err(FitError::NotFitted),
+        Some(p) if p != input.len() => Err(FitError::DimensionMismatch),
+        Some(_) => Ok(()),
+    }
+}
+
+fn column_means(x: &[Vec<f64>], p: usize) -> Vec<f64> {
+    (0..p).map(|j| x.iter().map(|row| row[j]).sum::<f64>() / x.len() as f64).collect()
+}
+
+/// z = (x − mean) / sd per column, with the population standard deviation.
+/// A constant column has nothing to scale and becomes all zeros.
+#[derive(Clone, Debug, Default)]
+pub struct StandardScaler {
+    pub means: Vec<f64>,
+    pub std_devs: Vec<f64>,
+    width: Option<usize>,
+}
+
+impl StandardScaler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Transformer for StandardScaler {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), FitError> {
+        let p = check_rows(x)?;
+        self.means = column_means(x, p);
+        self.std_devs = (0..p)
+            .map(|j| (x.iter().map(|row| (row[j] - self.means[j]).powi(2)).sum::<f64>() / x.len() as f64).sqrt())
+            .collect();
+        self.width = Some(p);
+        Ok(())
+    }
+
+    fn transform_row(&self, row: &[f64]) -> Result<Vec<f64>, FitError> {
+        check_width(self.width, row)?;
+        Ok(row.iter().zip(self.means.iter().zip(&self.std_devs))
+            .map(|(v, (m, s))| if *s == 0.0 { 0.0 } else { (v - m) / s })
+            .collect())
+    }
+}
+
+/// Maps each column's training range linearly onto `range`, [0, 1] by
+/// default. Later rows may fall outside it; they are not clipped.
+#[derive(Clone, Debug)]
+pub struct MinMaxScaler {
+    pub range: (f64, f64),
+    pub mins: Vec<f64>,
+    pub maxs: Vec<f64>,
+    width: Option<usize>,
+}
+
+impl MinMaxScaler {
+    pub fn new() -> Self {
+        Self::with_range(0.0, 1.0)
+    }
+
+    pub fn with_range(low: f64, high: f64) -> Self {
+        Self { range: (low, high), mins: Vec::new(), maxs: Vec::new(), width: None }
+    }
+}
+
+impl Default for MinMaxScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transformer for MinMaxScaler {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), FitError> {
+        let p = check_rows(x)?;
+        if self.range.0.partial_cmp(&self.range.1) != Some(std::cmp::Ordering::Less) {
+            return Err(FitError::InvalidParameter("min-max range must have low < high"));
+        }
+        self.mins = (0..p).map(|j| x.iter().map(|row| row[j]).fold(f64::INFINITY, f64::min)).collect();
+        self.maxs = (0..p).map(|j| x.iter().map(|row| row[j]).fold(f64::NEG_INFINITY, f64::max)).collect();
+        self.width = Some(p);
+        Ok(())
+    }
+
+    fn transform_row(&self, row: &[f64]) -> Result<Vec<f64>, FitError> {
+        check_width(self.width, row)?;
+        let (low, high) = self.range;
+        Ok(row.iter().zip(self.mins.iter().zip(&self.maxs))
+            .map(|(v, (min, max))| if max == min { low } else { low + (v - min) / (max - min) * (high - low) })
+            .collect())
+    }
+}
+
+/// Every product of up to `degree` input columns: for [a, b] at degree 2,
+/// [a, b, a², ab, b²]. No constant column - `fit` adds the intercept.
+/// With `interaction_only` no column is multiplied by itself: [a, b, ab].
+#[derive(Clone, Debug)]
+pub struct PolynomialFeatures {
+    pub degree: usize,
+    pub interaction_only: bool,
+    /// Input column indices multiplied together for each output column
+    terms: Vec<Vec<usize>>,
+    width: Option<usize>,
+}
+
+impl PolynomialFeatures {
+    pub fn new(degree: usize) -> Self {
+        Self { degree, interaction_only: false, terms: Vec::new(), width: None }
+    }
+
+    pub fn interaction_only(degree: usize) -> Self {
+        Self { interaction_only: true, ..Self::new(degree) }
+    }
+}
+
+impl Transformer for PolynomialFeatures {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), FitError> {
+        let p = check_rows(x)?;
+        if self.degree == 0 { return Err(FitError::InvalidParameter("polynomial degree must be at least 1")); }
+        // Grow each term by an index no smaller than its last (strictly larger
+        // for interactions), so every monomial appears once, by degree
+        let mut terms: Vec<Vec<usize>> = (0..p).map(|j| vec![j]).collect();
+        let mut frontier = terms.clone();
+        for _ in 1..self.degree {
+            frontier = frontier.iter()
+                .flat_map(|term| {
+                    let last = term[term.len() - 1];
+                    let first = if self.interaction_only { last + 1 } else { last };
+                    (first..p).map(move |j| term.iter().copied().chain(std::iter::once(j)).collect::<Vec<_>>())
+                })
+                .collect();
+            terms.extend(frontier.iter().cloned());
+        }
+        self.terms = terms;
+        self.width = Some(p);
+        Ok(())
+    }
+
+    fn transform_row(&self, row: &[f64]) -> Result<Vec<f64>, FitError> {
+        check_width(self.width, row)?;
+        Ok(self.terms.iter().map(|term| term.iter().map(|&j| row[j]).product()).collect())
+    }
+
+    fn feature_names(&self, input: &[String]) -> Result<Vec<String>, FitError> {
+        check_names(self.width, input)?;
+        Ok(self.terms.iter().map(|term| {
+            let mut parts: Vec<String> = Vec::new();
+            let mut i = 0;
+            while i < term.len() {
+                let power = term[i..].iter().take_while(|&&j| j == term[i]).count();
+                let name = &input[term[i]];
+                parts.push(if power == 1 { name.clone() } else { format!("{name}^{power}") });
+                i += power;
+            }
+            parts.join(" ")
+        }).collect())
+    }
+}
+
+/// Replaces each categorical column, holding category codes, with one 0/1
+/// indicator per category seen in training. The first category (in sorted
+/// order) is the baseline and gets no indicator unless `drop_first` is off:
+/// a full set of indicators always sums to 1, which makes it collinear with
+/// the intercept and leaves OLS singular. Other columns pass through.
+#[derive(Clone, Debug)]
+pub struct OneHotEncoder {
+    pub columns: Vec<usize>,
+    pub drop_first: bool,
+    /// Sorted distinct values of each categorical column, in `columns` order
+    pub categories: Vec<Vec<f64>>,
+    width: Option<usize>,
+}
+
+impl OneHotEncoder {
+    pub fn new(columns: &[usize]) -> Self {
+        Self { columns: columns.to_vec(), drop_first: true, categories: Vec::new(), width: None }
+    }
+
+    fn levels(&self, c: usize) -> &[f64] {
+        let levels = &self.categories[c];
+        if self.drop_first { &levels[1..] } else { levels }
+    }
+}
+
+impl Transformer for OneHotEncoder {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), FitError> {
+        let p = check_rows(x)?;
+        if self.columns.iter().any(|&c| c >= p) { return Err(FitError::DimensionMismatch); }
+        self.categories = self.columns.iter().map(|&c| {
+            let mut levels: Vec<f64> = x.iter().map(|row| row[c]).collect();
+            levels.sort_by(f64::total_cmp);
+            levels.dedup();
+            levels
+        }).collect();
+        self.width = Some(p);
+        Ok(())
+    }
+
+    /// A category that never appeared in training is an error: it has no
+    /// indicator, and silently coding it as the baseline would be wrong.
+    fn transform_row(&self, row: &[f64]) -> Result<Vec<f64>, FitError> {
+        check_width(self.width, row)?;
+        let mut out = Vec::with_capacity(row.len());
+        for (j, &v) in row.iter().enumerate() {
+            match self.columns.iter().position(|&c| c == j) {
+                None => out.push(v),
+                Some(c) => {
+                    if !self.categories[c].contains(&v) {
+                        return Err(FitError::UnknownCategory { column: j, value: v });
+                    }
+                    out.extend(self.levels(c).iter().map(|&level| if level == v { 1.0 } else { 0.0 }));
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn feature_names(&self, input: &[String]) -> Result<Vec<String>, FitError> {
+        check_names(self.width, input)?;
+        Ok(input.iter().enumerate().flat_map(|(j, name)| match self.columns.iter().position(|&c| c == j) {
+            None => vec![name.clone()],
+            Some(c) => self.levels(c).iter().map(|level| format!("{name}={level}")).collect(),
+        }).collect())
+    }
+}
+
+/// Transformers applied in order, themselves a `Transformer`:
+/// `Pipeline::new().then(OneHotEncoder::new(&[2])).then(StandardScaler::new())`
+#[derive(Debug, Default)]
+pub struct Pipeline {
+    steps: Vec<Box<dyn Transformer>>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn then(mut self, step: impl Transformer + 'static) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    /// Fit every step on the training rows, then the estimator on the
+    /// transformed rows. Any function with `fit`'s signature works:
+    /// `fit` itself, or a closure such as `|x, y| ridge(x, y, 0.1)`.
+    pub fn fit_model<F>(mut self, x: &[Vec<f64>], y: &[f64], estimator: F) -> Result<FittedPipeline, FitError>
+    where
+        F: FnOnce(&[Vec<f64>], &[f64]) -> Result<LinearModel, FitError>,
+    {
+        let transformed = self.fit_transform(x)?;
+        let model = estimator(&transformed, y)?;
+        Ok(FittedPipeline { pipeline: self, model })
+    }
+}
+
+impl Transformer for Pipeline {
+    fn fit(&mut self, x: &[Vec<f64>]) -> Result<(), FitError> {
+        self.fit_transform(x).map(|_| ())
+    }
+
+    fn transform_row(&self, row: &[f64]) -> Result<Vec<f64>, FitError> {
+        self.steps.iter().try_fold(row.to_vec(), |row, step| step.transform_row(&row))
+    }
+
+    fn feature_names(&self, input: &[String]) -> Result<Vec<String>, FitError> {
+        self.steps.iter().try_fold(input.to_vec(), |names, step| step.feature_names(&names))
+    }
+
+    // Each step must fit on the output of the one before
+    fn fit_transform(&mut self, x: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, FitError> {
+        let mut rows = x.to_vec();
+        for step in &mut self.steps {
+            rows = step.fit_transform(&rows)?;
+        }
+        Ok(rows)
+    }
+}
+
+/// A pipeline with its estimator: raw rows in, predictions out
+#[derive(Debug)]
+pub struct FittedPipeline {
+    pub pipeline: Pipeline,
+    /// Coefficients refer to the transformed features - see `feature_names`
+    pub model: LinearModel,
+}
+
+impl FittedPipeline {
+    pub fn predict(&self, row: &[f64]) -> Result<f64, FitError> {
+        let features = self.pipeline.transform_row(row)?;
+        let beta = &self.model.coefficients;
+        if features.len() + 1 != beta.len() { return Err(FitError::DimensionMismatch); }
+        Ok(beta[0] + features.iter().zip(&beta[1..]).map(|(a, b)| a * b).sum::<f64>())
+    }
+
+    pub fn feature_names(&self, input: &[String]) -> Result<Vec<String>, FitError> {
+        self.pipeline.feature_names(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ridge;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn standard_scaler_reuses_training_statistics() {
+        let train = vec![vec![1.0, 5.0], vec![3.0, 5.0], vec![5.0, 5.0]];
+        let mut s = StandardScaler::new();
+        let z = s.fit_transform(&train).unwrap();
+        let sd = (8.0_f64 / 3.0).sqrt();
+        assert_eq!(z[0], vec![-2.0 / sd, 0.0]);
+        assert_eq!(z[1], vec![0.0, 0.0]);
+        // New rows are scaled with the training mean and sd, not their own
+        assert_eq!(s.transform_row(&[7.0, 6.0]).unwrap(), vec![4.0 / sd, 0.0]);
+        assert_eq!(s.transform_row(&[1.0]).unwrap_err(), FitError::DimensionMismatch);
+    }
+
+    #[test]
+    fn min_max_scaler_maps_the_training_range() {
+        let train = vec![vec![2.0, -1.0], vec![4.0, 1.0], vec![6.0, 0.0]];
+        let mut s = MinMaxScaler::with_range(-1.0, 1.0);
+        assert_eq!(s.fit_transform(&train).unwrap(), vec![vec![-1.0, -1.0], vec![0.0, 1.0], vec![1.0, 0.0]]);
+        // Out-of-range values extrapolate rather than clip
+        assert_eq!(s.transform_row(&[8.0, 3.0]).unwrap(), vec![2.0, 3.0]);
+        assert!(matches!(MinMaxScaler::with_range(1.0, 1.0).fit(&train), Err(FitError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn polynomial_features_enumerate_each_monomial_once() {
+        let mut poly = PolynomialFeatures::new(2);
+        assert_eq!(poly.fit_transform(&[vec![2.0, 3.0]]).unwrap(), vec![vec![2.0, 3.0, 4.0, 6.0, 9.0]]);
+        assert_eq!(poly.feature_names(&names(&["a", "b"])).unwrap(), names(&["a", "b", "a^2", "a b", "b^2"]));
+        assert_eq!(poly.feature_names(&names(&["a"])).unwrap_err(), FitError::DimensionMismatch);
+
+        let mut cubic = PolynomialFeatures::new(3);
+        cubic.fit(&[vec![1.0, 1.0, 1.0]]).unwrap();
+        // C(3 + 3, 3) − 1 = 19 monomials of degree 1 to 3 in three variables
+        assert_eq!(cubic.transform_row(&[1.0, 2.0, 3.0]).unwrap().len(), 19);
+
+        let mut inter = PolynomialFeatures::interaction_only(3);
+        inter.fit(&[vec![1.0, 1.0, 1.0]]).unwrap();
+        assert_eq!(inter.transform_row(&[2.0, 3.0, 5.0]).unwrap(), vec![2.0, 3.0, 5.0, 6.0, 10.0, 15.0, 30.0]);
+    }
+
+    #[test]
+    fn one_hot_encoder_drops_the_baseline_category() {
+        // Column 1 holds a region code
+        let train = vec![vec![1.5, 2.0], vec![0.5, 0.0], vec![2.5, 1.0], vec![1.0, 2.0]];
+        let mut enc = OneHotEncoder::new(&[1]);
+        let out = enc.fit_transform(&train).unwrap();
+        assert_eq!(out[0], vec![1.5, 0.0, 1.0]);
+        assert_eq!(out[1], vec![0.5, 0.0, 0.0]);
+        assert_eq!(enc.feature_names(&names(&["price", "region"])).unwrap(), names(&["price", "region=1", "region=2"]));
+        assert_eq!(enc.feature_names(&names(&["price"])).unwrap_err(), FitError::DimensionMismatch);
+        assert_eq!(enc.transform_row(&[1.0, 7.0]).unwrap_err(), FitError::UnknownCategory { column: 1, value: 7.0 });
+
+        let mut full = OneHotEncoder { drop_first: false, ..OneHotEncoder::new(&[1]) };
+        assert_eq!(full.fit_transform(&train).unwrap()[1], vec![0.5, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn unfitted_transformers_refuse_to_transform() {
+        assert_eq!(StandardScaler::new().transform_row(&[1.0]).unwrap_err(), FitError::NotFitted);
+        assert_eq!(PolynomialFeatures::new(2).transform_row(&[1.0]).unwrap_err(), FitError::NotFitted);
+        assert_eq!(OneHotEncoder::new(&[0]).feature_names(&names(&["a"])).unwrap_err(), FitError::NotFitted);
+        assert_eq!(StandardScaler::new().fit(&[]).unwrap_err(), FitError::EmptyData);
+    }
+
+    #[test]
+    fn pipeline_applies_training_parameters_at_predict_time() {
+        // y = 1 + 2x − 0.5x² is exactly quadratic, so a polynomial pipeline recovers it
+        let x: Vec<Vec<f64>> = (0..12).map(|i| vec![i as f64]).collect();
+        let y: Vec<f64> = x.iter().map(|r| 1.0 + 2.0 * r[0] - 0.5 * r[0] * r[0]).collect();
+        let fitted = Pipeline::new()
+            .then(PolynomialFeatures::new(2))
+            .then(StandardScaler::new())
+            .fit_model(&x, &y, |x, y| ridge(x, y, 0.0))
+            .unwrap();
+        for probe in [3.5, 20.0, -4.0] {
+            let want = 1.0 + 2.0 * probe - 0.5 * probe * probe;
+            assert!((fitted.predict(&[probe]).unwrap() - want).abs() < 1e-8 * (1.0 + want.abs()));
+        }
+        assert_eq!(fitted.feature_names(&names(&["x"])).unwrap(), names(&["x", "x^2"]));
+        assert_eq!(fitted.predict(&[1.0, 2.0]).unwrap_err(), FitError::DimensionMismatch);
+    }
+}
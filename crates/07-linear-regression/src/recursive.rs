@@ -0,0 +1,193 @@
+//! Recursive least squares: OLS updated one observation at a time.
+//!
+//! Instead of refitting on the whole history, RLS carries the coefficients β
+//! and P ≈ (XᵀX)⁻¹ forward. Each new row φ = [1, x] costs O(k²):
+//!
+//! ```text
+//!   g = P φ / (λ + φᵀ P φ)        gain
+//!   β ← β + g (y − φᵀ β)           correct by the prediction error
+//!   P ← (P − g φᵀ P) / λ
+//! ```
+//!
+//! With forgetting factor λ = 1 this reproduces batch OLS. With λ < 1 an
+//! observation's weight decays by λ per later update - an effective memory of
+//! about 1 / (1 − λ) rows - so the fit tracks coefficients that drift, as a
+//! sensor calibration does as it ages.
+//!
+//! Forgetting has a catch: in directions the data never excites (a regressor
+//! that stays constant, say) P is divided by λ on every update and grows
+//! without bound until it overflows. P's trace is therefore capped at its
+//! starting value, which rescales P without changing its shape.
+
+use matrix_math::Matrix;
+
+use crate::validation::Regressor;
+use crate::FitError;
+
+/// Settings for `RecursiveLeastSquares`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RlsConfig {
+    /// λ in (0, 1]: 1 remembers everything, 0.99 about the last 100 rows
+    pub forgetting: f64,
+    /// P starts as this times the identity: a vague prior on β = 0. The
+    /// larger it is, the less that prior biases the first few estimates.
+    pub initial_variance: f64,
+}
+
+impl Default for RlsConfig {
+    fn default() -> Self {
+        Self { forgetting: 1.0, initial_variance: 1e6 }
+    }
+}
+
+/// Streaming linear model; `coefficients[0]` is the intercept, as in `LinearModel`
+#[derive(Clone, Debug)]
+pub struct RecursiveLeastSquares {
+    pub coefficients: Vec<f64>,
+    /// Scaled inverse information matrix (k × k)
+    p: Matrix,
+    forgetting: f64,
+    /// Cap on trace(P): its value at the start
+    max_trace: f64,
+    /// Observations absorbed so far
+    pub count: usize,
+}
+
+impl RecursiveLeastSquares {
+    pub fn new(features: usize, config: &RlsConfig) -> Result<Self, FitError> {
+        if !(config.forgetting > 0.0 && config.forgetting <= 1.0) {
+            return Err(FitError::InvalidParameter("forgetting factor must be in (0, 1]"));
+        }
+        if !(config.initial_variance > 0.0 && config.initial_variance.is_finite()) {
+            return Err(FitError::InvalidParameter("initial variance must be finite and > 0"));
+        }
+        let k = features + 1;
+        let mut p = Matrix::new(k, k);
+        for j in 0..k { p[(j, j)] = config.initial_variance; }
+        let max_trace = k as f64 * config.initial_variance;
+        Ok(Self { coefficients: vec![0.0; k], p, forgetting: config.forgetting, max_trace, count: 0 })
+    }
+
+    /// Absorb one observation and return the prediction error it had before
+    /// the update (the a-priori residual) - a running check on the fit.
+    /// A NaN or infinite observation is refused before it can touch the state.
+    pub fn update(&mut self, x: &[f64], y: f64) -> Result<f64, FitError> {
+        let k = self.coefficients.len();
+        if x.len() + 1 != k { return Err(FitError::DimensionMismatch); }
+        if !y.is_finite() || x.iter().any(|v| !v.is_finite()) { return Err(FitError::NonFinite); }
+        let phi: Vec<f64> = std::iter::once(1.0).chain(x.iter().copied()).collect();
+        let p_phi: Vec<f64> = (0..k).map(|a| (0..k).map(|b| self.p[(a, b)] * phi[b]).sum()).collect();
+        let denom = self.forgetting + phi.iter().zip(&p_phi).map(|(f, pf)| f * pf).sum::<f64>();
+        let gain: Vec<f64> = p_phi.iter().map(|v| v / denom).collect();
+
+        let error = y - phi.iter().zip(&self.coefficients).map(|(f, b)| f * b).sum::<f64>();
+        for (b, g) in self.coefficients.iter_mut().zip(&gain) { *b += g * error; }
+        // P is symmetric, so φᵀP = (Pφ)ᵀ; averaging the two halves keeps
+        // rounding from slowly making it asymmetric
+        for a in 0..k {
+            for b in 0..=a {
+                let v = (self.p[(a, b)] - gain[a] * p_phi[b]) / self.forgetting;
+                let w = (self.p[(b, a)] - gain[b] * p_phi[a]) / self.forgetting;
+                self.p[(a, b)] = 0.5 * (v + w);
+                self.p[(b, a)] = 0.5 * (v + w);
+            }
+        }
+        let trace: f64 = (0..k).map(|j| self.p[(j, j)]).sum();
+        if trace > self.max_trace {
+            let shrink = self.max_trace / trace;
+            for a in 0..k { for b in 0..k { self.p[(a, b)] *= shrink; } }
+        }
+        self.count += 1;
+        Ok(error)
+    }
+
+    /// β₀ + xᵀβ with the current coefficients
+    pub fn predict(&self, x: &[f64]) -> f64 {
+        self.coefficients[0] + x.iter().zip(&self.coefficients[1..]).map(|(a, b)| a * b).sum::<f64>()
+    }
+}
+
+impl Regressor for RecursiveLeastSquares {
+    fn predict_row(&self, row: &[f64]) -> Result<f64, FitError> {
+        if row.len() + 1 != self.coefficients.len() { return Err(FitError::DimensionMismatch); }
+        Ok(self.predict(row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fit_with, Solver};
+
+    #[test]
+    fn without_forgetting_it_converges_to_batch_ols() {
+        let x: Vec<Vec<f64>> = (0..50).map(|i| vec![i as f64 / 10.0, ((i * 13) % 7) as f64]).collect();
+        let y: Vec<f64> = x.iter().enumerate().map(|(i, r)| 2.0 + 0.5 * r[0] - r[1] + 0.1 * ((i % 3) as f64 - 1.0)).collect();
+        let mut rls = RecursiveLeastSquares::new(2, &RlsConfig { initial_variance: 1e10, ..Default::default() }).unwrap();
+        for (row, &yi) in x.iter().zip(&y) { rls.update(row, yi).unwrap(); }
+        let batch = fit_with(&x, &y, Solver::Qr).unwrap();
+        for (a, b) in rls.coefficients.iter().zip(&batch.coefficients) {
+            assert!((a - b).abs() < 1e-6, "{:?} vs {:?}", rls.coefficients, batch.coefficients);
+        }
+        assert_eq!(rls.count, 50);
+    }
+
+    #[test]
+    fn exact_data_is_learnt_after_k_observations() {
+        let mut rls = RecursiveLeastSquares::new(1, &RlsConfig { initial_variance: 1e12, ..Default::default() }).unwrap();
+        rls.update(&[1.0], 5.0).unwrap();
+        rls.update(&[3.0], 9.0).unwrap();
+        // y = 3 + 2x; the next point is predicted before it is seen
+        assert!((rls.predict(&[10.0]) - 23.0).abs() < 1e-6);
+        assert!(rls.update(&[10.0], 23.0).unwrap().abs() < 1e-6);
+    }
+
+    #[test]
+    fn forgetting_tracks_a_drifting_gain() {
+        // A sensor whose gain steps from 2 to 3 halfway through
+        let reading = |i: usize| ((i * 37) % 11) as f64;
+        let truth = |i: usize, x: f64| if i < 300 { 2.0 * x } else { 3.0 * x };
+        let mut steady = RecursiveLeastSquares::new(1, &RlsConfig::default()).unwrap();
+        let mut tracking = RecursiveLeastSquares::new(1, &RlsConfig { forgetting: 0.95, ..Default::default() }).unwrap();
+        for i in 0..600 {
+            let x = reading(i);
+            steady.update(&[x], truth(i, x)).unwrap();
+            tracking.update(&[x], truth(i, x)).unwrap();
+        }
+        assert!((tracking.coefficients[1] - 3.0).abs() < 1e-6, "{:?}", tracking.coefficients);
+        // Without forgetting the estimate averages the two regimes
+        assert!((steady.coefficients[1] - 2.5).abs() < 0.1, "{:?}", steady.coefficients);
+    }
+
+    #[test]
+    fn invalid_settings_and_rows_are_rejected() {
+        for forgetting in [0.0, 1.5, f64::NAN] {
+            let config = RlsConfig { forgetting, ..Default::default() };
+            assert!(matches!(RecursiveLeastSquares::new(2, &config), Err(FitError::InvalidParameter(_))));
+        }
+        let mut rls = RecursiveLeastSquares::new(2, &RlsConfig::default()).unwrap();
+        assert_eq!(rls.update(&[1.0], 2.0).unwrap_err(), FitError::DimensionMismatch);
+        assert_eq!(rls.update(&[1.0, f64::NAN], 2.0).unwrap_err(), FitError::NonFinite);
+        assert_eq!(rls.update(&[1.0, 2.0], f64::INFINITY).unwrap_err(), FitError::NonFinite);
+        assert_eq!(rls.count, 0);
+        // The refused rows left the state untouched
+        rls.update(&[1.0, 2.0], 3.0).unwrap();
+        assert!(rls.coefficients.iter().all(|b| b.is_finite()));
+    }
+
+    #[test]
+    fn forgetting_stays_finite_when_a_direction_is_never_excited() {
+        // x never varies, so only β₀ + 5β₁ is identified; uncapped, P grows
+        // by 1/λ per row along the other direction and overflows
+        let mut rls = RecursiveLeastSquares::new(1, &RlsConfig { forgetting: 0.98, ..Default::default() }).unwrap();
+        for _ in 0..100_000 { rls.update(&[5.0], 11.0).unwrap(); }
+        assert!(rls.coefficients.iter().all(|b| b.is_finite()), "{:?}", rls.coefficients);
+        assert!((rls.predict(&[5.0]) - 11.0).abs() < 1e-9);
+        // The state still adapts once the regressor starts to move
+        for i in 0..500 {
+            let x = (i % 7) as f64;
+            rls.update(&[x], 1.0 + 2.0 * x).unwrap();
+        }
+        assert!((rls.coefficients[1] - 2.0).abs() < 1e-6, "{:?}", rls.coefficients);
+    }
+}
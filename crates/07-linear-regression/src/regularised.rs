@@ -0,0 +1,334 @@
+//! Penalised least squares: ridge, lasso and elastic net.
+//!
+//! All three minimise, over the intercept β₀ and slopes β,
+//!
+//! ```text
+//!   1/(2n) ‖y − β₀ − Xβ‖²  +  λ ( α ‖β‖₁ + (1 − α)/2 ‖β‖² )
+//! ```
+//!
+//! the glmnet scaling: α = 0 is ridge, α = 1 the lasso. The penalty makes the
+//! problem well posed even when features are collinear, which is where plain
+//! OLS gives up with `FitError::SingularMatrix`. β₀ is never penalised - the
+//! data are centred first and β₀ = ȳ − x̄ᵀβ recovered at the end - so shrinking
+//! the slopes never drags predictions towards zero.
+//!
+//! Coefficients are on the scale of the raw features, and so is the penalty:
+//! standardise features first if they are in very different units.
+
+use matrix_math::Matrix;
+
+use crate::{check_shapes, model_from_coefficients, FitError, LinearModel};
+
+/// Stopping rules for coordinate descent
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CoordinateDescent {
+    /// Stop once no coefficient moved the fitted values by more than this
+    /// fraction of y's standard deviation in a full sweep
+    pub tolerance: f64,
+    /// Give up (with `FitError::NotConverged`) after this many sweeps
+    pub max_sweeps: usize,
+}
+
+impl Default for CoordinateDescent {
+    fn default() -> Self {
+        Self { tolerance: 1e-7, max_sweeps: 10_000 }
+    }
+}
+
+/// One fit along a regularisation path
+#[derive(Clone, Debug)]
+pub struct PathPoint {
+    pub lambda: f64,
+    pub model: LinearModel,
+    /// Coordinate-descent sweeps spent at this λ, after warm-starting from the previous one
+    pub sweeps: usize,
+}
+
+/// Features and response centred on their means, stored column by column
+struct Centred {
+    n: usize,
+    columns: Vec<Vec<f64>>,
+    /// (1/n) ‖x_j‖² for each centred column
+    scale: Vec<f64>,
+    x_mean: Vec<f64>,
+    y: Vec<f64>,
+    y_mean: f64,
+}
+
+impl Centred {
+    fn new(x: &[Vec<f64>], y: &[f64]) -> Result<Self, FitError> {
+        let (n, p) = check_shapes(x, y)?;
+        let nf = n as f64;
+        let x_mean: Vec<f64> = (0..p).map(|j| x.iter().map(|row| row[j]).sum::<f64>() / nf).collect();
+        let columns: Vec<Vec<f64>> = (0..p).map(|j| x.iter().map(|row| row[j] - x_mean[j]).collect()).collect();
+        let scale = columns.iter().map(|col| dot(col, col) / nf).collect();
+        let y_mean = y.iter().sum::<f64>() / nf;
+        Ok(Self { n, columns, scale, x_mean, y: y.iter().map(|v| v - y_mean).collect(), y_mean })
+    }
+
+    /// Intercept first, then the slopes
+    fn coefficients(&self, beta: &[f64]) -> Vec<f64> {
+        let intercept = self.y_mean - self.x_mean.iter().zip(beta).map(|(m, b)| m * b).sum::<f64>();
+        std::iter::once(intercept).chain(beta.iter().copied()).collect()
+    }
+}
+
+/// Ridge regression (α = 0) in closed form: (XᵀX/n + λI) β = Xᵀy/n on
+/// centred data, solved by Cholesky. Any λ > 0 makes the system positive
+/// definite, so collinear features are fine; λ = 0 is OLS.
+pub fn ridge(x: &[Vec<f64>], y: &[f64], lambda: f64) -> Result<LinearModel, FitError> {
+    check_lambda(lambda)?;
+    let c = Centred::new(x, y)?;
+    let p = c.columns.len();
+    let nf = c.n as f64;
+    let mut gram = Matrix::new(p, p);
+    for j in 0..p {
+        for k in 0..=j {
+            let g = dot(&c.columns[j], &c.columns[k]) / nf;
+            gram[(j, k)] = g;
+            gram[(k, j)] = g;
+        }
+        gram[(j, j)] += lambda;
+    }
+    let rhs: Vec<f64> = c.columns.iter().map(|col| dot(col, &c.y) / nf).collect();
+    let beta = if p == 0 {
+        Vec::new()
+    } else {
+        gram.cholesky().ok_or(FitError::SingularMatrix)?.solve(&rhs).expect("one entry per feature")
+    };
+    Ok(model_from_coefficients(x, y, c.coefficients(&beta)))
+}
+
+/// Lasso (α = 1): the ℓ₁ penalty sets weak coefficients exactly to zero,
+/// selecting features as it shrinks.
+pub fn lasso(x: &[Vec<f64>], y: &[f64], lambda: f64, cd: &CoordinateDescent) -> Result<LinearModel, FitError> {
+    elastic_net(x, y, lambda, 1.0, cd)
+}
+
+/// Elastic net with mixing parameter `alpha` in [0, 1], by cyclic coordinate
+/// descent from β = 0. Groups of correlated features tend to enter together,
+/// where the lasso would pick one arbitrarily.
+pub fn elastic_net(x: &[Vec<f64>], y: &[f64], lambda: f64, alpha: f64, cd: &CoordinateDescent) -> Result<LinearModel, FitError> {
+    check_lambda(lambda)?;
+    check_alpha(alpha)?;
+    let c = Centred::new(x, y)?;
+    let mut beta = vec![0.0; c.columns.len()];
+    let mut residual = c.y.clone();
+    descend(&c, lambda, alpha, &mut beta, &mut residual, cd)?;
+    Ok(model_from_coefficients(x, y, c.coefficients(&beta)))
+}
+
+/// Smallest λ at which every slope is zero: max_j |x_jᵀ y| / (n α) on centred
+/// data. The natural top of a path. Needs `alpha` > 0 - ridge never zeroes
+/// coefficients outright.
+pub fn lambda_max(x: &[Vec<f64>], y: &[f64], alpha: f64) -> Result<f64, FitError> {
+    check_alpha(alpha)?;
+    if alpha == 0.0 { return Err(FitError::InvalidParameter("lambda_max needs alpha > 0")); }
+    let c = Centred::new(x, y)?;
+    Ok(c.columns.iter().map(|col| dot(col, &c.y).abs()).fold(0.0, f64::max) / (c.n as f64 * alpha))
+}
+
+/// `count` values from `max` down to `max * ratio`, evenly spaced on a log
+/// scale - glmnet uses ratio = 1e-4 when n > p and 1e-2 otherwise.
+/// `max` must be finite and > 0, `count` at least 1 and `ratio` in (0, 1),
+/// so the path strictly decreases.
+pub fn lambda_grid(max: f64, count: usize, ratio: f64) -> Result<Vec<f64>, FitError> {
+    if !(max > 0.0 && max.is_finite()) { return Err(FitError::InvalidParameter("lambda grid max must be finite and > 0")); }
+    if count == 0 { return Err(FitError::InvalidParameter("lambda grid needs at least one value")); }
+    if !(ratio > 0.0 && ratio < 1.0) { return Err(FitError::InvalidParameter("lambda grid ratio must lie in (0, 1)")); }
+    if count == 1 { return Ok(vec![max]); }
+    let step = ratio.ln() / (count as f64 - 1.0);
+    Ok((0..count).map(|i| max * (step * i as f64).exp()).collect())
+}
+
+/// Fit the elastic net at every λ, largest first, warm-starting each fit
+/// from the previous solution - neighbouring solutions are close, so a whole
+/// path typically costs a few single fits. Points come back in decreasing λ.
+pub fn elastic_net_path(
+    x: &[Vec<f64>],
+    y: &[f64],
+    alpha: f64,
+    lambdas: &[f64],
+    cd: &CoordinateDescent,
+) -> Result<Vec<PathPoint>, FitError> {
+    check_alpha(alpha)?;
+    lambdas.iter().try_for_each(|&l| check_lambda(l))?;
+    let c = Centred::new(x, y)?;
+    let mut sorted = lambdas.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+
+    let mut beta = vec![0.0; c.columns.len()];
+    let mut residual = c.y.clone();
+    sorted.into_iter().map(|lambda| {
+        let sweeps = descend(&c, lambda, alpha, &mut beta, &mut residual, cd)?;
+        Ok(PathPoint { lambda, model: model_from_coefficients(x, y, c.coefficients(&beta)), sweeps })
+    }).collect()
+}
+
+/// Cyclic coordinate descent from the given `beta`, keeping `residual` = y − Xβ
+/// in step. Each coordinate has a closed-form minimiser:
+///
+///   β_j = S(x_jᵀ r / n + z_j β_j, λα) / (z_j + λ(1 − α)),  z_j = ‖x_j‖² / n
+///
+/// with S the soft-threshold. Returns the number of sweeps.
+fn descend(c: &Centred, lambda: f64, alpha: f64, beta: &mut [f64], residual: &mut [f64], cd: &CoordinateDescent) -> Result<usize, FitError> {
+    let nf = c.n as f64;
+    let y_scale = (dot(&c.y, &c.y) / nf).sqrt();
+    let (l1, l2) = (lambda * alpha, lambda * (1.0 - alpha));
+    for sweep in 1..=cd.max_sweeps {
+        let mut largest_move = 0.0_f64;
+        for (j, col) in c.columns.iter().enumerate() {
+            let z = c.scale[j];
+            // A constant feature has nothing to say once centred
+            if z == 0.0 { beta[j] = 0.0; continue; }
+            let old = beta[j];
+            let rho = dot(col, residual) / nf + z * old;
+            let new = soft_threshold(rho, l1) / (z + l2);
+            if new != old {
+                for (r, xij) in residual.iter_mut().zip(col) { *r -= xij * (new - old); }
+                beta[j] = new;
+                // RMS change in the fitted values from this coordinate alone
+                largest_move = largest_move.max((new - old).abs() * z.sqrt());
+            }
+        }
+        if largest_move <= cd.tolerance * y_scale { return Ok(sweep); }
+    }
+    Err(FitError::NotConverged { sweeps: cd.max_sweeps })
+}
+
+fn soft_threshold(v: f64, t: f64) -> f64 {
+    v.signum() * (v.abs() - t).max(0.0)
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn check_lambda(lambda: f64) -> Result<(), FitError> {
+    if lambda >= 0.0 && lambda.is_finite() { Ok(()) } else { Err(FitError::InvalidParameter("lambda must be finite and >= 0")) }
+}
+
+fn check_alpha(alpha: f64) -> Result<(), FitError> {
+    if (0.0..=1.0).contains(&alpha) { Ok(()) } else { Err(FitError::InvalidParameter("alpha must lie in [0, 1]")) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64, tol: f64) -> bool {
+        (a - b).abs() <= tol
+    }
+
+    /// Three deterministic, non-collinear features and y = 1 + 2x₁ − 3x₂ + 0·x₃
+    fn sample() -> (Vec<Vec<f64>>, Vec<f64>) {
+        let x: Vec<Vec<f64>> = (0..30).map(|i| {
+            let t = i as f64;
+            vec![t.sin() * 3.0, (t * 0.7).cos() * 2.0, (t * 1.3).sin()]
+        }).collect();
+        let y = x.iter().map(|r| 1.0 + 2.0 * r[0] - 3.0 * r[1]).collect();
+        (x, y)
+    }
+
+    #[test]
+    fn ridge_without_penalty_is_ols() {
+        let (x, y) = sample();
+        let m = ridge(&x, &y, 0.0).unwrap();
+        for (got, want) in m.coefficients.iter().zip([1.0, 2.0, -3.0, 0.0]) {
+            assert!(close(*got, want, 1e-10), "{:?}", m.coefficients);
+        }
+        assert!(close(m.r_squared, 1.0, 1e-12));
+    }
+
+    #[test]
+    fn ridge_survives_collinear_features() {
+        // x₂ = 2x₁ exactly, so XᵀX is singular; as λ → 0 ridge tends to the
+        // minimum-norm solution, which splits y = 3x₁ as 0.6x₁ + 1.2x₂
+        let x: Vec<Vec<f64>> = (0..10).map(|i| vec![i as f64, 2.0 * i as f64]).collect();
+        let y: Vec<f64> = (0..10).map(|i| 5.0 + 3.0 * i as f64).collect();
+        assert_eq!(ridge(&x, &y, 0.0).unwrap_err(), FitError::SingularMatrix);
+        let m = ridge(&x, &y, 1e-6).unwrap();
+        assert!(close(m.coefficients[1], 0.6, 1e-5) && close(m.coefficients[2], 1.2, 1e-5), "{:?}", m.coefficients);
+        assert!(close(m.coefficients[0], 5.0, 1e-6));
+    }
+
+    #[test]
+    fn elastic_net_with_alpha_zero_matches_closed_form_ridge() {
+        let (x, y) = sample();
+        let cd = CoordinateDescent { tolerance: 1e-12, ..CoordinateDescent::default() };
+        for lambda in [0.01, 0.5, 3.0] {
+            let closed = ridge(&x, &y, lambda).unwrap();
+            let iterative = elastic_net(&x, &y, lambda, 0.0, &cd).unwrap();
+            for (a, b) in closed.coefficients.iter().zip(&iterative.coefficients) {
+                assert!(close(*a, *b, 1e-9), "λ={lambda}: {a} vs {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn lasso_soft_thresholds_orthogonal_features() {
+        // Centred, mutually orthogonal columns with ‖x_j‖²/n = 1, so each lasso
+        // coefficient is exactly S(x_jᵀy/n, λ)
+        let x: Vec<Vec<f64>> = (0..8).map(|i| vec![
+            if i % 2 == 0 { 1.0 } else { -1.0 },
+            if (i / 2) % 2 == 0 { 1.0 } else { -1.0 },
+            if (i / 4) % 2 == 0 { 1.0 } else { -1.0 },
+        ]).collect();
+        let y: Vec<f64> = x.iter().map(|r| 10.0 + 3.0 * r[0] - 0.5 * r[1] + 0.2 * r[2]).collect();
+        let m = lasso(&x, &y, 1.0, &CoordinateDescent::default()).unwrap();
+        assert_eq!(&m.coefficients[2..], &[0.0, 0.0], "weak features are dropped exactly");
+        assert!(close(m.coefficients[1], 2.0, 1e-12));
+        assert!(close(m.coefficients[0], 10.0, 1e-12), "the intercept is not shrunk");
+    }
+
+    #[test]
+    fn path_runs_from_all_zero_to_nearly_ols() {
+        let (x, y) = sample();
+        let top = lambda_max(&x, &y, 1.0).unwrap();
+        let lambdas = lambda_grid(top, 30, 1e-5).unwrap();
+        let path = elastic_net_path(&x, &y, 1.0, &lambdas, &CoordinateDescent::default()).unwrap();
+        assert_eq!(path.len(), 30);
+        assert!(path.windows(2).all(|w| w[0].lambda > w[1].lambda));
+
+        let first = &path[0].model.coefficients;
+        assert!(first[1..].iter().all(|&b| b == 0.0));
+        assert!(close(first[0], y.iter().sum::<f64>() / y.len() as f64, 1e-12));
+        assert!(path[1].model.coefficients[1..].iter().any(|&b| b != 0.0));
+
+        let last = &path[29].model.coefficients;
+        for (got, want) in last.iter().zip([1.0, 2.0, -3.0, 0.0]) {
+            assert!(close(*got, want, 1e-3), "{last:?}");
+        }
+    }
+
+    #[test]
+    fn warm_starts_make_later_path_points_cheap() {
+        let (x, y) = sample();
+        let cd = CoordinateDescent::default();
+        let lambdas = lambda_grid(lambda_max(&x, &y, 0.5).unwrap(), 50, 1e-4).unwrap();
+        let path = elastic_net_path(&x, &y, 0.5, &lambdas, &cd).unwrap();
+        let last = path.last().unwrap();
+        let cold = elastic_net(&x, &y, last.lambda, 0.5, &cd).unwrap();
+        for (a, b) in cold.coefficients.iter().zip(&last.model.coefficients) {
+            assert!(close(*a, *b, 1e-5));
+        }
+        let average = path.iter().map(|p| p.sweeps).sum::<usize>() as f64 / path.len() as f64;
+        assert!(average < 20.0, "average {average} sweeps per λ");
+    }
+
+    #[test]
+    fn invalid_parameters_and_sweep_limits_are_errors() {
+        let (x, y) = sample();
+        let cd = CoordinateDescent::default();
+        assert!(matches!(ridge(&x, &y, -1.0), Err(FitError::InvalidParameter(_))));
+        assert!(matches!(elastic_net(&x, &y, 1.0, 1.5, &cd), Err(FitError::InvalidParameter(_))));
+        assert!(matches!(lambda_max(&x, &y, 0.0), Err(FitError::InvalidParameter(_))));
+        for (max, count, ratio) in [(1.0, 0, 1e-3), (1.0, 5, 0.0), (1.0, 5, 1.0), (1.0, 5, 2.0), (1.0, 5, f64::NAN), (0.0, 5, 1e-3)] {
+            assert!(matches!(lambda_grid(max, count, ratio), Err(FitError::InvalidParameter(_))), "{max} {count} {ratio}");
+        }
+        assert_eq!(lambda_grid(2.0, 1, 0.5).unwrap(), vec![2.0]);
+        let one_sweep = CoordinateDescent { max_sweeps: 1, tolerance: 1e-12 };
+        assert_eq!(lasso(&x, &y, 1e-3, &one_sweep).unwrap_err(), FitError::NotConverged { sweeps: 1 });
+        assert_eq!(ridge(&[vec![1.0], vec![2.0, 3.0]], &[1.0, 2.0], 1.0).unwrap_err(), FitError::DimensionMismatch);
+    }
+}
@@ -0,0 +1,316 @@
+//! Weighted and outlier-resistant regression.
+//!
+//! OLS squares every residual, so one reading that is off by 1000 counts a
+//! million times more than one off by 1 and drags the whole line towards it.
+//! Three remedies:
+//!
+//! - weighted least squares, when you know how much to trust each row;
+//! - M-estimation (Huber, Tukey), which learns the weights by down-weighting
+//!   rows with large residuals, re-solving WLS until the weights settle;
+//! - RANSAC, which fits many minimal random subsets and keeps the model with
+//!   the most rows inside a residual threshold, then refits on those alone.
+//!
+//! Each returns an ordinary `LinearModel`, whose residuals and R² cover every
+//! row, plus the per-row weights or inlier mask the fit settled on.
+
+use matrix_math::{Cholesky, Matrix};
+use rand::rngs::StdRng;
+use rand::seq::index::sample;
+use rand::SeedableRng;
+use statistics_core::scaled_mad;
+
+use crate::{check_finite, check_shapes, model_from_coefficients, FitError, Irls, LinearModel};
+
+/// Minimise Σ wᵢ (yᵢ − β₀ − xᵢᵀβ)². Weights are relative - scaling them all
+/// leaves the fit unchanged - and a weight of zero drops the row. With
+/// wᵢ = 1/σᵢ² this is the best linear unbiased fit for errors of known
+/// variance σᵢ². R² in the result is the ordinary, unweighted one.
+pub fn weighted_least_squares(x: &[Vec<f64>], y: &[f64], weights: &[f64]) -> Result<LinearModel, FitError> {
+    check_shapes(x, y)?;
+    if weights.len() != y.len() { return Err(FitError::DimensionMismatch); }
+    if weights.iter().any(|w| !(*w >= 0.0 && w.is_finite())) {
+        return Err(FitError::InvalidParameter("weights must be finite and >= 0"));
+    }
+    let coefficients = solve_weighted(x, y, weights)?;
+    Ok(model_from_coefficients(x, y, coefficients))
+}
+
+/// Solve the weighted normal equations (XᵀWX) β = XᵀW y with an intercept
+/// column, by Cholesky. Shapes are the caller's to check.
+pub(crate) fn solve_weighted(x: &[Vec<f64>], y: &[f64], w: &[f64]) -> Result<Vec<f64>, FitError> {
+    let k = x[0].len() + 1;
+    let mut gram = Matrix::new(k, k);
+    let mut rhs = vec![0.0; k];
+    for ((row, &yi), &wi) in x.iter().zip(y).zip(w) {
+        let d = |j: usize| if j == 0 { 1.0 } else { row[j - 1] };
+        for a in 0..k {
+            rhs[a] += wi * d(a) * yi;
+            for b in 0..=a { gram[(a, b)] += wi * d(a) * d(b); }
+        }
+    }
+    for a in 0..k {
+        for b in 0..a { gram[(b, a)] = gram[(a, b)]; }
+    }
+    Cholesky::new(&gram).and_then(|c| c.solve(&rhs)).ok_or(FitError::SingularMatrix)
+}
+
+/// How M-estimation weights a residual u, measured in robust standard deviations
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Loss {
+    /// Quadratic for |u| <= k, linear beyond: weight min(1, k / |u|).
+    /// Convex, so the fit is unique; outliers keep a little influence.
+    Huber(f64),
+    /// Tukey's biweight: weight (1 − (u/c)²)² for |u| < c, else 0.
+    /// Rejects gross outliers outright, but is not convex, so the fit starts
+    /// from the Huber solution to land in the right minimum.
+    Tukey(f64),
+}
+
+impl Loss {
+    /// Huber with k = 1.345: 95% as efficient as OLS on normal errors
+    pub fn huber() -> Self {
+        Loss::Huber(1.345)
+    }
+
+    /// Tukey with c = 4.685: 95% as efficient as OLS on normal errors
+    pub fn tukey() -> Self {
+        Loss::Tukey(4.685)
+    }
+
+    fn weight(self, u: f64) -> f64 {
+        match self {
+            Loss::Huber(k) => if u.abs() <= k { 1.0 } else { k / u.abs() },
+            Loss::Tukey(c) => if u.abs() < c { (1.0 - (u / c).powi(2)).powi(2) } else { 0.0 },
+        }
+    }
+
+    fn tuning(self) -> f64 {
+        match self {
+            Loss::Huber(k) | Loss::Tukey(k) => k,
+        }
+    }
+}
+
+/// An M-estimate and the weights it converged to
+#[derive(Clone, Debug)]
+pub struct RobustFit {
+    pub model: LinearModel,
+    /// Final weight of each row in [0, 1]: 1 for a row fitted as ordinary,
+    /// near 0 for one treated as an outlier
+    pub weights: Vec<f64>,
+    /// Robust residual scale: MAD of the residuals times `MAD_NORMAL_SCALE` (1.4826)
+    pub scale: f64,
+    pub iterations: usize,
+}
+
+/// Huber or Tukey M-estimation by IRLS, starting from OLS. Each iteration
+/// re-estimates the residual scale by `statistics_core::scaled_mad`, reweights the rows
+/// and solves WLS; it stops once no coefficient βⱼ moves by more than
+/// `irls.tolerance` × (1 + |βⱼ|).
+pub fn m_estimate(x: &[Vec<f64>], y: &[f64], loss: Loss, irls: &Irls) -> Result<RobustFit, FitError> {
+    let (n, p) = check_shapes(x, y)?;
+    check_finite(x, y)?;
+    if !(loss.tuning() > 0.0 && loss.tuning().is_finite()) {
+        return Err(FitError::InvalidParameter("loss tuning constant must be finite and > 0"));
+    }
+    if n <= p + 1 { return Err(FitError::InsufficientData { needed: p + 2, got: n }); }
+
+    let (mut coefficients, start) = match loss {
+        Loss::Tukey(_) => {
+            let huber = m_estimate(x, y, Loss::huber(), irls)?;
+            (huber.model.coefficients, huber.iterations)
+        }
+        Loss::Huber(_) => (solve_weighted(x, y, &vec![1.0; n])?, 0),
+    };
+    for iteration in 1..=irls.max_iterations {
+        let residuals = model_from_coefficients(x, y, coefficients.clone()).residuals;
+        let scale = scaled_mad(&residuals)?;
+        // More than half the rows fitted exactly: they are the data, the rest outliers
+        if scale == 0.0 {
+            let weights = residuals.iter().map(|&r| if r == 0.0 { 1.0 } else { 0.0 }).collect();
+            let model = model_from_coefficients(x, y, coefficients);
+            return Ok(RobustFit { model, weights, scale, iterations: start + iteration });
+        }
+        let weights: Vec<f64> = residuals.iter().map(|&r| loss.weight(r / scale)).collect();
+        let next = solve_weighted(x, y, &weights)?;
+        let settled = next.iter().zip(&coefficients).all(|(a, b)| (a - b).abs() <= irls.tolerance * (1.0 + b.abs()));
+        coefficients = next;
+        if settled {
+            let model = model_from_coefficients(x, y, coefficients);
+            return Ok(RobustFit { model, weights, scale, iterations: start + iteration });
+        }
+    }
+    Err(FitError::NotConverged { sweeps: start + irls.max_iterations })
+}
+
+/// RANSAC settings
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ransac {
+    /// A row is an inlier when its absolute residual is at most this, in units of y
+    pub threshold: f64,
+    /// Rows per random subset; `None` means the minimum, features + 1
+    pub min_samples: Option<usize>,
+    pub max_trials: usize,
+    pub seed: u64,
+}
+
+impl Ransac {
+    pub fn new(threshold: f64) -> Self {
+        Self { threshold, min_samples: None, max_trials: 100, seed: 0 }
+    }
+}
+
+/// A RANSAC fit and the rows it was refitted on
+#[derive(Clone, Debug)]
+pub struct RansacFit {
+    pub model: LinearModel,
+    /// true for each row within the threshold of the final model's consensus set
+    pub inliers: Vec<bool>,
+    pub trials: usize,
+}
+
+/// Random sample consensus. Each trial fits OLS to `min_samples` random rows
+/// and counts the rows within `threshold` of it; the largest consensus set
+/// (ties to the smaller inlier error) is refitted by OLS. Copes with far more
+/// outliers than M-estimation - up to about half - provided some trial draws
+/// a clean subset. Deterministic for a given `seed`.
+pub fn ransac(x: &[Vec<f64>], y: &[f64], config: &Ransac) -> Result<RansacFit, FitError> {
+    let (n, p) = check_shapes(x, y)?;
+    if !(config.threshold > 0.0 && config.threshold.is_finite()) {
+        return Err(FitError::InvalidParameter("RANSAC threshold must be finite and > 0"));
+    }
+    let m = config.min_samples.unwrap_or(p + 1);
+    if m < p + 1 { return Err(FitError::InvalidParameter("RANSAC needs min_samples >= features + 1")); }
+    if n < m { return Err(FitError::InsufficientData { needed: m, got: n }); }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let consensus = |coefficients: Vec<f64>| {
+        let residuals = model_from_coefficients(x, y, coefficients).residuals;
+        let mask: Vec<bool> = residuals.iter().map(|r| r.abs() <= config.threshold).collect();
+        let error: f64 = residuals.iter().zip(&mask).filter(|(_, &inlier)| inlier).map(|(r, _)| r * r).sum();
+        (mask, error)
+    };
+    let mut best: Option<(Vec<bool>, usize, f64)> = None;
+    let mut trials = 0;
+    for _ in 0..config.max_trials {
+        trials += 1;
+        let rows = sample(&mut rng, n, m).into_vec();
+        let xs: Vec<Vec<f64>> = rows.iter().map(|&i| x[i].clone()).collect();
+        let ys: Vec<f64> = rows.iter().map(|&i| y[i]).collect();
+        // A degenerate subset, such as repeated x values, says nothing - draw again
+        let Ok(coefficients) = solve_weighted(&xs, &ys, &vec![1.0; m]) else { continue };
+        let (mask, error) = consensus(coefficients);
+        let count = mask.iter().filter(|&&b| b).count();
+        if best.as_ref().is_none_or(|(_, c, e)| count > *c || (count == *c && error < *e)) {
+            best = Some((mask, count, error));
+            if count == n { break; }
+        }
+    }
+
+    let (mask, count, _) = best.ok_or(FitError::SingularMatrix)?;
+    if count < p + 1 { return Err(FitError::InsufficientData { needed: p + 1, got: count }); }
+    let weights: Vec<f64> = mask.iter().map(|&b| if b { 1.0 } else { 0.0 }).collect();
+    let coefficients = solve_weighted(x, y, &weights)?;
+    let (inliers, _) = consensus(coefficients.clone());
+    Ok(RansacFit { model: model_from_coefficients(x, y, coefficients), inliers, trials })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// y = 2 + 3x with small alternating noise, and gross errors at `bad`
+    fn contaminated(n: usize, bad: &[usize]) -> (Vec<Vec<f64>>, Vec<f64>) {
+        let x: Vec<Vec<f64>> = (0..n).map(|i| vec![i as f64]).collect();
+        let y = (0..n).map(|i| {
+            let clean = 2.0 + 3.0 * i as f64 + if i % 2 == 0 { 0.3 } else { -0.3 };
+            if bad.contains(&i) { clean + 500.0 } else { clean }
+        }).collect();
+        (x, y)
+    }
+
+    #[test]
+    fn integer_weights_match_repeated_rows() {
+        let x = vec![vec![0.0], vec![1.0], vec![2.0], vec![3.0]];
+        let y = [1.0, 2.5, 2.9, 4.4];
+        let wls = weighted_least_squares(&x, &y, &[1.0, 3.0, 1.0, 2.0]).unwrap();
+        let xr = vec![vec![0.0], vec![1.0], vec![1.0], vec![1.0], vec![2.0], vec![3.0], vec![3.0]];
+        let yr = [1.0, 2.5, 2.5, 2.5, 2.9, 4.4, 4.4];
+        let ols = solve_weighted(&xr, &yr, &[1.0; 7]).unwrap();
+        for (a, b) in wls.coefficients.iter().zip(&ols) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert_eq!(wls.residuals.len(), 4);
+    }
+
+    #[test]
+    fn zero_weights_drop_rows_and_bad_weights_are_rejected() {
+        let (x, y) = contaminated(10, &[4]);
+        let mut w = vec![1.0; 10];
+        w[4] = 0.0;
+        let m = weighted_least_squares(&x, &y, &w).unwrap();
+        assert!((m.coefficients[1] - 3.0).abs() < 0.1, "{:?}", m.coefficients);
+        assert!(m.residuals[4] > 400.0);
+        assert!(matches!(weighted_least_squares(&x, &y, &[-1.0; 10]), Err(FitError::InvalidParameter(_))));
+        assert_eq!(weighted_least_squares(&x, &y, &[1.0; 3]).unwrap_err(), FitError::DimensionMismatch);
+    }
+
+    #[test]
+    fn huber_down_weights_outliers() {
+        let (x, y) = contaminated(30, &[5, 20]);
+        let ols = solve_weighted(&x, &y, &[1.0; 30]).unwrap();
+        assert!((ols[1] - 3.0).abs() > 0.5, "OLS should be pulled off: {ols:?}");
+        let fit = m_estimate(&x, &y, Loss::huber(), &Irls::default()).unwrap();
+        assert!((fit.model.coefficients[1] - 3.0).abs() < 0.05, "{:?}", fit.model.coefficients);
+        assert!(fit.weights[5] < 0.01 && fit.weights[20] < 0.01);
+        assert!(fit.weights.iter().all(|w| (0.0..=1.0).contains(w)));
+        // The ±0.3 noise sets the scale, not the 500-unit errors
+        assert!(fit.scale < 1.0, "scale {}", fit.scale);
+    }
+
+    #[test]
+    fn tukey_rejects_gross_outliers_entirely() {
+        let (x, y) = contaminated(30, &[3, 12, 27]);
+        let fit = m_estimate(&x, &y, Loss::tukey(), &Irls::default()).unwrap();
+        for &i in &[3, 12, 27] { assert_eq!(fit.weights[i], 0.0); }
+        assert!((fit.model.coefficients[0] - 2.0).abs() < 0.2);
+        assert!((fit.model.coefficients[1] - 3.0).abs() < 0.01);
+        assert!(matches!(m_estimate(&x, &y, Loss::Huber(0.0), &Irls::default()), Err(FitError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn m_estimate_rejects_non_finite_observations() {
+        let (mut x, mut y) = contaminated(30, &[3]);
+        y[5] = f64::NAN;
+        assert_eq!(m_estimate(&x, &y, Loss::huber(), &Irls::default()).unwrap_err(), FitError::NonFinite);
+        y[5] = 1.0;
+        x[7][0] = f64::INFINITY;
+        assert_eq!(m_estimate(&x, &y, Loss::tukey(), &Irls::default()).unwrap_err(), FitError::NonFinite);
+    }
+
+    #[test]
+    fn ransac_separates_inliers_from_outliers() {
+        // A third of the rows are gross errors - too many for the Huber fit to ignore completely
+        let bad: Vec<usize> = (0..30).filter(|i| i % 3 == 1).collect();
+        let (x, y) = contaminated(30, &bad);
+        let fit = ransac(&x, &y, &Ransac::new(1.0)).unwrap();
+        for (i, &inlier) in fit.inliers.iter().enumerate() {
+            assert_eq!(inlier, !bad.contains(&i), "row {i}");
+        }
+        assert!((fit.model.coefficients[1] - 3.0).abs() < 0.01, "{:?}", fit.model.coefficients);
+        assert!(fit.trials <= 100);
+        // Same seed, same answer
+        let again = ransac(&x, &y, &Ransac::new(1.0)).unwrap();
+        assert_eq!(again.model.coefficients, fit.model.coefficients);
+    }
+
+    #[test]
+    fn ransac_validates_its_settings() {
+        let (x, y) = contaminated(10, &[]);
+        assert!(matches!(ransac(&x, &y, &Ransac::new(0.0)), Err(FitError::InvalidParameter(_))));
+        let tiny = Ransac { min_samples: Some(1), ..Ransac::new(1.0) };
+        assert!(matches!(ransac(&x, &y, &tiny), Err(FitError::InvalidParameter(_))));
+        let too_many = Ransac { min_samples: Some(11), ..Ransac::new(1.0) };
+        assert_eq!(ransac(&x, &y, &too_many).unwrap_err(), FitError::InsufficientData { needed: 11, got: 10 });
+    }
+}
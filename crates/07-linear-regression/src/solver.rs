@@ -0,0 +1,157 @@
+//! Batch least-squares solvers for OLS.
+//!
+//! The normal equations form XᵀX, whose condition number is κ(X)²: a design
+//! with κ = 10⁶ - a handful of polynomial terms will do it - leaves XᵀX at
+//! 10¹², and the solve keeps only about four of f64's sixteen digits.
+//! Householder QR works on X itself, so its error grows with κ rather than κ²;
+//! the SVD costs more again but copes with rank-deficient designs too.
+//!
+//! Columns are scaled to unit length before factorising. That changes no
+//! fitted value, but it makes the rank test independent of the units each
+//! feature happens to be measured in.
+
+use matrix_math::Matrix;
+
+use crate::robust::solve_weighted;
+use crate::{check_finite, check_shapes, model_from_coefficients, FitError, LinearModel};
+
+/// How to solve the least-squares problem
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Solver {
+    /// Householder QR: accurate to about κ(X) · ε. Fails with
+    /// `FitError::SingularMatrix` when the design is rank deficient.
+    #[default]
+    Qr,
+    /// SVD pseudo-inverse. Singular values below σ₁ · max(n, k) · ε are
+    /// dropped, so a rank-deficient design gets the minimum-norm solution
+    /// (in the column-scaled coordinates) instead of an error.
+    Svd,
+    /// Cholesky on XᵀX: fastest and fine for well-conditioned data, but
+    /// accurate only to about κ(X)² · ε
+    NormalEquations,
+}
+
+/// OLS with an explicit choice of solver; `x` has no bias column and an
+/// intercept is always fitted.
+pub fn fit_with(x: &[Vec<f64>], y: &[f64], solver: Solver) -> Result<LinearModel, FitError> {
+    let (n, p) = check_shapes(x, y)?;
+    check_finite(x, y)?;
+    let k = p + 1;
+    if n < k { return Err(FitError::InsufficientData { needed: k, got: n }); }
+    let coefficients = match solver {
+        Solver::NormalEquations => solve_weighted(x, y, &vec![1.0; n])?,
+        Solver::Qr | Solver::Svd => {
+            let (design, scales) = scaled_design(x)?;
+            let beta = match solver {
+                Solver::Qr => design.least_squares(y).ok_or(FitError::SingularMatrix)?,
+                _ => {
+                    let svd = design.svd();
+                    let pinv = svd.pseudo_inverse(svd.default_tolerance());
+                    (0..k).map(|j| (0..n).map(|i| pinv[(j, i)] * y[i]).sum()).collect()
+                }
+            };
+            beta.iter().zip(&scales).map(|(b, s)| b / s).collect()
+        }
+    };
+    Ok(model_from_coefficients(x, y, coefficients))
+}
+
+/// The design matrix [1 | x] with every column divided by its 2-norm, and
+/// those norms. An all-zero column keeps scale 1: QR then reports the design
+/// singular, while the SVD drops the column and gives it coefficient 0.
+fn scaled_design(x: &[Vec<f64>]) -> Result<(Matrix, Vec<f64>), FitError> {
+    let (n, k) = (x.len(), x[0].len() + 1);
+    let value = |i: usize, j: usize| if j == 0 { 1.0 } else { x[i][j - 1] };
+    let scales: Vec<f64> = (0..k)
+        .map(|j| (0..n).map(|i| value(i, j).powi(2)).sum::<f64>().sqrt())
+        .map(|s| if s == 0.0 { 1.0 } else { s })
+        .collect();
+    if scales.iter().any(|s| !s.is_finite()) { return Err(FitError::NonFinite); }
+    let mut design = Matrix::new(n, k);
+    for i in 0..n {
+        for j in 0..k { design[(i, j)] = value(i, j) / scales[j]; }
+    }
+    Ok((design, scales))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// y = 1 + x + x² + ... + x^degree exactly, for x in [0, 1]
+    fn polynomial(degree: usize) -> (Vec<Vec<f64>>, Vec<f64>) {
+        let x: Vec<Vec<f64>> = (0..40).map(|i| {
+            let t = i as f64 / 39.0;
+            (1..=degree as i32).map(|d| t.powi(d)).collect()
+        }).collect();
+        let y = x.iter().map(|row| 1.0 + row.iter().sum::<f64>()).collect();
+        (x, y)
+    }
+
+    fn max_error(model: &LinearModel) -> f64 {
+        model.coefficients.iter().map(|c| (c - 1.0).abs()).fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn every_solver_agrees_on_a_well_conditioned_fit() {
+        let x: Vec<Vec<f64>> = (0..10).map(|i| vec![i as f64, ((i * 7) % 5) as f64]).collect();
+        let y: Vec<f64> = x.iter().map(|r| 3.0 - 2.0 * r[0] + 0.5 * r[1]).collect();
+        for solver in [Solver::Qr, Solver::Svd, Solver::NormalEquations] {
+            let m = fit_with(&x, &y, solver).unwrap();
+            for (c, want) in m.coefficients.iter().zip([3.0, -2.0, 0.5]) {
+                assert!((c - want).abs() < 1e-12, "{solver:?}: {:?}", m.coefficients);
+            }
+            assert!((m.r_squared - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn qr_keeps_the_digits_the_normal_equations_lose() {
+        // κ(X) ≈ 10⁷ for a degree-7 polynomial on [0, 1]; κ(XᵀX) ≈ 10¹⁴
+        let (x, y) = polynomial(7);
+        let qr = max_error(&fit_with(&x, &y, Solver::Qr).unwrap());
+        let svd = max_error(&fit_with(&x, &y, Solver::Svd).unwrap());
+        let normal = fit_with(&x, &y, Solver::NormalEquations).map_or(f64::INFINITY, |m| max_error(&m));
+        assert!(qr < 1e-7 && svd < 1e-7, "qr {qr:e}, svd {svd:e}");
+        assert!(normal > 100.0 * qr, "normal equations {normal:e} vs qr {qr:e}");
+    }
+
+    #[test]
+    fn rank_deficient_designs_fail_for_qr_and_get_minimum_norm_from_svd() {
+        // Third feature is the sum of the first two
+        let x: Vec<Vec<f64>> = (0..8).map(|i| { let (a, b) = (i as f64, (i * i % 5) as f64); vec![a, b, a + b] }).collect();
+        let y: Vec<f64> = x.iter().map(|r| 1.0 + r[2]).collect();
+        assert_eq!(fit_with(&x, &y, Solver::Qr).unwrap_err(), FitError::SingularMatrix);
+        let m = fit_with(&x, &y, Solver::Svd).unwrap();
+        // Still an exact fit, with the weight spread over the dependent columns
+        assert!(m.residuals.iter().all(|r| r.abs() < 1e-9));
+        assert!(m.coefficients[1] > 0.0 && m.coefficients[2] > 0.0 && m.coefficients[3] > 0.0);
+    }
+
+    #[test]
+    fn an_all_zero_feature_is_dropped_by_svd() {
+        // A sensor channel that never fired
+        let x: Vec<Vec<f64>> = (0..6).map(|i| vec![i as f64, 0.0]).collect();
+        let y: Vec<f64> = x.iter().map(|r| 2.0 + 3.0 * r[0]).collect();
+        assert_eq!(fit_with(&x, &y, Solver::Qr).unwrap_err(), FitError::SingularMatrix);
+        let m = fit_with(&x, &y, Solver::Svd).unwrap();
+        for (c, want) in m.coefficients.iter().zip([2.0, 3.0, 0.0]) {
+            assert!((c - want).abs() < 1e-12, "{:?}", m.coefficients);
+        }
+        assert_eq!(fit_with(&[vec![f64::INFINITY], vec![1.0]], &[1.0, 2.0], Solver::Svd).unwrap_err(), FitError::NonFinite);
+        for solver in [Solver::Qr, Solver::Svd, Solver::NormalEquations] {
+            assert_eq!(fit_with(&[vec![0.0], vec![1.0]], &[1.0, f64::NAN], solver).unwrap_err(), FitError::NonFinite);
+        }
+    }
+
+    #[test]
+    fn scaling_does_not_depend_on_units() {
+        // The same fit with one feature in grams instead of kilograms
+        let x: Vec<Vec<f64>> = (0..6).map(|i| vec![i as f64 * 1e6, (i % 2) as f64]).collect();
+        let y: Vec<f64> = x.iter().map(|r| 5.0 + 2e-6 * r[0] + r[1]).collect();
+        let m = fit_with(&x, &y, Solver::Qr).unwrap();
+        assert!((m.coefficients[1] - 2e-6).abs() < 1e-18);
+        assert_eq!(fit_with(&[vec![0.0], vec![0.0]], &[1.0, 2.0], Solver::Qr).unwrap_err(), FitError::SingularMatrix);
+        assert_eq!(fit_with(&[vec![1.0, 2.0]], &[1.0], Solver::Svd).unwrap_err(), FitError::InsufficientData { needed: 3, got: 1 });
+    }
+}
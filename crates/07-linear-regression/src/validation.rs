@@ -0,0 +1,346 @@
+//! Out-of-sample evaluation: train/test splits, k-fold and time-series
+//! cross-validation, error metrics and grid search.
+//!
+//! R² on the training data only ever rises as features are added, so it
+//! cannot say whether a model will predict new data well. Scoring on rows the
+//! model never saw can. Splits are returned as row indices (`Fold`), so the
+//! same folds can be reused to compare several models fairly.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::{FitError, FittedPipeline, GlmModel, LinearModel};
+
+/// Anything fitted that can predict a response for one row of raw features
+pub trait Regressor {
+    fn predict_row(&self, row: &[f64]) -> Result<f64, FitError>;
+}
+
+impl Regressor for LinearModel {
+    fn predict_row(&self, row: &[f64]) -> Result<f64, FitError> {
+        if row.len() + 1 != self.coefficients.len() { return Err(FitError::DimensionMismatch); }
+        Ok(self.coefficients[0] + row.iter().zip(&self.coefficients[1..]).map(|(a, b)| a * b).sum::<f64>())
+    }
+}
+
+impl Regressor for FittedPipeline {
+    fn predict_row(&self, row: &[f64]) -> Result<f64, FitError> {
+        self.predict(row)
+    }
+}
+
+/// Predicts the fitted mean, as `predict_proba` does
+impl Regressor for GlmModel {
+    fn predict_row(&self, row: &[f64]) -> Result<f64, FitError> {
+        if row.len() + 1 != self.coefficients.len() { return Err(FitError::DimensionMismatch); }
+        Ok(self.predict_proba(row))
+    }
+}
+
+/// Row indices to train on and to score on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fold {
+    pub train: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+/// Rows copied out of a data set for one fold
+pub struct FoldData {
+    pub x_train: Vec<Vec<f64>>,
+    pub y_train: Vec<f64>,
+    pub x_test: Vec<Vec<f64>>,
+    pub y_test: Vec<f64>,
+}
+
+impl Fold {
+    pub fn select(&self, x: &[Vec<f64>], y: &[f64]) -> FoldData {
+        FoldData {
+            x_train: self.train.iter().map(|&i| x[i].clone()).collect(),
+            y_train: self.train.iter().map(|&i| y[i]).collect(),
+            x_test: self.test.iter().map(|&i| x[i].clone()).collect(),
+            y_test: self.test.iter().map(|&i| y[i]).collect(),
+        }
+    }
+}
+
+/// Shuffle `n` rows with `seed` and hold out `test_fraction` of them
+/// (rounded, but at least one row on each side). The same seed always gives
+/// the same split.
+pub fn train_test_split(n: usize, test_fraction: f64, seed: u64) -> Result<Fold, FitError> {
+    if !(test_fraction > 0.0 && test_fraction < 1.0) {
+        return Err(FitError::InvalidParameter("test fraction must be in (0, 1)"));
+    }
+    if n < 2 { return Err(FitError::InsufficientData { needed: 2, got: n }); }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.shuffle(&mut StdRng::seed_from_u64(seed));
+    let n_test = ((n as f64 * test_fraction).round() as usize).clamp(1, n - 1);
+    let test = order.split_off(n - n_test);
+    Ok(Fold { train: order, test })
+}
+
+/// Split `n` rows into `k` test folds of near-equal size; each fold trains on
+/// the other k − 1. Rows keep their order unless `shuffle_seed` is given -
+/// shuffle when the data are sorted by anything that matters.
+pub fn k_fold(n: usize, k: usize, shuffle_seed: Option<u64>) -> Result<Vec<Fold>, FitError> {
+    if k < 2 { return Err(FitError::InvalidParameter("k-fold needs k >= 2")); }
+    if n < k { return Err(FitError::InsufficientData { needed: k, got: n }); }
+    let mut order: Vec<usize> = (0..n).collect();
+    if let Some(seed) = shuffle_seed {
+        order.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
+    let mut start = 0;
+    Ok((0..k).map(|f| {
+        let size = n / k + usize::from(f < n % k);
+        let test = order[start..start + size].to_vec();
+        let train = order[..start].iter().chain(&order[start + size..]).copied().collect();
+        start += size;
+        Fold { train, test }
+    }).collect())
+}
+
+/// Forward-chaining splits for time-ordered rows: `splits` consecutive test
+/// blocks at the end of the series, each trained only on rows before it -
+/// never on the future. `gap` rows between training and test are left out,
+/// so autocorrelation cannot leak the answer across the boundary.
+pub fn time_series_split(n: usize, splits: usize, gap: usize) -> Result<Vec<Fold>, FitError> {
+    if splits == 0 { return Err(FitError::InvalidParameter("time-series split needs at least one split")); }
+    let blocks = splits.checked_add(1).ok_or(FitError::InvalidParameter("time-series split count is too large"))?;
+    let test_size = n / blocks;
+    if test_size == 0 || n - splits * test_size <= gap {
+        return Err(FitError::InsufficientData { needed: blocks.saturating_add(gap), got: n });
+    }
+    Ok((0..splits).map(|s| {
+        let test_start = n - (splits - s) * test_size;
+        Fold { train: (0..test_start - gap).collect(), test: (test_start..test_start + test_size).collect() }
+    }).collect())
+}
+
+/// How to score predictions against the actual values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    Rmse,
+    Mae,
+    Mape,
+    RSquared,
+}
+
+impl Metric {
+    pub fn score(self, actual: &[f64], predicted: &[f64]) -> Result<f64, FitError> {
+        match self {
+            Metric::Rmse     => rmse(actual, predicted),
+            Metric::Mae      => mae(actual, predicted),
+            Metric::Mape     => mape(actual, predicted),
+            Metric::RSquared => r_squared(actual, predicted),
+        }
+    }
+
+    /// R² is a score to maximise; the others are errors to minimise
+    pub fn greater_is_better(self) -> bool {
+        self == Metric::RSquared
+    }
+}
+
+fn check_pairs(actual: &[f64], predicted: &[f64]) -> Result<f64, FitError> {
+    if actual.is_empty() { return Err(FitError::EmptyData); }
+    if actual.len() != predicted.len() { return Err(FitError::DimensionMismatch); }
+    Ok(actual.len() as f64)
+}
+
+/// Root mean squared error, in the units of y
+pub fn rmse(actual: &[f64], predicted: &[f64]) -> Result<f64, FitError> {
+    let n = check_pairs(actual, predicted)?;
+    Ok((actual.iter().zip(predicted).map(|(a, p)| (a - p).powi(2)).sum::<f64>() / n).sqrt())
+}
+
+/// Mean absolute error - less swayed by a few large misses than RMSE
+pub fn mae(actual: &[f64], predicted: &[f64]) -> Result<f64, FitError> {
+    let n = check_pairs(actual, predicted)?;
+    Ok(actual.iter().zip(predicted).map(|(a, p)| (a - p).abs()).sum::<f64>() / n)
+}
+
+/// Mean absolute percentage error, as a percentage. Undefined when any
+/// actual value is zero, which is reported as an error.
+pub fn mape(actual: &[f64], predicted: &[f64]) -> Result<f64, FitError> {
+    let n = check_pairs(actual, predicted)?;
+    if actual.contains(&0.0) { return Err(FitError::InvalidParameter("MAPE is undefined when an actual value is zero")); }
+    Ok(100.0 * actual.iter().zip(predicted).map(|(a, p)| ((a - p) / a).abs()).sum::<f64>() / n)
+}
+
+/// 1 − SS_res / SS_tot around the mean of `actual`. Out of sample it can be
+/// negative: the model predicted worse than the test set's own mean.
+pub fn r_squared(actual: &[f64], predicted: &[f64]) -> Result<f64, FitError> {
+    let n = check_pairs(actual, predicted)?;
+    let mean = actual.iter().sum::<f64>() / n;
+    let ss_tot: f64 = actual.iter().map(|a| (a - mean).powi(2)).sum();
+    let ss_res: f64 = actual.iter().zip(predicted).map(|(a, p)| (a - p).powi(2)).sum();
+    Ok(if ss_tot == 0.0 { if ss_res == 0.0 { 1.0 } else { f64::NEG_INFINITY } } else { 1.0 - ss_res / ss_tot })
+}
+
+/// Per-fold scores and their summary
+#[derive(Clone, Debug, PartialEq)]
+pub struct CvScores {
+    pub scores: Vec<f64>,
+    pub mean: f64,
+    /// Sample standard deviation across folds; 0 for a single fold
+    pub std_dev: f64,
+}
+
+/// Fit `estimator` on each fold's training rows and score its predictions
+/// on the test rows. The estimator is any function with `fit`'s signature
+/// returning a `Regressor`: `fit`, `|x, y| ridge(x, y, 0.1)`, or a closure
+/// that fits a whole `Pipeline`.
+pub fn cross_validate<M, F>(x: &[Vec<f64>], y: &[f64], folds: &[Fold], estimator: F, metric: Metric) -> Result<CvScores, FitError>
+where
+    M: Regressor,
+    F: Fn(&[Vec<f64>], &[f64]) -> Result<M, FitError>,
+{
+    if folds.is_empty() { return Err(FitError::EmptyData); }
+    if x.len() != y.len() { return Err(FitError::DimensionMismatch); }
+    let scores = folds.iter().map(|fold| {
+        let d = fold.select(x, y);
+        let model = estimator(&d.x_train, &d.y_train)?;
+        let predicted = d.x_test.iter().map(|row| model.predict_row(row)).collect::<Result<Vec<_>, _>>()?;
+        metric.score(&d.y_test, &predicted)
+    }).collect::<Result<Vec<f64>, FitError>>()?;
+    let k = scores.len() as f64;
+    let mean = scores.iter().sum::<f64>() / k;
+    let std_dev = if scores.len() < 2 { 0.0 } else { (scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (k - 1.0)).sqrt() };
+    Ok(CvScores { scores, mean, std_dev })
+}
+
+/// Cross-validated scores for every candidate, and which one won
+#[derive(Clone, Debug)]
+pub struct GridSearch<P> {
+    pub results: Vec<(P, CvScores)>,
+    /// Index into `results` of the best mean score
+    pub best: usize,
+}
+
+impl<P> GridSearch<P> {
+    pub fn best_params(&self) -> &P {
+        &self.results[self.best].0
+    }
+
+    pub fn best_scores(&self) -> &CvScores {
+        &self.results[self.best].1
+    }
+}
+
+/// Cross-validate the model built from each candidate in `params` on the same
+/// folds and pick the best mean score, e.g. ridge over a λ grid:
+/// `grid_search(&x, &y, &folds, &lambdas, |&l, x, y| ridge(x, y, l), Metric::Rmse)`.
+/// Ties go to the earlier candidate, so list simpler models first.
+pub fn grid_search<P, M, F>(x: &[Vec<f64>], y: &[f64], folds: &[Fold], params: &[P], build: F, metric: Metric) -> Result<GridSearch<P>, FitError>
+where
+    P: Clone,
+    M: Regressor,
+    F: Fn(&P, &[Vec<f64>], &[f64]) -> Result<M, FitError>,
+{
+    if params.is_empty() { return Err(FitError::InvalidParameter("grid search needs at least one candidate")); }
+    let results = params.iter()
+        .map(|p| cross_validate(x, y, folds, |xt, yt| build(p, xt, yt), metric).map(|s| (p.clone(), s)))
+        .collect::<Result<Vec<_>, FitError>>()?;
+    let better = |a: f64, b: f64| if metric.greater_is_better() { a > b } else { a < b };
+    let best = (1..results.len()).fold(0, |best, i| if better(results[i].1.mean, results[best].1.mean) { i } else { best });
+    Ok(GridSearch { results, best })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{model_from_coefficients, ridge};
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    fn covers_every_row_once(folds: &[Fold], n: usize) {
+        let mut seen: Vec<usize> = folds.iter().flat_map(|f| f.test.iter().copied()).collect();
+        seen.sort_unstable();
+        assert_eq!(seen, (0..n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn train_test_split_is_seeded_and_disjoint() {
+        let a = train_test_split(50, 0.2, 7).unwrap();
+        assert_eq!(a, train_test_split(50, 0.2, 7).unwrap());
+        assert_ne!(a, train_test_split(50, 0.2, 8).unwrap());
+        assert_eq!((a.train.len(), a.test.len()), (40, 10));
+        let mut all: Vec<usize> = a.train.iter().chain(&a.test).copied().collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..50).collect::<Vec<_>>());
+        assert!(matches!(train_test_split(50, 1.0, 7), Err(FitError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn k_fold_partitions_the_rows() {
+        let folds = k_fold(10, 3, None).unwrap();
+        let sizes: Vec<usize> = folds.iter().map(|f| f.test.len()).collect();
+        assert_eq!(sizes, vec![4, 3, 3]);
+        assert_eq!(folds[1].test, vec![4, 5, 6]);
+        assert_eq!(folds[1].train, vec![0, 1, 2, 3, 7, 8, 9]);
+        covers_every_row_once(&folds, 10);
+        let shuffled = k_fold(10, 3, Some(1)).unwrap();
+        covers_every_row_once(&shuffled, 10);
+        assert_ne!(shuffled, folds);
+        assert_eq!(k_fold(2, 3, None).unwrap_err(), FitError::InsufficientData { needed: 3, got: 2 });
+    }
+
+    #[test]
+    fn time_series_split_never_trains_on_the_future() {
+        let folds = time_series_split(10, 3, 0).unwrap();
+        assert_eq!(folds[0], Fold { train: (0..4).collect(), test: vec![4, 5] });
+        assert_eq!(folds[2], Fold { train: (0..8).collect(), test: vec![8, 9] });
+        let gapped = time_series_split(10, 3, 1).unwrap();
+        assert_eq!(gapped[0].train, vec![0, 1, 2]);
+        for fold in &gapped {
+            assert!(fold.train.iter().max() < fold.test.iter().min());
+        }
+        assert!(time_series_split(3, 3, 0).is_err());
+        assert!(matches!(time_series_split(10, usize::MAX, 0), Err(FitError::InvalidParameter(_))));
+        assert!(matches!(time_series_split(10, 3, usize::MAX), Err(FitError::InsufficientData { needed: usize::MAX, got: 10 })));
+    }
+
+    #[test]
+    fn metrics_match_hand_computation() {
+        let actual = [2.0, 4.0, 6.0, 8.0];
+        let predicted = [3.0, 4.0, 5.0, 10.0];
+        assert!(close(rmse(&actual, &predicted).unwrap(), 1.5_f64.sqrt()));
+        assert!(close(mae(&actual, &predicted).unwrap(), 1.0));
+        // (50% + 0% + 16.67% + 25%) / 4
+        assert!(close(mape(&actual, &predicted).unwrap(), 100.0 * (0.5 + 1.0 / 6.0 + 0.25) / 4.0));
+        assert!(close(r_squared(&actual, &predicted).unwrap(), 1.0 - 6.0 / 20.0));
+        assert!(mape(&[0.0, 1.0], &[1.0, 1.0]).is_err());
+        assert_eq!(rmse(&actual, &predicted[..3]).unwrap_err(), FitError::DimensionMismatch);
+    }
+
+    #[test]
+    fn cross_validation_of_an_exact_model_scores_perfectly() {
+        let x: Vec<Vec<f64>> = (0..20).map(|i| vec![i as f64, (i % 3) as f64]).collect();
+        let y: Vec<f64> = x.iter().map(|r| 1.0 + 2.0 * r[0] - r[1]).collect();
+        let folds = k_fold(20, 4, Some(3)).unwrap();
+        let cv = cross_validate(&x, &y, &folds, |x, y| ridge(x, y, 0.0), Metric::Rmse).unwrap();
+        assert_eq!(cv.scores.len(), 4);
+        assert!(cv.mean < 1e-9, "{cv:?}");
+        // A fixed model that ignores the data is scored as it stands
+        let flat = |x: &[Vec<f64>], y: &[f64]| Ok(model_from_coefficients(x, y, vec![0.0, 0.0, 0.0]));
+        assert!(cross_validate(&x, &y, &folds, flat, Metric::RSquared).unwrap().mean < 0.0);
+    }
+
+    #[test]
+    fn grid_search_picks_the_best_lambda() {
+        // Noisy linear data: heavy shrinkage is clearly worse than light
+        let x: Vec<Vec<f64>> = (0..40).map(|i| vec![i as f64 / 4.0]).collect();
+        let y: Vec<f64> = x.iter().enumerate().map(|(i, r)| 3.0 * r[0] + if i % 2 == 0 { 0.5 } else { -0.5 }).collect();
+        let folds = k_fold(40, 5, Some(11)).unwrap();
+        let lambdas = [1000.0, 10.0, 0.01];
+        let search = grid_search(&x, &y, &folds, &lambdas, |&l, x, y| ridge(x, y, l), Metric::Rmse).unwrap();
+        assert_eq!(*search.best_params(), 0.01);
+        assert_eq!(search.results.len(), 3);
+        assert!(search.results[0].1.mean > search.best_scores().mean);
+
+        let by_r2 = grid_search(&x, &y, &folds, &lambdas, |&l, x, y| ridge(x, y, l), Metric::RSquared).unwrap();
+        assert_eq!(by_r2.best, 2);
+    }
+}
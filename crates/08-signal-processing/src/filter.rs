@@ -0,0 +1,537 @@
+//! Digital filter design and application.
+//!
+//! Two families, with different trade-offs:
+//! - **FIR** by windowed sinc: truncate the ideal (infinitely long) impulse
+//!   response and taper it with a window. Always stable and exactly linear
+//!   phase, but a sharp transition needs many taps.
+//! - **IIR** Butterworth (maximally flat pass band) and Chebyshev type I
+//!   (equiripple pass band, steeper roll-off). Designed as an analogue
+//!   prototype, moved to the requested band, then mapped to z by the bilinear
+//!   transform with pre-warped edges. The result is returned as cascaded
+//!   biquads: one high-order polynomial loses its roots to rounding, while
+//!   second-order sections each keep theirs.
+//!
+//! `lfilter` runs a filter forwards and is causal, so it delays the signal.
+//! `filtfilt` runs it forwards and then backwards, which cancels the phase,
+//! squares the magnitude response and needs the whole signal up front.
+
+use std::f64::consts::PI;
+
+use matrix_math::Matrix;
+use rustfft::num_complex::Complex;
+
+use crate::window::Window;
+use crate::SignalError;
+
+/// Which frequencies a filter passes; edges in Hz
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Band {
+    LowPass(f64),
+    HighPass(f64),
+    BandPass(f64, f64),
+    BandStop(f64, f64),
+}
+
+impl Band {
+    fn validate(&self, sample_rate: f64) -> Result<(), SignalError> {
+        if !(sample_rate > 0.0 && sample_rate.is_finite()) {
+            return Err(SignalError::InvalidParameter("sample rate must be positive and finite"));
+        }
+        let inside = |f: f64| f > 0.0 && f < sample_rate / 2.0;
+        match *self {
+            Band::LowPass(f) | Band::HighPass(f) if !inside(f) => {
+                Err(SignalError::InvalidParameter("cut-off must lie strictly between 0 and the Nyquist frequency"))
+            }
+            Band::BandPass(low, high) | Band::BandStop(low, high) if !(inside(low) && inside(high) && low < high) => {
+                Err(SignalError::InvalidParameter("band edges must satisfy 0 < low < high < Nyquist"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for Band {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Band::LowPass(edge)        => write!(f, "low-pass {edge} Hz"),
+            Band::HighPass(edge)       => write!(f, "high-pass {edge} Hz"),
+            Band::BandPass(low, high)  => write!(f, "band-pass {low}-{high} Hz"),
+            Band::BandStop(low, high)  => write!(f, "band-stop {low}-{high} Hz"),
+        }
+    }
+}
+
+/// Windowed-sinc FIR taps, scaled to unit gain in the middle of the pass
+/// band. The -6 dB point sits at each edge; the window sets how deep the
+/// stop band goes and the tap count how narrow the transition is (about
+/// 3.3 · fs / taps for Hamming).
+pub fn fir_design(taps: usize, band: Band, sample_rate: f64, window: Window) -> Result<Vec<f64>, SignalError> {
+    band.validate(sample_rate)?;
+    if taps == 0 { return Err(SignalError::InvalidParameter("an FIR filter needs at least one tap")); }
+    // An even-length symmetric filter always has a zero at Nyquist
+    if matches!(band, Band::HighPass(_) | Band::BandStop(..)) && taps.is_multiple_of(2) {
+        return Err(SignalError::InvalidParameter("high-pass and band-stop FIR filters need an odd number of taps"));
+    }
+    let centre = (taps - 1) as f64 / 2.0;
+    // Ideal low-pass impulse response, cut-off f, delayed to the centre tap
+    let low_pass = |f: f64, i: usize| {
+        let (fc, t) = (f / sample_rate, i as f64 - centre);
+        if t == 0.0 { 2.0 * fc } else { (2.0 * PI * fc * t).sin() / (PI * t) }
+    };
+    let impulse = |i: usize| if i as f64 == centre { 1.0 } else { 0.0 };
+    let w = window.symmetric(taps);
+    let mut h: Vec<f64> = (0..taps).map(|i| w[i] * match band {
+        Band::LowPass(f)           => low_pass(f, i),
+        Band::HighPass(f)          => impulse(i) - low_pass(f, i),
+        Band::BandPass(low, high)  => low_pass(high, i) - low_pass(low, i),
+        Band::BandStop(low, high)  => impulse(i) - low_pass(high, i) + low_pass(low, i),
+    }).collect();
+    let reference = match band {
+        Band::LowPass(_) | Band::BandStop(..) => 0.0,
+        Band::HighPass(_)                     => sample_rate / 2.0,
+        Band::BandPass(low, high)             => (low + high) / 2.0,
+    };
+    let gain = freqz(&h, &[1.0], &[reference], sample_rate)[0].norm();
+    for v in &mut h { *v /= gain; }
+    Ok(h)
+}
+
+/// One second-order section: b₀ + b₁z⁻¹ + b₂z⁻² over 1 + a₁z⁻¹ + a₂z⁻²
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Biquad {
+    pub b: [f64; 3],
+    pub a: [f64; 3],
+}
+
+/// An IIR filter as a cascade of biquads (second-order sections)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sos {
+    pub sections: Vec<Biquad>,
+}
+
+impl Sos {
+    /// Run the cascade forwards from rest
+    pub fn filter(&self, x: &[f64]) -> Vec<f64> {
+        self.sections.iter().fold(x.to_vec(), |y, s| run(&s.b, &s.a, &y, vec![0.0; 2]))
+    }
+
+    /// Zero-phase filtering of the whole cascade; see `filtfilt`
+    pub fn filtfilt(&self, x: &[f64]) -> Result<Vec<f64>, SignalError> {
+        let stages: Vec<(Vec<f64>, Vec<f64>)> = self.sections.iter().map(|s| (s.b.to_vec(), s.a.to_vec())).collect();
+        zero_phase(&stages, x, 3 * (2 * self.sections.len() + 1))
+    }
+
+    /// Complex response H(e^{jω}) at each frequency in Hz
+    pub fn response(&self, freqs: &[f64], sample_rate: f64) -> Vec<Complex<f64>> {
+        let mut h = vec![Complex::new(1.0, 0.0); freqs.len()];
+        for s in &self.sections {
+            for (total, part) in h.iter_mut().zip(freqz(&s.b, &s.a, freqs, sample_rate)) { *total *= part; }
+        }
+        h
+    }
+}
+
+/// Butterworth filter of the given order (twice that for band filters).
+/// The response is down exactly 3 dB at each band edge.
+pub fn butterworth(order: usize, band: Band, sample_rate: f64) -> Result<Sos, SignalError> {
+    design_iir(order, Prototype::Butterworth, band, sample_rate)
+}
+
+/// Chebyshev type I filter: the pass band ripples by `ripple_db` and the
+/// response is down exactly `ripple_db` at each band edge
+pub fn chebyshev1(order: usize, ripple_db: f64, band: Band, sample_rate: f64) -> Result<Sos, SignalError> {
+    if !(ripple_db > 0.0 && ripple_db.is_finite()) {
+        return Err(SignalError::InvalidParameter("pass-band ripple must be positive and finite"));
+    }
+    design_iir(order, Prototype::Chebyshev(ripple_db), band, sample_rate)
+}
+
+/// Apply the filter b(z)/a(z) to `x` from rest (direct form II transposed).
+/// FIR taps are `b` with `a = [1.0]`.
+pub fn lfilter(b: &[f64], a: &[f64], x: &[f64]) -> Result<Vec<f64>, SignalError> {
+    let (b, a) = normalise(b, a)?;
+    let state = vec![0.0; b.len() - 1];
+    Ok(run(&b, &a, x, state))
+}
+
+/// Zero-phase filtering: forwards, then backwards over the result. Each end
+/// is padded with a point-reflected copy of the signal, and the filter state
+/// starts at its steady state for that edge value, so the output does not
+/// ring at the boundaries. Needs more than 3 · max(len(a), len(b)) samples.
+pub fn filtfilt(b: &[f64], a: &[f64], x: &[f64]) -> Result<Vec<f64>, SignalError> {
+    let (b, a) = normalise(b, a)?;
+    let pad = 3 * b.len();
+    zero_phase(&[(b, a)], x, pad)
+}
+
+/// Complex response b(e^{jω}) / a(e^{jω}) at each frequency in Hz
+pub fn freqz(b: &[f64], a: &[f64], freqs: &[f64], sample_rate: f64) -> Vec<Complex<f64>> {
+    freqs.iter().map(|&f| {
+        let z_inv = Complex::from_polar(1.0, -2.0 * PI * f / sample_rate);
+        // Horner's rule in z⁻¹
+        let poly = |c: &[f64]| c.iter().rev().fold(Complex::new(0.0, 0.0), |acc, &v| acc * z_inv + v);
+        poly(b) / poly(a)
+    }).collect()
+}
+
+/// Pad b and a to the same length and divide through by a₀
+fn normalise(b: &[f64], a: &[f64]) -> Result<(Vec<f64>, Vec<f64>), SignalError> {
+    if b.is_empty() || a.is_empty() { return Err(SignalError::InvalidParameter("filter coefficients must not be empty")); }
+    if a[0] == 0.0 { return Err(SignalError::InvalidParameter("a[0] must be non-zero")); }
+    let n = b.len().max(a.len());
+    let pad = |c: &[f64]| (0..n).map(|i| c.get(i).map_or(0.0, |v| v / a[0])).collect::<Vec<f64>>();
+    Ok((pad(b), pad(a)))
+}
+
+/// Direct form II transposed, with `state` (length n - 1) as the initial
+/// delay line; b and a are normalised and of equal length n
+fn run(b: &[f64], a: &[f64], x: &[f64], mut state: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
+    x.iter().map(|&xi| {
+        let yi = b[0] * xi + state.first().copied().unwrap_or(0.0);
+        for j in 1..n {
+            let next = if j < n - 1 { state[j] } else { 0.0 };
+            state[j - 1] = b[j] * xi - a[j] * yi + next;
+        }
+        yi
+    }).collect()
+}
+
+/// The delay-line state a filter settles into under a constant unit input:
+/// solves (I − Aᵀ) z = b[1..] − a[1..] b₀, with A the companion matrix of a
+fn steady_state(b: &[f64], a: &[f64]) -> Vec<f64> {
+    let m = b.len() - 1;
+    if m == 0 { return Vec::new(); }
+    let mut system = Matrix::new(m, m);
+    for i in 0..m {
+        system[(i, i)] = 1.0;
+        system[(i, 0)] += a[i + 1];
+        if i + 1 < m { system[(i, i + 1)] -= 1.0; }
+    }
+    let rhs: Vec<f64> = (0..m).map(|i| b[i + 1] - a[i + 1] * b[0]).collect();
+    // A pole at z = 1 has no steady state; start from rest instead
+    system.solve(&rhs).unwrap_or_else(|| vec![0.0; m])
+}
+
+/// Forward-backward filtering through a chain of (b, a) stages
+fn zero_phase(stages: &[(Vec<f64>, Vec<f64>)], x: &[f64], pad: usize) -> Result<Vec<f64>, SignalError> {
+    let n = x.len();
+    if n <= pad { return Err(SignalError::SignalTooShort { needed: pad + 1, got: n }); }
+    // Later stages see the earlier ones' DC gain times the edge value
+    let mut scale = 1.0;
+    let initial: Vec<Vec<f64>> = stages.iter().map(|(b, a)| {
+        let zi = steady_state(b, a).into_iter().map(|v| v * scale).collect();
+        scale *= b.iter().sum::<f64>() / a.iter().sum::<f64>();
+        zi
+    }).collect();
+    let pass = |signal: Vec<f64>| {
+        let edge = signal[0];
+        stages.iter().zip(&initial).fold(signal, |y, ((b, a), zi)| run(b, a, &y, zi.iter().map(|v| v * edge).collect()))
+    };
+
+    let (first, last) = (x[0], x[n - 1]);
+    let extended: Vec<f64> = (1..=pad).rev().map(|i| 2.0 * first - x[i])
+        .chain(x.iter().copied())
+        .chain((1..=pad).map(|i| 2.0 * last - x[n - 1 - i]))
+        .collect();
+    let forward = pass(extended);
+    let backward = pass(forward.into_iter().rev().collect());
+    Ok(backward.into_iter().rev().skip(pad).take(n).collect())
+}
+
+#[derive(Clone, Copy)]
+enum Prototype {
+    Butterworth,
+    Chebyshev(f64),
+}
+
+/// A transfer function by its zeros, poles and gain
+struct Zpk {
+    zeros: Vec<Complex<f64>>,
+    poles: Vec<Complex<f64>>,
+    gain: f64,
+}
+
+fn design_iir(order: usize, prototype: Prototype, band: Band, sample_rate: f64) -> Result<Sos, SignalError> {
+    band.validate(sample_rate)?;
+    if order == 0 { return Err(SignalError::InvalidParameter("filter order must be at least 1")); }
+    let fs2 = 2.0 * sample_rate;
+    // The bilinear transform squeezes the whole analogue axis into 0..Nyquist;
+    // pre-warping the edges puts them back where they were asked for
+    let warp = |f: f64| fs2 * (PI * f / sample_rate).tan();
+    let analogue = analogue_prototype(order, prototype);
+    let analogue = match band {
+        Band::LowPass(f)          => to_low_pass(analogue, warp(f)),
+        Band::HighPass(f)         => to_high_pass(analogue, warp(f)),
+        Band::BandPass(low, high) => to_band(analogue, warp(low), warp(high), false),
+        Band::BandStop(low, high) => to_band(analogue, warp(low), warp(high), true),
+    };
+    Ok(to_sections(bilinear(analogue, fs2)))
+}
+
+/// Normalised analogue low-pass with its band edge at 1 rad/s
+fn analogue_prototype(order: usize, prototype: Prototype) -> Zpk {
+    let n = order as f64;
+    // Poles sit on the left half of a circle (Butterworth) or an ellipse (Chebyshev)
+    let angles = (0..order).map(|k| PI * (2.0 * k as f64 - n + 1.0) / (2.0 * n));
+    match prototype {
+        Prototype::Butterworth => Zpk {
+            zeros: Vec::new(),
+            poles: angles.map(|t| -Complex::from_polar(1.0, t)).collect(),
+            gain: 1.0,
+        },
+        Prototype::Chebyshev(ripple_db) => {
+            let eps = (10f64.powf(ripple_db / 10.0) - 1.0).sqrt();
+            let mu = (1.0 / eps).asinh() / n;
+            let poles: Vec<Complex<f64>> = angles.map(|t| -Complex::new(mu, t).sinh()).collect();
+            let mut gain = poles.iter().fold(Complex::new(1.0, 0.0), |acc, p| acc * -p).re;
+            // Even orders start at the bottom of the ripple, not the top
+            if order.is_multiple_of(2) { gain /= (1.0 + eps * eps).sqrt(); }
+            Zpk { zeros: Vec::new(), poles, gain }
+        }
+    }
+}
+
+fn product(roots: &[Complex<f64>], f: impl Fn(Complex<f64>) -> Complex<f64>) -> Complex<f64> {
+    roots.iter().fold(Complex::new(1.0, 0.0), |acc, &r| acc * f(r))
+}
+
+/// s → s / ω
+fn to_low_pass(proto: Zpk, w: f64) -> Zpk {
+    let degree = (proto.poles.len() - proto.zeros.len()) as i32;
+    Zpk {
+        zeros: proto.zeros.iter().map(|z| z * w).collect(),
+        poles: proto.poles.iter().map(|p| p * w).collect(),
+        gain: proto.gain * w.powi(degree),
+    }
+}
+
+/// s → ω / s; zeros at infinity move to the origin
+fn to_high_pass(proto: Zpk, w: f64) -> Zpk {
+    let degree = proto.poles.len() - proto.zeros.len();
+    let gain = proto.gain * (product(&proto.zeros, |z| -z) / product(&proto.poles, |p| -p)).re;
+    let mut zeros: Vec<Complex<f64>> = proto.zeros.iter().map(|z| w / z).collect();
+    zeros.extend(std::iter::repeat_n(Complex::new(0.0, 0.0), degree));
+    Zpk { zeros, poles: proto.poles.iter().map(|p| w / p).collect(), gain }
+}
+
+/// s → (s² + ω₀²) / (s · bw) for a band pass, or its reciprocal for a band
+/// stop. Every root splits in two, doubling the order.
+fn to_band(proto: Zpk, low: f64, high: f64, stop: bool) -> Zpk {
+    let (w0, bw) = ((low * high).sqrt(), high - low);
+    let degree = proto.poles.len() - proto.zeros.len();
+    let split = |roots: &[Complex<f64>]| -> Vec<Complex<f64>> {
+        let scaled = roots.iter().map(|&r| if stop { (bw / 2.0) / r } else { r * (bw / 2.0) });
+        scaled.flat_map(|r| {
+            let d = (r * r - w0 * w0).sqrt();
+            [r + d, r - d]
+        }).collect()
+    };
+    let (mut zeros, poles) = (split(&proto.zeros), split(&proto.poles));
+    let gain = if stop {
+        // Zeros at infinity become notches at ±jω₀
+        zeros.extend((0..degree).flat_map(|_| [Complex::new(0.0, w0), Complex::new(0.0, -w0)]));
+        proto.gain * (product(&proto.zeros, |z| -z) / product(&proto.poles, |p| -p)).re
+    } else {
+        zeros.extend(std::iter::repeat_n(Complex::new(0.0, 0.0), degree));
+        proto.gain * bw.powi(degree as i32)
+    };
+    Zpk { zeros, poles, gain }
+}
+
+/// s → fs2 · (z − 1) / (z + 1); zeros at infinity land on Nyquist (z = −1)
+fn bilinear(analogue: Zpk, fs2: f64) -> Zpk {
+    let degree = analogue.poles.len() - analogue.zeros.len();
+    let map = |r: &Complex<f64>| (fs2 + r) / (fs2 - r);
+    let mut zeros: Vec<Complex<f64>> = analogue.zeros.iter().map(map).collect();
+    zeros.extend(std::iter::repeat_n(Complex::new(-1.0, 0.0), degree));
+    let gain = analogue.gain * (product(&analogue.zeros, |z| fs2 - z) / product(&analogue.poles, |p| fs2 - p)).re;
+    Zpk { zeros, poles: analogue.poles.iter().map(map).collect(), gain }
+}
+
+/// Split roots into conjugate pairs and leftover reals, two to a group
+fn group_roots(roots: &[Complex<f64>]) -> Vec<Vec<Complex<f64>>> {
+    let is_real = |r: &Complex<f64>| r.im.abs() <= 1e-10 * r.norm().max(1.0);
+    let mut groups: Vec<Vec<Complex<f64>>> = roots.iter()
+        .filter(|r| !is_real(r) && r.im > 0.0)
+        .map(|&r| vec![r, r.conj()])
+        .collect();
+    let mut reals: Vec<Complex<f64>> = roots.iter().filter(|r| is_real(r)).map(|r| Complex::new(r.re, 0.0)).collect();
+    reals.sort_by(|a, b| a.re.total_cmp(&b.re));
+    groups.extend(reals.chunks(2).map(|c| c.to_vec()));
+    groups
+}
+
+/// Coefficients of Π (1 − r z⁻¹), padded to three
+fn polynomial(roots: &[Complex<f64>]) -> [f64; 3] {
+    match roots {
+        [r]        => [1.0, -r.re, 0.0],
+        [r1, r2]   => [1.0, -(r1 + r2).re, (r1 * r2).re],
+        _          => [1.0, 0.0, 0.0],
+    }
+}
+
+/// Pair each pole group with the nearest zero group of the same size, so
+/// each section's peak is tempered by its own zeros, and fold the gain
+/// into the first section
+fn to_sections(digital: Zpk) -> Sos {
+    let mut poles = group_roots(&digital.poles);
+    let mut zeros = group_roots(&digital.zeros);
+    // Sections nearest the unit circle are the most resonant; place them last
+    poles.sort_by(|a, b| a[0].norm().total_cmp(&b[0].norm()));
+    let mut sections: Vec<Biquad> = poles.iter().map(|group| {
+        let nearest = zeros.iter().enumerate()
+            .filter(|(_, z)| z.len() == group.len())
+            .min_by(|(_, a), (_, b)| (a[0] - group[0]).norm().total_cmp(&(b[0] - group[0]).norm()))
+            .or_else(|| zeros.iter().enumerate().next())
+            .map(|(i, _)| i);
+        let b = nearest.map_or([1.0, 0.0, 0.0], |i| polynomial(&zeros.remove(i)));
+        Biquad { b, a: polynomial(group) }
+    }).collect();
+    if let Some(first) = sections.first_mut() {
+        for v in &mut first.b { *v *= digital.gain; }
+    }
+    Sos { sections }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FS: f64 = 1000.0;
+
+    fn gain(h: &[Complex<f64>]) -> Vec<f64> {
+        h.iter().map(|c| c.norm()).collect()
+    }
+
+    fn fir_gain(taps: &[f64], f: f64) -> f64 {
+        freqz(taps, &[1.0], &[f], FS)[0].norm()
+    }
+
+    #[test]
+    fn fir_low_pass_is_linear_phase_with_a_deep_stop_band() {
+        let h = fir_design(101, Band::LowPass(100.0), FS, Window::Hamming).unwrap();
+        for i in 0..101 { assert!((h[i] - h[100 - i]).abs() < 1e-15); }
+        assert!((fir_gain(&h, 0.0) - 1.0).abs() < 1e-12);
+        assert!((fir_gain(&h, 100.0) - 0.5).abs() < 0.01, "edge gain {}", fir_gain(&h, 100.0));
+        for f in (0..=60).map(f64::from) { assert!((fir_gain(&h, f) - 1.0).abs() < 0.005, "{f} Hz"); }
+        // Hamming reaches -53 dB one transition width (3.3 fs / taps) past the edge
+        for f in (140..=500).map(f64::from) {
+            assert!(20.0 * fir_gain(&h, f).log10() < -50.0, "{f} Hz at {:.1} dB", 20.0 * fir_gain(&h, f).log10());
+        }
+    }
+
+    #[test]
+    fn fir_high_band_pass_and_band_stop_respond_where_expected() {
+        let high = fir_design(101, Band::HighPass(200.0), FS, Window::Blackman).unwrap();
+        assert!((fir_gain(&high, 500.0) - 1.0).abs() < 1e-12 && fir_gain(&high, 50.0) < 1e-3);
+        let pass = fir_design(151, Band::BandPass(100.0, 200.0), FS, Window::Hamming).unwrap();
+        assert!((fir_gain(&pass, 150.0) - 1.0).abs() < 1e-12);
+        assert!(fir_gain(&pass, 20.0) < 3e-3 && fir_gain(&pass, 300.0) < 3e-3);
+        let stop = fir_design(151, Band::BandStop(100.0, 200.0), FS, Window::Hamming).unwrap();
+        assert!((fir_gain(&stop, 0.0) - 1.0).abs() < 1e-12 && fir_gain(&stop, 150.0) < 3e-3);
+        assert!((fir_gain(&stop, 400.0) - 1.0).abs() < 3e-3);
+        assert!(matches!(fir_design(100, Band::HighPass(200.0), FS, Window::Hann), Err(SignalError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn butterworth_is_three_db_down_at_the_edge_and_monotonic() {
+        let low = butterworth(5, Band::LowPass(100.0), FS).unwrap();
+        assert_eq!(low.sections.len(), 3);
+        let freqs: Vec<f64> = (0..500).map(f64::from).collect();
+        let g = gain(&low.response(&freqs, FS));
+        assert!((g[0] - 1.0).abs() < 1e-12);
+        assert!((g[100].powi(2) - 0.5).abs() < 1e-12, "|H(fc)|² = {}", g[100].powi(2));
+        assert!(g.windows(2).all(|w| w[1] <= w[0] + 1e-15));
+        let high = butterworth(4, Band::HighPass(100.0), FS).unwrap();
+        let g = gain(&high.response(&[0.0, 100.0, 500.0], FS));
+        assert!(g[0] < 1e-12 && (g[1].powi(2) - 0.5).abs() < 1e-12 && (g[2] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn chebyshev_ripples_within_its_bound_and_rolls_off_faster() {
+        let ripple = 0.5;
+        let floor = 10f64.powf(-ripple / 20.0);
+        for order in [4, 5] {
+            let cheby = chebyshev1(order, ripple, Band::LowPass(100.0), FS).unwrap();
+            let pass: Vec<f64> = (0..=1000).map(|i| i as f64 / 10.0).collect();
+            let g = gain(&cheby.response(&pass, FS));
+            let (max, min) = g.iter().fold((0.0f64, 1.0f64), |(hi, lo), &v| (hi.max(v), lo.min(v)));
+            // The grid only samples the ripple peaks, so allow for missing the tops
+            assert!(max <= 1.0 + 1e-12 && max > 1.0 - 1e-6, "order {order}: peak {max}");
+            assert!(min >= floor - 1e-12 && min < floor + 1e-6, "order {order}: trough {min}");
+            assert!((g[1000] - floor).abs() < 1e-12);
+            let butter = butterworth(order, Band::LowPass(100.0), FS).unwrap();
+            // Trading flatness for steepness: at twice the cut-off, over 6 dB further down
+            assert!(cheby.response(&[200.0], FS)[0].norm() < butter.response(&[200.0], FS)[0].norm() / 2.0);
+        }
+    }
+
+    #[test]
+    fn band_designs_double_the_order() {
+        let pass = butterworth(3, Band::BandPass(50.0, 150.0), FS).unwrap();
+        assert_eq!(pass.sections.len(), 3);
+        let g = gain(&pass.response(&[0.0, 50.0, 150.0, 499.0], FS));
+        assert!(g[0] < 1e-12 && g[3] < 1e-6);
+        assert!((g[1].powi(2) - 0.5).abs() < 1e-10 && (g[2].powi(2) - 0.5).abs() < 1e-10);
+        // The notch sits at the warped geometric centre of the edges
+        let warp = |f: f64| (PI * f / FS).tan();
+        let notch = FS / PI * (warp(50.0) * warp(150.0)).sqrt().atan();
+        let stop = chebyshev1(2, 1.0, Band::BandStop(50.0, 150.0), FS).unwrap();
+        let g = gain(&stop.response(&[0.0, notch, 499.0], FS));
+        assert!(g[1] < 1e-9, "notch gain {}", g[1]);
+        assert!((g[0] - 10f64.powf(-1.0 / 20.0)).abs() < 1e-9 && g[2] > 0.85);
+    }
+
+    #[test]
+    fn lfilter_matches_convolution_and_the_cascade() {
+        let x: Vec<f64> = (0..50).map(|i| ((i * 7) % 11) as f64 - 5.0).collect();
+        let taps = [0.25, 0.5, 0.25];
+        let y = lfilter(&taps, &[1.0], &x).unwrap();
+        for n in 0..x.len() {
+            let direct: f64 = (0..3).filter(|&k| k <= n).map(|k| taps[k] * x[n - k]).sum();
+            assert!((y[n] - direct).abs() < 1e-12);
+        }
+        // y[n] = x[n] + 0.5 y[n-1], written with a[0] = 2
+        let impulse: Vec<f64> = (0..10).map(|i| if i == 0 { 1.0 } else { 0.0 }).collect();
+        let decay = lfilter(&[2.0], &[2.0, -1.0], &impulse).unwrap();
+        assert!(decay.iter().enumerate().all(|(i, v)| (v - 0.5f64.powi(i as i32)).abs() < 1e-15));
+        // The cascade equals one filter with the multiplied-out polynomials
+        let sos = butterworth(4, Band::LowPass(120.0), FS).unwrap();
+        let multiply = |p: &[f64], q: &[f64; 3]| {
+            let mut r = vec![0.0; p.len() + 2];
+            for (i, a) in p.iter().enumerate() { for (j, b) in q.iter().enumerate() { r[i + j] += a * b; } }
+            r
+        };
+        let (b, a) = sos.sections.iter().fold((vec![1.0], vec![1.0]), |(b, a), s| (multiply(&b, &s.b), multiply(&a, &s.a)));
+        let whole = lfilter(&b, &a, &x).unwrap();
+        assert!(sos.filter(&x).iter().zip(&whole).all(|(p, q)| (p - q).abs() < 1e-9));
+        assert!(matches!(lfilter(&[1.0], &[0.0, 1.0], &x), Err(SignalError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn filtfilt_removes_the_delay_and_the_edge_transient() {
+        let sos = butterworth(4, Band::LowPass(50.0), FS).unwrap();
+        let slow: Vec<f64> = (0..1000).map(|i| (2.0 * PI * 5.0 * i as f64 / FS).sin()).collect();
+        let forward = sos.filter(&slow);
+        let zero_phase = sos.filtfilt(&slow).unwrap();
+        let worst = |y: &[f64]| y[100..900].iter().zip(&slow[100..900]).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+        // Forwards only, the output lags; |H(5 Hz)|² is within 1e-8 of one
+        assert!(worst(&forward) > 0.1);
+        assert!(worst(&zero_phase) < 1e-6, "{}", worst(&zero_phase));
+        // A constant passes straight through, edges included
+        let level = filtfilt(&[0.2, 0.3], &[1.0, -0.5], &[3.0; 40]).unwrap();
+        assert!(level.iter().all(|v| (v - 3.0).abs() < 1e-12));
+        assert_eq!(sos.filtfilt(&[1.0; 10]).unwrap_err(), SignalError::SignalTooShort { needed: 16, got: 10 });
+    }
+
+    #[test]
+    fn invalid_designs_are_rejected() {
+        assert!(butterworth(0, Band::LowPass(100.0), FS).is_err());
+        assert!(butterworth(2, Band::LowPass(500.0), FS).is_err());
+        assert!(butterworth(2, Band::BandPass(200.0, 100.0), FS).is_err());
+        assert!(chebyshev1(2, 0.0, Band::LowPass(100.0), FS).is_err());
+        assert!(fir_design(0, Band::LowPass(100.0), FS, Window::Hann).is_err());
+        assert!(fir_design(11, Band::LowPass(100.0), -1.0, Window::Hann).is_err());
+    }
+}
@@ -20,11 +20,41 @@
 //  Hint: use rustfft's FftPlanner. Magnitude = c.norm() / N.
 // ============================================================
 
+mod filter;
+mod stft;
+mod wav;
+mod window;
+
 use rustfft::{FftPlanner, num_complex::Complex};
 
+pub use filter::{butterworth, chebyshev1, filtfilt, fir_design, freqz, lfilter, Band, Biquad, Sos};
+pub use stft::{istft, stft, Spectrogram, Stft, StftConfig};
+pub use wav::{SampleFormat, Wav, WavError};
+pub use window::Window;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignalError {
+    /// A design or analysis parameter is out of range; names the rule it broke
+    InvalidParameter(&'static str),
+    /// The signal is shorter than the operation needs
+    SignalTooShort { needed: usize, got: usize },
+}
+
+impl std::fmt::Display for SignalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignalError::InvalidParameter(rule) => write!(f, "invalid parameter: {rule}"),
+            SignalError::SignalTooShort { needed, got } => write!(f, "need at least {needed} samples, got {got}"),
+        }
+    }
+}
+
+impl std::error::Error for SignalError {}
+
 /// Apply a Hann window to a signal to reduce spectral leakage
 pub fn hann_window(signal: &[f64]) -> Vec<f64> {
-    todo!()
+    let n = signal.len(); if n < 2 { return signal.to_vec(); }
+    signal.iter().enumerate().map(|(i, x)| x * 0.5 * (1.0 - (2.0 * std::f64::consts::PI * i as f64 / (n - 1) as f64).cos())).collect()
 }
 
 /// Result of an FFT analysis
@@ -42,17 +72,22 @@ pub struct SpectrumAnalysis {
 /// Compute the FFT magnitude spectrum of a real-valued signal.
 /// Returns magnitude for bins 0..=N/2 (positive frequencies only).
 pub fn analyse(signal: &[f64], sample_rate: f64) -> SpectrumAnalysis {
-    todo!()
+    let n = signal.len();
+    let mut buf: Vec<Complex<f64>> = hann_window(signal).into_iter().map(|x| Complex::new(x, 0.0)).collect();
+    FftPlanner::new().plan_fft_forward(n).process(&mut buf);
+    let magnitudes: Vec<f64> = buf[..=n / 2].iter().map(|c| c.norm() / n as f64).collect();
+    let dominant_bin = (1..magnitudes.len()).max_by(|&a, &b| magnitudes[a].partial_cmp(&magnitudes[b]).unwrap()).unwrap_or(0);
+    SpectrumAnalysis { dominant_freq_hz: dominant_bin as f64 * sample_rate / n as f64, magnitudes, dominant_bin, sample_rate }
 }
 
 /// Generate a pure sine wave
 pub fn sine_wave(freq_hz: f64, sample_rate: f64, n_samples: usize) -> Vec<f64> {
-    todo!()
+    (0..n_samples).map(|i| (2.0 * std::f64::consts::PI * freq_hz * i as f64 / sample_rate).sin()).collect()
 }
 
 /// Compute signal RMS (root mean square) - power metric
 pub fn rms(signal: &[f64]) -> f64 {
-    todo!()
+    (signal.iter().map(|x| x * x).sum::<f64>() / signal.len() as f64).sqrt()
 }
 
 #[cfg(test)]
@@ -1,6 +1,165 @@
-use signal_processing::{analyse, rms, sine_wave};
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
+use signal_processing::{
+    analyse, butterworth, chebyshev1, fir_design, freqz, istft, lfilter, rms, sine_wave, stft, Band, SampleFormat,
+    Spectrogram, StftConfig, Wav, Window,
+};
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    U8,
+    S16,
+    S24,
+    S32,
+    F32,
+}
+
+impl From<Format> for SampleFormat {
+    fn from(f: Format) -> Self {
+        match f {
+            Format::U8  => SampleFormat::Int8,
+            Format::S16 => SampleFormat::Int16,
+            Format::S24 => SampleFormat::Int24,
+            Format::S32 => SampleFormat::Int32,
+            Format::F32 => SampleFormat::Float32,
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "signal-processing", about = "Spectrum analysis, filtering and spectrograms")]
+struct Args {
+    /// PCM WAV file to analyse. Runs the ECG and vibration demos when omitted.
+    input: Option<PathBuf>,
+
+    /// Channel to analyse, 0-based
+    #[arg(short, long, default_value_t = 0)]
+    channel: usize,
+
+    /// Start of the time range to analyse, in seconds
+    #[arg(long, default_value_t = 0.0)]
+    start: f64,
+
+    /// End of the time range, in seconds (default: end of file)
+    #[arg(long)]
+    end: Option<f64>,
+
+    /// Remove content above this frequency (Hz) with a zero-phase 4th-order Butterworth
+    #[arg(long)]
+    low_pass: Option<f64>,
+
+    /// Remove content below this frequency (Hz); with --low-pass, keeps the band between
+    #[arg(long)]
+    high_pass: Option<f64>,
+
+    /// Write the time range, every channel filtered, to this WAV file
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Sample format of the output file (default: same as the input)
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// Number of strongest frequency bins to list
+    #[arg(long, default_value_t = 5)]
+    top: usize,
+
+    /// Write a spectrogram here: .csv for a table of dB values, .pgm for a greyscale image
+    #[arg(long)]
+    spectrogram: Option<PathBuf>,
+
+    /// Dynamic range of the spectrogram image in dB; quieter points are black
+    #[arg(long, default_value_t = 80.0)]
+    range_db: f64,
+}
 
 fn main() {
+    let args = Args::parse();
+    let result = match &args.input {
+        Some(path) => run_wav(&args, path),
+        None => {
+            ecg_demo();
+            vibration_demo(&args)
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn run_wav(args: &Args, path: &PathBuf) -> Result<(), Box<dyn Error>> {
+    let wav = Wav::read_file(path)?;
+    let sample_rate = wav.sample_rate as f64;
+    println!("=== {} ===\n", path.display());
+    println!("  {} channel(s), {} Hz, {}, {:.3} s", wav.channels.len(), wav.sample_rate, wav.format, wav.duration_secs());
+
+    let channel = wav.channels.get(args.channel)
+        .ok_or_else(|| format!("channel {} does not exist; the file has {}", args.channel, wav.channels.len()))?;
+    let range = wav.sample_range(args.start, args.end).ok_or_else(|| {
+        format!("time range {}..{} s selects no samples; the file is {:.3} s long",
+            args.start, args.end.map_or("end".to_string(), |e| e.to_string()), wav.duration_secs())
+    })?;
+    let band = match (args.high_pass, args.low_pass) {
+        (None, None)             => None,
+        (None, Some(high))       => Some(Band::LowPass(high)),
+        (Some(low), None)        => Some(Band::HighPass(low)),
+        (Some(low), Some(high))  => Some(Band::BandPass(low, high)),
+    };
+    let filter = band.map(|b| butterworth(4, b, sample_rate)).transpose()?;
+    let process = |x: &[f64]| match &filter {
+        Some(f) => f.filtfilt(x),
+        None => Ok(x.to_vec()),
+    };
+
+    let selected = process(&channel[range.clone()])?;
+    println!("  Channel {}, {:.3}-{:.3} s ({} samples){}", args.channel,
+        range.start as f64 / sample_rate, range.end as f64 / sample_rate, selected.len(),
+        band.map_or(String::new(), |b| format!(", {b} filtered")));
+    let peak = selected.iter().fold(0.0f64, |m, x| m.max(x.abs()));
+    println!("  RMS {:.4} ({:.1} dBFS), peak {:.4} ({:.1} dBFS)",
+        rms(&selected), 20.0 * rms(&selected).log10(), peak, 20.0 * peak.log10());
+
+    let result = analyse(&selected, sample_rate);
+    println!("  Dominant frequency: {:.2} Hz", result.dominant_freq_hz);
+    println!("\n  Top {} frequency bins by magnitude:", args.top);
+    let mut indexed: Vec<(usize, f64)> = result.magnitudes.iter().copied().enumerate().collect();
+    indexed.sort_by(|a, b| b.1.total_cmp(&a.1));
+    for (bin, mag) in indexed.iter().take(args.top) {
+        let freq = *bin as f64 * sample_rate / selected.len() as f64;
+        println!("    bin {:>7} → {:>10.2} Hz   magnitude {:.6}", bin, freq, mag);
+    }
+
+    if args.spectrogram.is_some() {
+        let config = StftConfig { frame_len: 1024, hop: 256, fft_size: 1024, ..Default::default() };
+        write_spectrogram(&stft(&selected, sample_rate, &config)?.spectrogram(), args)?;
+    }
+    if let Some(out) = &args.output {
+        let channels = wav.channels.iter().map(|c| process(&c[range.clone()])).collect::<Result<Vec<_>, _>>()?;
+        let format = args.format.map_or(wav.format, SampleFormat::from);
+        Wav { sample_rate: wav.sample_rate, format, channels }.write_file(out)?;
+        println!("\n  Wrote {} ({format})", out.display());
+    }
+    Ok(())
+}
+
+fn write_spectrogram(spec: &Spectrogram, args: &Args) -> Result<(), Box<dyn Error>> {
+    if let Some(path) = &args.spectrogram {
+        let out = BufWriter::new(File::create(path)?);
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("pgm") => spec.write_pgm(out, args.range_db)?,
+            _           => spec.write_csv(out)?,
+        }
+        println!("  Spectrogram written to {}", path.display());
+    }
+    Ok(())
+}
+
+fn ecg_demo() {
     let sample_rate = 1000.0_f64; // 1 kHz
     let n = 2048;
 
@@ -35,4 +194,73 @@ fn main() {
         let freq = *bin as f64 * sample_rate / n as f64;
         println!("    bin {:>4} → {:>8.4} Hz   magnitude {:.6}", bin, freq, mag);
     }
+
+    // Remove the mains hum, keep the heartbeat and respiration
+    println!("\n=== Filtering out the 50 Hz mains interference ===\n");
+    let fir = fir_design(201, Band::LowPass(25.0), sample_rate, Window::Hamming).expect("valid FIR design");
+    let butter = butterworth(4, Band::LowPass(25.0), sample_rate).expect("valid Butterworth design");
+    let cheby = chebyshev1(5, 0.5, Band::LowPass(25.0), sample_rate).expect("valid Chebyshev design");
+    let notch = butterworth(2, Band::BandStop(45.0, 55.0), sample_rate).expect("valid band-stop design");
+
+    let probes = [1.2, 25.0, 50.0, 100.0];
+    let db = |g: f64| 20.0 * g.max(1e-12).log10();
+    println!("  Gain (dB) at      {}", probes.iter().map(|f| format!("{:>8.1} Hz", f)).collect::<String>());
+    let fir_gain = freqz(&fir, &[1.0], &probes, sample_rate);
+    let rows = [
+        ("FIR, 201 taps", fir_gain),
+        ("Butterworth 4", butter.response(&probes, sample_rate)),
+        ("Chebyshev 5", cheby.response(&probes, sample_rate)),
+        ("Band-stop 2×2", notch.response(&probes, sample_rate)),
+    ];
+    for (label, response) in &rows {
+        println!("    {:<16}{}", label, response.iter().map(|h| format!("{:>11.2}", db(h.norm()))).collect::<String>());
+    }
+
+    let clean: Vec<f64> = heartbeat.iter().zip(&respiration).map(|(h, r)| h + 0.3 * r).collect();
+    let error = |y: &[f64]| {
+        let diff: Vec<f64> = y[200..n - 200].iter().zip(&clean[200..n - 200]).map(|(a, b)| a - b).collect();
+        rms(&diff)
+    };
+    println!("\n  RMS error against the clean signal (edges excluded):");
+    println!("    {:<30} {:.5}", "unfiltered", error(&signal));
+    println!("    {:<30} {:.5}", "FIR, causal (100-sample delay)", error(&lfilter(&fir, &[1.0], &signal).expect("valid taps")));
+    println!("    {:<30} {:.5}", "Butterworth, causal", error(&butter.filter(&signal)));
+    println!("    {:<30} {:.5}", "Butterworth, zero-phase", error(&butter.filtfilt(&signal).expect("signal long enough")));
+    println!("    {:<30} {:.5}", "Chebyshev, zero-phase", error(&cheby.filtfilt(&signal).expect("signal long enough")));
+    println!("    {:<30} {:.5}", "Band-stop, zero-phase", error(&notch.filtfilt(&signal).expect("signal long enough")));
+
+}
+
+fn vibration_demo(args: &Args) -> Result<(), Box<dyn Error>> {
+    let sample_rate = 2048.0;
+    let n = 4 * 2048;
+    println!("\n=== Vibration log: a bearing fault appears after 2 s ===\n");
+
+    // Shaft at 29.5 Hz throughout; a 157 Hz fault tone from t = 2 s
+    let shaft = sine_wave(29.5, sample_rate, n);
+    let fault = sine_wave(157.0, sample_rate, n);
+    let signal: Vec<f64> = (0..n).map(|i| shaft[i] + if i >= n / 2 { 0.2 * fault[i] } else { 0.0 }).collect();
+
+    let config = StftConfig { frame_len: 512, hop: 128, fft_size: 1024, ..Default::default() };
+    let transform = stft(&signal, sample_rate, &config)?;
+    let spec = transform.spectrogram();
+    println!("  {} frames of {} bins: {:.3} s apart, {:.1} Hz bins, {} window",
+        spec.times.len(), spec.frequencies.len(), config.hop as f64 / sample_rate,
+        sample_rate / config.fft_size as f64, config.window);
+
+    // Loudest bin between 140 and 175 Hz, every half second
+    let band: Vec<usize> = (0..spec.frequencies.len()).filter(|&k| (140.0..175.0).contains(&spec.frequencies[k])).collect();
+    println!("\n    {:>6}  {:>10}  {:>14}", "time s", "peak Hz", "140-175 Hz dB");
+    for (m, (t, peak)) in spec.times.iter().zip(spec.peak_frequencies()).enumerate() {
+        if m % 8 == 0 && m > 0 {
+            let level = band.iter().map(|&k| spec.db[m][k]).fold(f64::NEG_INFINITY, f64::max);
+            println!("    {:>6.2}  {:>10.1}  {:>14.1}", t, peak, level);
+        }
+    }
+
+    let back = istft(&transform)?;
+    let worst = back.iter().zip(&signal).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+    println!("\n  Inverse STFT (overlap-add) reconstruction error: {worst:.1e}");
+
+    write_spectrogram(&spec, args)
 }
@@ -0,0 +1,322 @@
+//! Short-time Fourier transform: how the spectrum changes over time.
+//!
+//! The signal is cut into overlapping frames, `hop` samples apart. Each frame
+//! is windowed, zero-padded to `fft_size` and transformed. Frame m is centred
+//! on sample m · hop, so the signal is padded with zeros by half a frame at
+//! each end.
+//!
+//! Trade-offs:
+//! - A long frame resolves close frequencies but smears fast changes.
+//! - A short frame resolves fast changes but smears close frequencies.
+//! - A smaller hop adds frames without sharpening either.
+//! - Zero padding only interpolates between bins.
+//!
+//! The inverse overlap-adds the windowed frames and divides by Σ w², which
+//! undoes the analysis exactly wherever the frames overlap.
+
+use std::io::{self, Write};
+
+use rustfft::{FftPlanner, num_complex::Complex};
+
+use crate::window::Window;
+use crate::SignalError;
+
+/// Frame layout for `stft`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StftConfig {
+    pub window: Window,
+    /// Samples per frame (the window length)
+    pub frame_len: usize,
+    /// Samples between frame starts; at most `frame_len`
+    pub hop: usize,
+    /// Transform length, at least `frame_len`; the frame is zero-padded to it
+    pub fft_size: usize,
+}
+
+impl Default for StftConfig {
+    fn default() -> Self {
+        Self { window: Window::Hann, frame_len: 256, hop: 64, fft_size: 256 }
+    }
+}
+
+impl StftConfig {
+    /// The frame layout both directions rely on: 0 < hop <= frame_len <= fft_size
+    fn validate(&self) -> Result<(), SignalError> {
+        if self.frame_len == 0 || self.hop == 0 || self.hop > self.frame_len {
+            return Err(SignalError::InvalidParameter("need 0 < hop <= frame_len"));
+        }
+        if self.fft_size < self.frame_len { return Err(SignalError::InvalidParameter("fft_size must be at least frame_len")); }
+        Ok(())
+    }
+}
+
+/// A complex STFT: `frames[m][k]` is bin k (0..=fft_size/2) of frame m
+#[derive(Clone, Debug)]
+pub struct Stft {
+    pub frames: Vec<Vec<Complex<f64>>>,
+    pub config: StftConfig,
+    pub sample_rate: f64,
+    /// Length of the analysed signal, so the inverse can trim its padding
+    pub signal_len: usize,
+}
+
+/// Spectrogram in dB: `db[m][k]` is the amplitude of frequency k in frame m,
+/// scaled so that a unit-amplitude sine on a bin reads 0 dB
+#[derive(Clone, Debug)]
+pub struct Spectrogram {
+    /// Frame centres in seconds
+    pub times: Vec<f64>,
+    /// Bin centres in Hz
+    pub frequencies: Vec<f64>,
+    pub db: Vec<Vec<f64>>,
+}
+
+/// Short-time Fourier transform of `signal`
+pub fn stft(signal: &[f64], sample_rate: f64, config: &StftConfig) -> Result<Stft, SignalError> {
+    config.validate()?;
+    let StftConfig { window, frame_len, hop, fft_size } = *config;
+    if !(sample_rate > 0.0 && sample_rate.is_finite()) {
+        return Err(SignalError::InvalidParameter("sample rate must be positive and finite"));
+    }
+    if signal.is_empty() { return Err(SignalError::SignalTooShort { needed: 1, got: 0 }); }
+
+    // Enough frames that the last is centred at or past the final sample
+    let count = signal.len().div_ceil(hop) + 1;
+    let half = frame_len / 2;
+    let sample = |i: usize| i.checked_sub(half).and_then(|j| signal.get(j)).copied().unwrap_or(0.0);
+    let w = window.periodic(frame_len);
+    let fft = FftPlanner::new().plan_fft_forward(fft_size);
+    let frames = (0..count).map(|m| {
+        let mut buf = vec![Complex::new(0.0, 0.0); fft_size];
+        for (i, slot) in buf.iter_mut().take(frame_len).enumerate() {
+            *slot = Complex::new(w[i] * sample(m * hop + i), 0.0);
+        }
+        fft.process(&mut buf);
+        buf.truncate(fft_size / 2 + 1);
+        buf
+    }).collect();
+    Ok(Stft { frames, config: *config, sample_rate, signal_len: signal.len() })
+}
+
+/// Inverse STFT by weighted overlap-add. Recovers the original signal to
+/// rounding error if the frames are unchanged; after editing them it gives
+/// the signal whose STFT is closest to the edited one in least squares.
+/// Samples no window reaches (a `hop` as long as a tapered frame) come back
+/// as zero. `stft.config` is public, so its layout is checked again here.
+pub fn istft(stft: &Stft) -> Result<Vec<f64>, SignalError> {
+    stft.config.validate()?;
+    let StftConfig { window, frame_len, hop, fft_size } = stft.config;
+    let bins = fft_size / 2 + 1;
+    if stft.frames.iter().any(|f| f.len() != bins) {
+        return Err(SignalError::InvalidParameter("every frame must have fft_size / 2 + 1 bins"));
+    }
+    let w = window.periodic(frame_len);
+    let span = stft.frames.len().saturating_sub(1) * hop + frame_len;
+    let (mut sum, mut weight) = (vec![0.0; span], vec![0.0; span]);
+    let ifft = FftPlanner::new().plan_fft_inverse(fft_size);
+    for (m, frame) in stft.frames.iter().enumerate() {
+        // Rebuild the negative frequencies by conjugate symmetry
+        let mut buf: Vec<Complex<f64>> = (0..fft_size)
+            .map(|k| if k < bins { frame[k] } else { frame[fft_size - k].conj() })
+            .collect();
+        ifft.process(&mut buf);
+        for i in 0..frame_len {
+            sum[m * hop + i] += w[i] * buf[i].re / fft_size as f64;
+            weight[m * hop + i] += w[i] * w[i];
+        }
+    }
+    let floor = weight.iter().fold(0.0f64, |a, &b| a.max(b)) * 1e-10;
+    let half = frame_len / 2;
+    Ok((half..half + stft.signal_len)
+        .map(|i| if i < span && weight[i] > floor { sum[i] / weight[i] } else { 0.0 })
+        .collect())
+}
+
+impl Stft {
+    /// Centre of each frame in seconds
+    pub fn times(&self) -> Vec<f64> {
+        (0..self.frames.len()).map(|m| (m * self.config.hop) as f64 / self.sample_rate).collect()
+    }
+
+    /// Centre of each bin in Hz
+    pub fn frequencies(&self) -> Vec<f64> {
+        (0..=self.config.fft_size / 2).map(|k| k as f64 * self.sample_rate / self.config.fft_size as f64).collect()
+    }
+
+    /// Amplitude in dB, floored at -200 dB so silence stays finite
+    pub fn spectrogram(&self) -> Spectrogram {
+        let coherent_gain: f64 = self.config.window.periodic(self.config.frame_len).iter().sum();
+        let nyquist = self.config.fft_size / 2;
+        let db = self.frames.iter().map(|frame| {
+            frame.iter().enumerate().map(|(k, c)| {
+                // Positive and negative frequencies each hold half a sine's amplitude
+                let one_sided = if k == 0 || (k == nyquist && self.config.fft_size.is_multiple_of(2)) { 1.0 } else { 2.0 };
+                20.0 * (one_sided * c.norm() / coherent_gain).max(1e-10).log10()
+            }).collect()
+        }).collect();
+        Spectrogram { times: self.times(), frequencies: self.frequencies(), db }
+    }
+}
+
+impl Spectrogram {
+    /// The frequency with the most energy in each frame, ignoring DC
+    pub fn peak_frequencies(&self) -> Vec<f64> {
+        self.db.iter().map(|row| {
+            let k = (1..row.len()).max_by(|&a, &b| row[a].total_cmp(&row[b])).unwrap_or(0);
+            self.frequencies[k]
+        }).collect()
+    }
+
+    /// One row per frame: `time_s` then one dB column per frequency, with the
+    /// frequencies in Hz as the header
+    pub fn write_csv(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "time_s")?;
+        for f in &self.frequencies { write!(out, ",{f}")?; }
+        writeln!(out)?;
+        for (t, row) in self.times.iter().zip(&self.db) {
+            write!(out, "{t}")?;
+            for v in row { write!(out, ",{v:.2}")?; }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// Binary greyscale PGM (P5): time runs left to right, frequency bottom
+    /// to top. The loudest point is white, and anything `range_db` or more
+    /// below it is black. `range_db` must be positive and finite.
+    pub fn write_pgm(&self, mut out: impl Write, range_db: f64) -> io::Result<()> {
+        if !(range_db > 0.0 && range_db.is_finite()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("range_db must be positive and finite, got {range_db}")));
+        }
+        let (width, height) = (self.db.len(), self.frequencies.len());
+        let top = self.db.iter().flatten().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        write!(out, "P5\n{width} {height}\n255\n")?;
+        let mut pixels = Vec::with_capacity(width * height);
+        for k in (0..height).rev() {
+            for row in &self.db {
+                let level = 1.0 - (top - row[k]) / range_db;
+                pixels.push((level.clamp(0.0, 1.0) * 255.0).round() as u8);
+            }
+        }
+        out.write_all(&pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    const FS: f64 = 1024.0;
+
+    fn tone(freq: f64, n: usize) -> Vec<f64> {
+        (0..n).map(|i| (2.0 * PI * freq * i as f64 / FS).sin()).collect()
+    }
+
+    #[test]
+    fn a_steady_tone_reads_zero_db_at_its_bin() {
+        let stft = stft(&tone(100.0, 4096), FS, &StftConfig::default()).unwrap();
+        let spec = stft.spectrogram();
+        assert_eq!(spec.frequencies.len(), 129);
+        assert_eq!(stft.frames.len(), 4096 / 64 + 1);
+        // Bins are 4 Hz wide, so 100 Hz is bin 25; skip the half-empty edge frames
+        for row in &spec.db[4..spec.db.len() - 4] {
+            assert!(row[25].abs() < 1e-9, "{:.2e} dB", row[25]);
+        }
+        assert!(spec.peak_frequencies()[4..60].iter().all(|&f| f == 100.0));
+    }
+
+    #[test]
+    fn frequency_changes_show_up_at_the_right_time() {
+        // 52 Hz for one second, then 200 Hz
+        let signal: Vec<f64> = tone(52.0, 1024).into_iter().chain(tone(200.0, 1024)).collect();
+        let spec = stft(&signal, FS, &StftConfig::default()).unwrap().spectrogram();
+        for (t, f) in spec.times.iter().zip(spec.peak_frequencies()) {
+            if *t < 0.85 { assert_eq!(f, 52.0, "at {t} s"); }
+            if *t > 1.15 && *t < 1.85 { assert_eq!(f, 200.0, "at {t} s"); }
+        }
+    }
+
+    #[test]
+    fn overlap_add_reconstructs_the_signal() {
+        let signal: Vec<f64> = (0..1001).map(|i| ((i * 37) % 101) as f64 / 50.0 - 1.0).collect();
+        let configs = [
+            StftConfig::default(),
+            StftConfig { window: Window::Hamming, frame_len: 100, hop: 50, fft_size: 128 },
+            StftConfig { window: Window::Blackman, frame_len: 64, hop: 16, fft_size: 64 },
+            StftConfig { window: Window::Rectangular, frame_len: 32, hop: 32, fft_size: 32 },
+        ];
+        for config in configs {
+            let back = istft(&stft(&signal, FS, &config).unwrap()).unwrap();
+            assert_eq!(back.len(), signal.len());
+            let worst = back.iter().zip(&signal).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+            assert!(worst < 1e-12, "{config:?}: {worst:e}");
+        }
+    }
+
+    #[test]
+    fn editing_frames_filters_the_signal() {
+        // Zero every bin above 150 Hz and the 300 Hz component is gone
+        let signal: Vec<f64> = tone(100.0, 2048).iter().zip(tone(300.0, 2048)).map(|(a, b)| a + b).collect();
+        let mut spectrum = stft(&signal, FS, &StftConfig::default()).unwrap();
+        for frame in &mut spectrum.frames {
+            for bin in frame.iter_mut().skip(150 * 256 / 1024) { *bin = Complex::new(0.0, 0.0); }
+        }
+        let low = istft(&spectrum).unwrap();
+        let reference = tone(100.0, 2048);
+        let worst = low[256..1792].iter().zip(&reference[256..1792]).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+        assert!(worst < 1e-3, "{worst}");
+        spectrum.frames[0].pop();
+        assert!(matches!(istft(&spectrum), Err(SignalError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn csv_and_pgm_have_the_expected_layout() {
+        let spec = stft(&tone(256.0, 512), FS, &StftConfig { frame_len: 64, hop: 32, fft_size: 64, ..Default::default() })
+            .unwrap()
+            .spectrogram();
+        let mut csv = Vec::new();
+        spec.write_csv(&mut csv).unwrap();
+        let text = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 1 + spec.times.len());
+        assert!(lines[0].starts_with("time_s,0,16,32,"));
+        assert_eq!(lines[1].split(',').count(), 34);
+
+        let mut pgm = Vec::new();
+        spec.write_pgm(&mut pgm, 60.0).unwrap();
+        let header = format!("P5\n{} 33\n255\n", spec.times.len());
+        assert!(pgm.starts_with(header.as_bytes()));
+        let pixels = &pgm[header.len()..];
+        assert_eq!(pixels.len(), spec.times.len() * 33);
+        // 256 Hz is bin 16, drawn on row 32 - 16 counting from the top
+        let row = &pixels[16 * spec.times.len()..17 * spec.times.len()];
+        assert_eq!(row[spec.times.len() / 2], 255);
+        assert_eq!(pixels[spec.times.len() / 2], 0);
+
+        for range_db in [0.0, -20.0, f64::NAN, f64::INFINITY] {
+            let mut out = Vec::new();
+            let err = spec.write_pgm(&mut out, range_db).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            assert!(out.is_empty());
+        }
+    }
+
+    #[test]
+    fn invalid_layouts_are_rejected() {
+        let x = [0.0; 100];
+        for config in [
+            StftConfig { hop: 0, ..Default::default() },
+            StftConfig { hop: 300, ..Default::default() },
+            StftConfig { fft_size: 128, ..Default::default() },
+            StftConfig { frame_len: 512, ..Default::default() },
+        ] {
+            assert!(matches!(stft(&x, FS, &config), Err(SignalError::InvalidParameter(_))));
+            // An Stft whose public config was edited afterwards
+            let mut edited = stft(&x, FS, &StftConfig::default()).unwrap();
+            edited.config = config;
+            assert!(matches!(istft(&edited), Err(SignalError::InvalidParameter(_))), "{config:?}");
+        }
+        assert_eq!(stft(&[], FS, &StftConfig::default()).unwrap_err(), SignalError::SignalTooShort { needed: 1, got: 0 });
+    }
+}
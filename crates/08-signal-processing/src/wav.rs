@@ -0,0 +1,411 @@
+//! Reading and writing PCM WAV files without an audio library.
+//!
+//! A WAV file is a RIFF container: a `fmt ` chunk describing the samples, a
+//! `data` chunk holding them interleaved frame by frame, and any number of
+//! other chunks (`LIST`, `cue `, ...) that are skipped. Supported encodings:
+//! unsigned 8-bit, signed 16/24/32-bit little-endian integers and 32-bit
+//! IEEE float, including the `WAVE_FORMAT_EXTENSIBLE` header that
+//! multichannel and high-resolution recorders write.
+//!
+//! Samples are held as f64 in [-1, 1): integers are divided by 2^(bits-1),
+//! so an integer file read and written back in its own format is bit-exact.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::ops::Range;
+use std::path::Path;
+
+const PCM: u16 = 1;
+const IEEE_FLOAT: u16 = 3;
+const EXTENSIBLE: u16 = 0xFFFE;
+
+#[derive(Debug)]
+pub enum WavError {
+    Io(io::Error),
+    /// Not a RIFF/WAVE file, or one whose chunks contradict each other
+    Malformed(String),
+    /// A valid WAV in an encoding this module does not decode
+    Unsupported(String),
+}
+
+impl fmt::Display for WavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WavError::Io(e)            => write!(f, "{e}"),
+            WavError::Malformed(what)  => write!(f, "malformed WAV: {what}"),
+            WavError::Unsupported(what) => write!(f, "unsupported WAV: {what}"),
+        }
+    }
+}
+
+impl std::error::Error for WavError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WavError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for WavError {
+    fn from(e: io::Error) -> Self { WavError::Io(e) }
+}
+
+fn malformed(what: impl Into<String>) -> WavError {
+    WavError::Malformed(what.into())
+}
+
+/// How each sample is stored on disk
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// Unsigned, 128 is silence
+    Int8,
+    Int16,
+    Int24,
+    Int32,
+    Float32,
+}
+
+impl SampleFormat {
+    pub fn bits(&self) -> u16 {
+        match self {
+            SampleFormat::Int8    => 8,
+            SampleFormat::Int16   => 16,
+            SampleFormat::Int24   => 24,
+            SampleFormat::Int32   => 32,
+            SampleFormat::Float32 => 32,
+        }
+    }
+
+    fn bytes(&self) -> usize { self.bits() as usize / 8 }
+
+    fn decode(&self, b: &[u8]) -> f64 {
+        match self {
+            SampleFormat::Int8    => (b[0] as f64 - 128.0) / 128.0,
+            SampleFormat::Int16   => i16::from_le_bytes([b[0], b[1]]) as f64 / 32_768.0,
+            // Shift into the top of an i32 so the sign bit lands in place
+            SampleFormat::Int24   => (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f64 / 8_388_608.0,
+            SampleFormat::Int32   => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64 / 2_147_483_648.0,
+            SampleFormat::Float32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+        }
+    }
+
+    /// Integers are rounded and clipped to their range; floats pass through
+    fn encode(&self, x: f64, out: &mut Vec<u8>) {
+        let scale = |bits: i32| {
+            let full = 2f64.powi(bits - 1);
+            (x * full).round().clamp(-full, full - 1.0) as i64
+        };
+        match self {
+            SampleFormat::Int8    => out.push((scale(8) + 128) as u8),
+            SampleFormat::Int16   => out.extend_from_slice(&(scale(16) as i16).to_le_bytes()),
+            SampleFormat::Int24   => out.extend_from_slice(&(scale(24) as i32).to_le_bytes()[..3]),
+            SampleFormat::Int32   => out.extend_from_slice(&(scale(32) as i32).to_le_bytes()),
+            SampleFormat::Float32 => out.extend_from_slice(&(x as f32).to_le_bytes()),
+        }
+    }
+}
+
+impl fmt::Display for SampleFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SampleFormat::Float32 => write!(f, "32-bit float"),
+            other                 => write!(f, "{}-bit PCM", other.bits()),
+        }
+    }
+}
+
+/// Decoded audio: `channels[c][i]` is sample i of channel c
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wav {
+    pub sample_rate: u32,
+    pub format: SampleFormat,
+    pub channels: Vec<Vec<f64>>,
+}
+
+impl Wav {
+    /// Samples per channel
+    pub fn len(&self) -> usize {
+        self.channels.first().map_or(0, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        self.len() as f64 / self.sample_rate as f64
+    }
+
+    /// Sample indices covering `start..end` seconds, clipped to the file;
+    /// `end: None` runs to the end. None if that leaves nothing.
+    pub fn sample_range(&self, start: f64, end: Option<f64>) -> Option<Range<usize>> {
+        let end = end.unwrap_or(f64::INFINITY);
+        if !(start >= 0.0 && end > start) { return None; }
+        let to_index = |t: f64| (t * self.sample_rate as f64).round().min(self.len() as f64) as usize;
+        let range = to_index(start)..to_index(end);
+        (!range.is_empty()).then_some(range)
+    }
+
+    pub fn read(mut reader: impl Read) -> Result<Wav, WavError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        parse(&bytes)
+    }
+
+    pub fn read_file(path: impl AsRef<Path>) -> Result<Wav, WavError> {
+        Wav::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Write as canonical PCM (or IEEE float) WAV in `self.format`
+    pub fn write(&self, mut writer: impl Write) -> Result<(), WavError> {
+        let n = self.len();
+        if self.channels.is_empty() || self.channels.iter().any(|c| c.len() != n) {
+            return Err(malformed("need at least one channel, all of the same length"));
+        }
+        let channels = u16::try_from(self.channels.len()).map_err(|_| malformed("too many channels"))?;
+        let block_align = u16::try_from(channels as usize * self.format.bytes()).map_err(|_| malformed("too many channels"))?;
+        let byte_rate = self.sample_rate.checked_mul(block_align as u32).ok_or_else(|| malformed("sample rate too high for the byte-rate field"))?;
+        let data_len = n.checked_mul(block_align as usize).and_then(|len| u32::try_from(len).ok())
+            .ok_or_else(|| malformed("audio too long for a 4 GB WAV"))?;
+        let float = self.format == SampleFormat::Float32;
+
+        let mut fmt = Vec::with_capacity(18);
+        fmt.extend_from_slice(&(if float { IEEE_FLOAT } else { PCM }).to_le_bytes());
+        fmt.extend_from_slice(&channels.to_le_bytes());
+        fmt.extend_from_slice(&self.sample_rate.to_le_bytes());
+        fmt.extend_from_slice(&byte_rate.to_le_bytes());
+        fmt.extend_from_slice(&block_align.to_le_bytes());
+        fmt.extend_from_slice(&self.format.bits().to_le_bytes());
+        // Non-PCM formats carry an (empty) extension size and a fact chunk
+        if float { fmt.extend_from_slice(&0u16.to_le_bytes()); }
+
+        let mut out = Vec::with_capacity(60 + data_len as usize);
+        out.extend_from_slice(b"RIFF\0\0\0\0WAVE");
+        write_chunk(&mut out, b"fmt ", &fmt);
+        if float { write_chunk(&mut out, b"fact", &(n as u32).to_le_bytes()); }
+        let mut data = Vec::with_capacity(data_len as usize);
+        for i in 0..n {
+            for channel in &self.channels { self.format.encode(channel[i], &mut data); }
+        }
+        write_chunk(&mut out, b"data", &data);
+        let riff_len = (out.len() - 8) as u32;
+        out[4..8].copy_from_slice(&riff_len.to_le_bytes());
+        writer.write_all(&out)?;
+        Ok(())
+    }
+
+    pub fn write_file(&self, path: impl AsRef<Path>) -> Result<(), WavError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn write_chunk(out: &mut Vec<u8>, id: &[u8; 4], body: &[u8]) {
+    out.extend_from_slice(id);
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    out.extend_from_slice(body);
+    // Chunks are word aligned
+    if body.len() % 2 == 1 { out.push(0); }
+}
+
+fn u16_at(b: &[u8], at: usize) -> u16 { u16::from_le_bytes([b[at], b[at + 1]]) }
+fn u32_at(b: &[u8], at: usize) -> u32 { u32::from_le_bytes([b[at], b[at + 1], b[at + 2], b[at + 3]]) }
+
+fn parse(bytes: &[u8]) -> Result<Wav, WavError> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(malformed("missing RIFF/WAVE header"));
+    }
+    let mut format = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let (id, size) = (&bytes[pos..pos + 4], u32_at(bytes, pos + 4) as usize);
+        let body = pos + 8;
+        match id {
+            b"fmt " => {
+                if size < 16 || body + size > bytes.len() { return Err(malformed("fmt chunk too short")); }
+                format = Some(parse_format(&bytes[body..body + size])?);
+            }
+            b"data" => {
+                let (channels, sample_rate, sample, frame) = format.ok_or_else(|| malformed("data chunk before fmt chunk"))?;
+                // Streaming writers leave the size at 0 or 0xFFFFFFFF; take what is there
+                let available = bytes.len() - body;
+                let size = if size == 0 || size > available { available } else { size };
+                let frames = size / frame;
+                let mut decoded = vec![Vec::with_capacity(frames); channels];
+                for f in 0..frames {
+                    for (c, channel) in decoded.iter_mut().enumerate() {
+                        let at = body + f * frame + c * sample.bytes();
+                        channel.push(sample.decode(&bytes[at..at + sample.bytes()]));
+                    }
+                }
+                return Ok(Wav { sample_rate, format: sample, channels: decoded });
+            }
+            _ => {}
+        }
+        pos = body + size + size % 2;
+    }
+    Err(malformed("no data chunk"))
+}
+
+/// (channels, sample rate, encoding, bytes per frame) from a fmt chunk body.
+/// The frame size is the header's block_align, which may include padding
+/// after the samples, so it is the stride between frames.
+fn parse_format(fmt: &[u8]) -> Result<(usize, u32, SampleFormat, usize), WavError> {
+    let mut tag = u16_at(fmt, 0);
+    let channels = u16_at(fmt, 2) as usize;
+    let sample_rate = u32_at(fmt, 4);
+    let block_align = u16_at(fmt, 12) as usize;
+    let bits = u16_at(fmt, 14);
+    if tag == EXTENSIBLE {
+        // The real format tag opens the sub-format GUID at offset 24
+        if fmt.len() < 26 { return Err(malformed("extensible fmt chunk too short")); }
+        tag = u16_at(fmt, 24);
+    }
+    if channels == 0 || sample_rate == 0 { return Err(malformed("zero channels or sample rate")); }
+    let sample = match (tag, bits) {
+        (PCM, 8)         => SampleFormat::Int8,
+        (PCM, 16)        => SampleFormat::Int16,
+        (PCM, 24)        => SampleFormat::Int24,
+        (PCM, 32)        => SampleFormat::Int32,
+        (IEEE_FLOAT, 32) => SampleFormat::Float32,
+        (PCM, _) | (IEEE_FLOAT, _) => return Err(WavError::Unsupported(format!("{bits}-bit samples"))),
+        _ => return Err(WavError::Unsupported(format!("format tag {tag:#06x} (compressed audio)"))),
+    };
+    if block_align < channels * sample.bytes() {
+        return Err(malformed(format!("block_align {block_align} is smaller than {channels} × {}-byte samples", sample.bytes())));
+    }
+    Ok((channels, sample_rate, sample, block_align))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stereo(format: SampleFormat) -> Wav {
+        // Multiples of 1/128 are exact in every format
+        let left: Vec<f64> = (0..50).map(|i| (i as f64 - 25.0) / 128.0).collect();
+        let right: Vec<f64> = left.iter().map(|v| -v).collect();
+        Wav { sample_rate: 8000, format, channels: vec![left, right] }
+    }
+
+    fn round_trip(wav: &Wav) -> (Vec<u8>, Wav) {
+        let mut bytes = Vec::new();
+        wav.write(&mut bytes).unwrap();
+        let back = Wav::read(&bytes[..]).unwrap();
+        (bytes, back)
+    }
+
+    #[test]
+    fn every_format_round_trips_exactly() {
+        for format in [SampleFormat::Int8, SampleFormat::Int16, SampleFormat::Int24, SampleFormat::Int32, SampleFormat::Float32] {
+            let wav = stereo(format);
+            assert_eq!(round_trip(&wav).1, wav, "{format}");
+        }
+        let three = Wav { sample_rate: 48_000, format: SampleFormat::Int24, channels: vec![vec![0.5; 4], vec![-0.25; 4], vec![0.0; 4]] };
+        assert_eq!(round_trip(&three).1, three);
+    }
+
+    #[test]
+    fn canonical_header_has_the_expected_fields() {
+        let (bytes, _) = round_trip(&stereo(SampleFormat::Int16));
+        assert_eq!(bytes.len(), 44 + 50 * 4);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u32_at(&bytes, 4) as usize, bytes.len() - 8);
+        assert_eq!(&bytes[12..16], b"fmt ");
+        assert_eq!((u16_at(&bytes, 20), u16_at(&bytes, 22), u32_at(&bytes, 24)), (PCM, 2, 8000));
+        assert_eq!((u32_at(&bytes, 28), u16_at(&bytes, 32), u16_at(&bytes, 34)), (32_000, 4, 16));
+        assert_eq!(&bytes[36..40], b"data");
+        // First frame: left -25/128 = -6400, right 6400
+        assert_eq!(i16::from_le_bytes([bytes[44], bytes[45]]), -6400);
+        assert_eq!(i16::from_le_bytes([bytes[46], bytes[47]]), 6400);
+    }
+
+    #[test]
+    fn integer_encodings_decode_and_clip_correctly() {
+        assert_eq!(SampleFormat::Int8.decode(&[128]), 0.0);
+        assert_eq!(SampleFormat::Int8.decode(&[0]), -1.0);
+        assert_eq!(SampleFormat::Int24.decode(&[0x00, 0x00, 0x80]), -1.0);
+        assert_eq!(SampleFormat::Int24.decode(&[0xFF, 0xFF, 0xFF]), -1.0 / 8_388_608.0);
+        assert_eq!(SampleFormat::Int32.decode(&[0, 0, 0, 0x40]), 0.5);
+        let mut out = Vec::new();
+        SampleFormat::Int16.encode(1.5, &mut out);
+        SampleFormat::Int16.encode(-1.5, &mut out);
+        SampleFormat::Int8.encode(1.0, &mut out);
+        assert_eq!(out, [0xFF, 0x7F, 0x00, 0x80, 0xFF]);
+    }
+
+    #[test]
+    fn extensible_headers_and_unknown_chunks_are_handled() {
+        let mut fmt = Vec::new();
+        for v in [EXTENSIBLE, 1] { fmt.extend_from_slice(&v.to_le_bytes()); }
+        fmt.extend_from_slice(&44_100u32.to_le_bytes());
+        fmt.extend_from_slice(&(44_100u32 * 4).to_le_bytes());
+        for v in [4u16, 32, 22, 32] { fmt.extend_from_slice(&v.to_le_bytes()); }
+        fmt.extend_from_slice(&4u32.to_le_bytes());
+        fmt.extend_from_slice(&IEEE_FLOAT.to_le_bytes());
+        fmt.extend_from_slice(&[0; 14]);
+        let mut bytes = b"RIFF\0\0\0\0WAVE".to_vec();
+        write_chunk(&mut bytes, b"LIST", b"odd");
+        write_chunk(&mut bytes, b"fmt ", &fmt);
+        let samples: Vec<u8> = [0.25f32, -0.75].iter().flat_map(|v| v.to_le_bytes()).collect();
+        write_chunk(&mut bytes, b"data", &samples);
+        let wav = Wav::read(&bytes[..]).unwrap();
+        assert_eq!((wav.format, wav.sample_rate, wav.len()), (SampleFormat::Float32, 44_100, 2));
+        assert_eq!(wav.channels, vec![vec![0.25, -0.75]]);
+    }
+
+    #[test]
+    fn broken_and_unsupported_files_are_rejected() {
+        assert!(matches!(Wav::read(&b"RIFX\0\0\0\0WAVE"[..]), Err(WavError::Malformed(_))));
+        assert!(matches!(Wav::read(&b"RIFF\0\0\0\0WAVE"[..]), Err(WavError::Malformed(_))));
+        let (mut bytes, _) = round_trip(&stereo(SampleFormat::Int16));
+        bytes[34] = 12;
+        assert!(matches!(Wav::read(&bytes[..]), Err(WavError::Unsupported(_))));
+        bytes[34] = 16;
+        bytes[20] = 2; // ADPCM
+        assert!(matches!(Wav::read(&bytes[..]), Err(WavError::Unsupported(_))));
+        // A truncated data chunk keeps the whole frames that arrived
+        let (bytes, _) = round_trip(&stereo(SampleFormat::Int16));
+        assert_eq!(Wav::read(&bytes[..bytes.len() - 3]).unwrap().len(), 49);
+    }
+
+    #[test]
+    fn block_align_sets_the_frame_stride() {
+        // Mono 16-bit in 4-byte frames: two bytes of padding after each sample
+        let mut fmt = Vec::new();
+        for v in [PCM, 1] { fmt.extend_from_slice(&v.to_le_bytes()); }
+        fmt.extend_from_slice(&8000u32.to_le_bytes());
+        fmt.extend_from_slice(&32_000u32.to_le_bytes());
+        for v in [4u16, 16] { fmt.extend_from_slice(&v.to_le_bytes()); }
+        let mut bytes = b"RIFF\0\0\0\0WAVE".to_vec();
+        write_chunk(&mut bytes, b"fmt ", &fmt);
+        let data: Vec<u8> = [16_384i16, -8192, 4096].iter().flat_map(|v| [v.to_le_bytes(), [0xAA, 0xAA]].concat()).collect();
+        write_chunk(&mut bytes, b"data", &data);
+        assert_eq!(Wav::read(&bytes[..]).unwrap().channels, vec![vec![0.5, -0.25, 0.125]]);
+
+        // A block_align too small to hold a frame
+        bytes[32] = 1;
+        assert!(matches!(Wav::read(&bytes[..]), Err(WavError::Malformed(_))));
+    }
+
+    #[test]
+    fn header_fields_that_would_overflow_are_errors() {
+        let wav = Wav { sample_rate: u32::MAX, ..stereo(SampleFormat::Int16) };
+        assert!(matches!(wav.write(Vec::new()), Err(WavError::Malformed(_))));
+    }
+
+    #[test]
+    fn time_ranges_are_clipped_to_the_file() {
+        let wav = stereo(SampleFormat::Int16); // 50 samples at 8 kHz = 6.25 ms
+        assert_eq!(wav.sample_range(0.0, None), Some(0..50));
+        assert_eq!(wav.sample_range(0.001, Some(0.002)), Some(8..16));
+        assert_eq!(wav.sample_range(0.005, Some(1.0)), Some(40..50));
+        assert_eq!(wav.sample_range(0.002, Some(0.001)), None);
+        assert_eq!(wav.sample_range(-1.0, None), None);
+        assert_eq!(wav.sample_range(1.0, None), None);
+    }
+}
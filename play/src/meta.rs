@@ -277,6 +277,7 @@ pub const CRATES: &[CrateMeta] = &[
             "Row-major matrix storage: element (r,c) at index r*cols+c",
             "Transpose: swap rows and columns",
             "Matrix multiplication: result[i][j] = sum_k a[i][k] * b[k][j]",
+            "Cache blocking: why the naive triple loop is memory-bound, and how packing fixes it",
             "Gaussian elimination with partial pivoting for inverse + determinant",
         ],
         docs: &[
@@ -287,8 +288,8 @@ pub const CRATES: &[CrateMeta] = &[
             TestHints {
                 test_name: "identity_times_matrix",
                 hints: &[
-                    "Access element (row r, col c): `self.data[r * self.cols + c]`. Return None if self.cols != rhs.rows.",
-                    "`result[(i,j)] = (0..self.cols).map(|k| self[(i,k)] * rhs[(k,j)]).sum::<f64>()`",
+                    "matmul is provided (it is the hot path for every later crate), but write the naive version yourself first: `result[(i,j)] = (0..self.cols).map(|k| self[(i,k)] * rhs[(k,j)]).sum::<f64>()`",
+                    "Then read src/gemm.rs: packing blocks of A and B so the inner loop reads memory sequentially is what makes it faster. Measure the gap on your machine with `cargo bench -p matrix-math`.",
                 ],
             },
             TestHints {